@@ -1,24 +1,18 @@
-use ml_rs::perceptron::{Perceptron, PerceptronOptions};
-use ml_rs::MatF;
+use ml_lib::linear_perceptron::LinearPerceptron;
 
 fn main() {
-    let x_data: MatF = vec![
+    let inputs = vec![
         vec![0.0, 0.0],
         vec![0.0, 1.0],
         vec![1.0, 0.0],
         vec![1.0, 1.0],
     ];
-    let y_data = vec![-1.0, 1.0, 1.0, 1.0];
+    let outputs = vec![vec![-1.0], vec![1.0], vec![1.0], vec![1.0]];
 
-    let mut classifier = Perceptron::new(PerceptronOptions {
-        learning_rate: 1.0,
-        max_epochs: 50,
-        shuffle: true,
-        seed: 123,
-    });
+    let mut classifier = LinearPerceptron::new(2);
 
-    classifier.fit(&x_data, &y_data);
-    println!("Weights (bias first): {:?}", classifier.weights_with_bias());
-    let predictions = classifier.predict(&x_data);
-    println!("Predictions: {:?}", predictions);
+    classifier.train_classification(&inputs, &outputs, 10_000, 0.1);
+    for x in &inputs {
+        println!("x={:?}, pred={}", x, classifier.predict_class(x));
+    }
 }
@@ -0,0 +1,34 @@
+use ml_lib::cross_validation::{cross_validate, CvStrategy};
+use ml_lib::datasets;
+use ml_lib::metrics::{accuracy, mean_squared_error};
+use ml_lib::naive_multi_layer_perceptron::MyMLP;
+use ml_lib::split::class_labels;
+
+fn main() {
+    // Which hidden layer size suits Cross best?
+    let dataset = datasets::cross(0, 500, 0.0);
+    let labels = class_labels(&dataset.targets);
+
+    for hidden in [4, 8] {
+        let results = cross_validate(
+            &dataset.inputs,
+            &dataset.targets,
+            CvStrategy::StratifiedKFold { k: 5, labels: &labels, seed: 0 },
+            |inputs, outputs| {
+                let mut mlp = MyMLP::new(&[2, hidden, 1]);
+                mlp.train(inputs, outputs, true, 100_000, 0.05);
+                mlp
+            },
+            |mlp, x| mlp.predict(x, true),
+            &[("accuracy", accuracy), ("mse", mean_squared_error)],
+        );
+
+        println!("\n[2, {}, 1]", hidden);
+        for fold in &results.folds {
+            println!("  fold {}: {:.3?}", fold.fold, fold.scores);
+        }
+        for (metric, mean, std) in results.summary() {
+            println!("  {}: {:.3} ± {:.3}", metric, mean, std);
+        }
+    }
+}
@@ -0,0 +1,25 @@
+use ml_lib::datasets;
+use ml_lib::naive_multi_layer_perceptron::MyMLP;
+use ml_lib::plot::{plot_decision_boundary, PlotOptions};
+
+fn main() {
+    let dir = std::env::temp_dir();
+
+    // Binary: single tanh output, boundary at 0
+    let cross = datasets::cross(0, 500, 0.0);
+    let mut mlp = MyMLP::new(&[2, 8, 1]);
+    mlp.train(&cross.inputs, &cross.targets, true, 200_000, 0.05);
+    for file in ["cross.png", "cross.svg"] {
+        let path = dir.join(file);
+        plot_decision_boundary(&path, &cross.inputs, &cross.targets, &PlotOptions::default(), |x| mlp.predict(x, true)).unwrap();
+        println!("Written {}", path.display());
+    }
+
+    // Three classes: one output per class, boundary where the argmax changes
+    let three = datasets::three_classes(0, 500, 0.0);
+    let mut mlp = MyMLP::new(&[2, 3]);
+    mlp.train(&three.inputs, &three.targets, true, 200_000, 0.05);
+    let path = dir.join("three_classes.png");
+    plot_decision_boundary(&path, &three.inputs, &three.targets, &PlotOptions::default(), |x| mlp.predict(x, true)).unwrap();
+    println!("Written {}", path.display());
+}
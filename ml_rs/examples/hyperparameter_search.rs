@@ -0,0 +1,60 @@
+use ml_lib::datasets;
+use ml_lib::metrics::accuracy;
+use ml_lib::naive_multi_layer_perceptron::MyMLP;
+use ml_lib::search::{search, Domain, Evaluation, Objective, ParamValue, SearchSpace, SearchStrategy};
+use ml_lib::split::{class_labels, SplitStrategy};
+
+fn main() {
+    let dataset = datasets::cross(0, 500, 0.0);
+    let labels = class_labels(&dataset.targets);
+
+    let space = SearchSpace::new()
+        .with(
+            "npl",
+            Domain::Values(vec![
+                ParamValue::Layers(vec![2, 4, 1]),
+                ParamValue::Layers(vec![2, 8, 1]),
+                ParamValue::Layers(vec![2, 8, 8, 1]),
+            ]),
+        )
+        .with("alpha", Domain::FloatRange { low: 1e-3, high: 1e-1, log: true });
+
+    // 8 candidates with 20k iterations, the best half gets 40k, the best quarter 80k
+    let results = search(
+        &space,
+        SearchStrategy::SuccessiveHalving {
+            num_candidates: 8,
+            resource: "num_iter".to_string(),
+            min_resource: 20_000,
+            max_resource: 80_000,
+            eta: 2,
+            seed: 0,
+        },
+        &dataset.inputs,
+        &dataset.targets,
+        &Objective {
+            evaluation: Evaluation::Holdout {
+                validation_ratio: 0.2,
+                strategy: SplitStrategy::Stratified(&labels),
+                seed: 0,
+            },
+            scorer: accuracy,
+            maximize: true,
+        },
+        |params, inputs, outputs| {
+            let mut mlp = MyMLP::new(params.layers("npl"));
+            mlp.train(inputs, outputs, true, params.int("num_iter") as usize, params.float("alpha"));
+            mlp
+        },
+        |mlp, x| mlp.predict(x, true),
+    );
+
+    println!("\n{}", results);
+    if let Some(best) = results.best() {
+        println!("Best: {}", best.params);
+    }
+
+    let path = std::env::temp_dir().join("cross_search.csv");
+    results.write_csv(&path).unwrap();
+    println!("Results written to {}", path.display());
+}
@@ -1,8 +1,7 @@
-use ml_rs::linear_regressor::{LinearRegressor, LinearRegressorOptions};
-use ml_rs::{MatF, mse};
+use ml_lib::linear_perceptron::LinearPerceptron;
 
 fn main() {
-    let x_data: MatF = vec![
+    let inputs = vec![
         vec![0.0, 0.0],
         vec![1.0, 0.0],
         vec![0.0, 1.0],
@@ -10,18 +9,22 @@ fn main() {
         vec![2.0, 1.0],
         vec![3.0, 2.0],
     ];
-    let y_data = vec![1.0, 3.0, -2.0, -2.0, 2.0, 1.0];
+    let outputs = vec![
+        vec![1.0],
+        vec![3.0],
+        vec![-2.0],
+        vec![-2.0],
+        vec![2.0],
+        vec![1.0],
+    ];
 
-    let mut regressor = LinearRegressor::new(LinearRegressorOptions {
-        learning_rate: 0.05,
-        max_epochs: 500,
-        shuffle: true,
-        seed: 123,
-    });
+    let mut regressor = LinearPerceptron::new(2);
 
-    regressor.fit(&x_data, &y_data);
-    println!("Weights (bias, w1, w2): {:?}", regressor.weights_with_bias());
-    let y_pred = regressor.predict(&x_data);
-    println!("MSE: {}", mse(&y_data, &y_pred));
-    println!("Pred(1.0, 0.5) = {}", regressor.predict_one(&[1.0, 0.5]));
+    regressor.train_regression(&inputs, &outputs, 50_000, 0.01);
+    let mut mse = 0.0;
+    for (x, y) in inputs.iter().zip(outputs.iter()) {
+        mse += (regressor.predict_regression(x) - y[0]).powi(2);
+    }
+    println!("MSE: {}", mse / inputs.len() as f64);
+    println!("Pred(1.0, 0.5) = {}", regressor.predict_regression(&[1.0, 0.5]));
 }
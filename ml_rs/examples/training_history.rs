@@ -0,0 +1,43 @@
+use ml_lib::datasets;
+use ml_lib::history::Monitor;
+use ml_lib::metrics;
+use ml_lib::naive_multi_layer_perceptron::MyMLP;
+use ml_lib::plot::ChartOptions;
+use ml_lib::split::{class_labels, train_test_split, SplitStrategy};
+
+fn main() {
+    let dir = std::env::temp_dir();
+
+    let cross = datasets::cross(0, 500, 0.0);
+    let labels = class_labels(&cross.targets);
+    let split = train_test_split(&cross.inputs, &cross.targets, 0.2, SplitStrategy::Stratified(&labels), 0);
+
+    let monitor = Monitor::new(2_000)
+        .validation(&split.test_inputs, &split.test_outputs)
+        .metric("accuracy", metrics::accuracy);
+    let mut mlp = MyMLP::new(&[2, 8, 1]);
+    let history = mlp.train_monitored(&split.train_inputs, &split.train_outputs, true, 200_000, 0.05, &monitor);
+
+    let csv = dir.join("cross_history.csv");
+    history.write_csv(&csv).unwrap();
+    println!("Written {}", csv.display());
+
+    let loss = ChartOptions {
+        log_scale: true,
+        title: "Cross - MLP [2, 8, 1] loss".to_string(),
+        ..ChartOptions::default()
+    };
+    for file in ["cross_loss.svg", "cross_loss.png"] {
+        let path = dir.join(file);
+        history.plot(&path, &["train_loss", "validation_loss"], &loss).unwrap();
+        println!("Written {}", path.display());
+    }
+
+    let accuracy = ChartOptions {
+        title: "Cross - MLP [2, 8, 1] accuracy".to_string(),
+        ..ChartOptions::default()
+    };
+    let path = dir.join("cross_accuracy.svg");
+    history.plot(&path, &["train_accuracy", "validation_accuracy"], &accuracy).unwrap();
+    println!("Written {}", path.display());
+}
@@ -0,0 +1,227 @@
+use crate::datasets::gaussian;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+/// Layout of a flattened image: row by row, channels interleaved
+/// (the layout produced by `image_folder` and `idx`)
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ImageShape {
+    pub width: usize,
+    pub height: usize,
+    pub channels: usize,
+}
+
+impl ImageShape {
+    pub fn new(width: usize, height: usize, channels: usize) -> Self {
+        ImageShape {
+            width,
+            height,
+            channels,
+        }
+    }
+
+    /// Number of values of a flattened image
+    pub fn len(&self) -> usize {
+        self.width * self.height * self.channels
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Connect Four board: 7 columns x 6 rows, `channels` values per cell
+/// (e.g. 1 for a +1 / -1 / 0 encoding, 2 for one plane per player)
+pub fn connect_four_board(channels: usize) -> ImageShape {
+    ImageShape::new(7, 6, channels)
+}
+
+/// One random transformation of a sample
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Augmentation {
+    /// Left-right flip of an image
+    HorizontalFlip(ImageShape),
+    /// Top-bottom flip of an image
+    VerticalFlip(ImageShape),
+    /// Rotation around the centre by an angle drawn in [-max_degrees, max_degrees]
+    /// (bilinear interpolation, borders replicated)
+    Rotation { shape: ImageShape, max_degrees: f64 },
+    /// Shift by a whole number of pixels drawn in [-max_pixels, max_pixels] on each axis
+    /// (borders replicated)
+    Translation { shape: ImageShape, max_pixels: usize },
+    /// Gaussian noise of standard deviation `std` added to every value
+    GaussianNoise { std: f64 },
+    /// Same offset drawn in [-max_delta, max_delta] added to every value
+    Brightness { max_delta: f64 },
+    /// Distance to the mean value scaled by a factor drawn in [1 - max_factor, 1 + max_factor]
+    Contrast { max_factor: f64 },
+    /// Gaussian noise of standard deviation `std` on the coordinates of a point
+    Jitter { std: f64 },
+    /// (x, y) -> (-x, y), symmetry about the vertical axis
+    MirrorX,
+    /// (x, y) -> (x, -y), symmetry about the horizontal axis
+    MirrorY,
+    /// (x, y) -> (y, x), symmetry about the diagonal
+    SwapXY,
+    /// Connect Four board mirrored column-wise (the game is symmetric, so the
+    /// outcome of the position does not change)
+    ConnectFourMirror { channels: usize },
+}
+
+impl Augmentation {
+    /// Apply the transformation (with its own random parameters) to `x`
+    pub fn apply(&self, x: &[f64], rng: &mut StdRng) -> Vec<f64> {
+        match *self {
+            Augmentation::HorizontalFlip(shape) => flip(x, shape, true),
+            Augmentation::VerticalFlip(shape) => flip(x, shape, false),
+            Augmentation::Rotation { shape, max_degrees } => {
+                let angle = rng.gen_range(-max_degrees..=max_degrees).to_radians();
+                rotate(x, shape, angle)
+            }
+            Augmentation::Translation { shape, max_pixels } => {
+                let max = max_pixels as i64;
+                let dx = rng.gen_range(-max..=max);
+                let dy = rng.gen_range(-max..=max);
+                translate(x, shape, dx, dy)
+            }
+            Augmentation::GaussianNoise { std } | Augmentation::Jitter { std } => {
+                x.iter().map(|&v| v + std * gaussian(rng)).collect()
+            }
+            Augmentation::Brightness { max_delta } => {
+                let delta = rng.gen_range(-max_delta..=max_delta);
+                x.iter().map(|&v| v + delta).collect()
+            }
+            Augmentation::Contrast { max_factor } => {
+                let factor = rng.gen_range(1.0 - max_factor..=1.0 + max_factor);
+                let mean = x.iter().sum::<f64>() / x.len().max(1) as f64;
+                x.iter().map(|&v| mean + factor * (v - mean)).collect()
+            }
+            Augmentation::MirrorX => point(x, |p| p[0] = -p[0]),
+            Augmentation::MirrorY => point(x, |p| p[1] = -p[1]),
+            Augmentation::SwapXY => point(x, |p| p.swap(0, 1)),
+            Augmentation::ConnectFourMirror { channels } => flip(x, connect_four_board(channels), true),
+        }
+    }
+}
+
+/// Sequence of augmentations, each applied with its own probability.
+///
+/// The pipeline owns its generator so the augmented samples are reproducible from the seed.
+pub struct AugmentationPipeline {
+    steps: Vec<(Augmentation, f64)>,
+    rng: StdRng,
+}
+
+impl AugmentationPipeline {
+    pub fn new(seed: u64) -> Self {
+        AugmentationPipeline {
+            steps: Vec::new(),
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+
+    /// Append `step`, applied to each sample with the given probability (0..=1)
+    pub fn then(mut self, step: Augmentation, probability: f64) -> Self {
+        assert!((0.0..=1.0).contains(&probability), "Probability must be in [0, 1]");
+        self.steps.push((step, probability));
+        self
+    }
+
+    /// A fresh random variant of `x`
+    pub fn apply(&mut self, x: &[f64]) -> Vec<f64> {
+        let mut out = x.to_vec();
+        for (step, probability) in &self.steps {
+            if self.rng.gen::<f64>() < *probability {
+                out = step.apply(&out, &mut self.rng);
+            }
+        }
+        out
+    }
+
+    /// A fresh random variant of every sample (one augmented epoch)
+    pub fn augment(&mut self, inputs: &[Vec<f64>]) -> Vec<Vec<f64>> {
+        inputs.iter().map(|x| self.apply(x)).collect()
+    }
+
+    pub fn steps(&self) -> &[(Augmentation, f64)] {
+        &self.steps
+    }
+}
+
+fn point(x: &[f64], f: impl Fn(&mut [f64])) -> Vec<f64> {
+    assert!(x.len() >= 2, "Point augmentations need 2-D inputs");
+    let mut out = x.to_vec();
+    f(&mut out);
+    out
+}
+
+fn check_shape(x: &[f64], shape: ImageShape) {
+    assert_eq!(x.len(), shape.len(), "Input size must match the image shape");
+}
+
+/// Value of channel `c` at (row, col), coordinates clamped to the image
+fn pixel(x: &[f64], shape: ImageShape, row: i64, col: i64, c: usize) -> f64 {
+    let row = row.clamp(0, shape.height as i64 - 1) as usize;
+    let col = col.clamp(0, shape.width as i64 - 1) as usize;
+    x[(row * shape.width + col) * shape.channels + c]
+}
+
+fn flip(x: &[f64], shape: ImageShape, horizontal: bool) -> Vec<f64> {
+    check_shape(x, shape);
+    let mut out = Vec::with_capacity(x.len());
+    for row in 0..shape.height {
+        for col in 0..shape.width {
+            let (r, c) = if horizontal {
+                (row, shape.width - 1 - col)
+            } else {
+                (shape.height - 1 - row, col)
+            };
+            let start = (r * shape.width + c) * shape.channels;
+            out.extend_from_slice(&x[start..start + shape.channels]);
+        }
+    }
+    out
+}
+
+fn translate(x: &[f64], shape: ImageShape, dx: i64, dy: i64) -> Vec<f64> {
+    check_shape(x, shape);
+    let mut out = Vec::with_capacity(x.len());
+    for row in 0..shape.height as i64 {
+        for col in 0..shape.width as i64 {
+            for c in 0..shape.channels {
+                out.push(pixel(x, shape, row - dy, col - dx, c));
+            }
+        }
+    }
+    out
+}
+
+fn rotate(x: &[f64], shape: ImageShape, angle: f64) -> Vec<f64> {
+    check_shape(x, shape);
+    let (sin, cos) = angle.sin_cos();
+    let cx = (shape.width as f64 - 1.0) / 2.0;
+    let cy = (shape.height as f64 - 1.0) / 2.0;
+    let mut out = Vec::with_capacity(x.len());
+    for row in 0..shape.height {
+        for col in 0..shape.width {
+            // Source position: inverse rotation of the destination pixel
+            let u = col as f64 - cx;
+            let v = row as f64 - cy;
+            let src_col = cos * u + sin * v + cx;
+            let src_row = -sin * u + cos * v + cy;
+
+            let c0 = src_col.floor();
+            let r0 = src_row.floor();
+            let fc = src_col - c0;
+            let fr = src_row - r0;
+            let (c0, r0) = (c0 as i64, r0 as i64);
+            for c in 0..shape.channels {
+                let top = (1.0 - fc) * pixel(x, shape, r0, c0, c) + fc * pixel(x, shape, r0, c0 + 1, c);
+                let bottom =
+                    (1.0 - fc) * pixel(x, shape, r0 + 1, c0, c) + fc * pixel(x, shape, r0 + 1, c0 + 1, c);
+                out.push((1.0 - fr) * top + fr * bottom);
+            }
+        }
+    }
+    out
+}
@@ -0,0 +1,540 @@
+// `train`, `evaluate`, `predict` and `inspect` subcommands of the binary.
+//
+// Arguments are parsed by hand: positional arguments anywhere, options as
+// `--name value` or `--name=value`. Usage errors exit with code 2, failures with 1.
+
+use ml_lib::csv_loader::{load_csv, parse_inputs, Column, CsvOptions, LabelEncoding};
+use ml_lib::experiment::{load_configs, run_experiments, ExperimentConfig, Report};
+use ml_lib::linear_perceptron::LinearPerceptron;
+use ml_lib::metrics;
+use ml_lib::naive_multi_layer_perceptron::MyMLP;
+use ml_lib::saved_model::{Model, SavedModel};
+use ml_lib::scaling::{Scaler, ScalerKind};
+use ml_lib::split::{class_labels, train_test_split, SplitStrategy};
+use std::fmt;
+use std::io::{self, Read, Write};
+
+pub const USAGE: &str = "\
+Usage: ml_lib <command> [options]
+
+Commands:
+  train      Train a model on a CSV file and save it as JSON
+  evaluate   Score a saved model on a labelled CSV file
+  predict    Predict the samples of a CSV file (or stdin)
+  inspect    Print the architecture and parameter counts of a saved model
+  experiment Run the experiments of TOML / JSON config files and report the scores
+  course     Run the course test cases (e.g. course --classification --mlp)
+
+Run `ml_lib <command> --help` for the options of a command.";
+
+const TRAIN_USAGE: &str = "\
+Usage: ml_lib train <data.csv> --task <classification|regression> --output <model.json> [options]
+
+The last column is the target unless --target is given, every other column is a feature.
+Classes are encoded as ±1 (one output for two classes, one per class otherwise).
+
+Options:
+  --task <classification|regression>  Kind of problem (required)
+  --output, -o <file>                 Where to save the trained model (required)
+  --model <mlp|linear>                Model kind [default: mlp]
+  --hidden <sizes>                    Hidden layer sizes of the MLP, comma separated, 0 = none [default: 8]
+  --iterations <n>                    Stochastic gradient steps [default: 100000]
+  --alpha <rate>                      Learning rate [default: 0.01]
+  --scale <standard|min-max|robust|max-abs|none>  Input scaling [default: none]
+  --target <column>                   Target column, by header name or 0-based position
+  --validation <ratio>                Hold out this fraction of the samples and score it [default: 0]
+  --seed <n>                          Seed of the validation split [default: 0]";
+
+const EVALUATE_USAGE: &str = "\
+Usage: ml_lib evaluate <model.json> <test.csv>
+
+The test file needs the feature and target columns of the training file.";
+
+const PREDICT_USAGE: &str = "\
+Usage: ml_lib predict <model.json> [inputs.csv | -] [--raw] [--header | --no-header]
+
+Reads the samples from stdin when no file (or -) is given. With a header, the columns
+are picked by name (a full labelled file works), otherwise each line holds the features
+in training order. Prints one line per sample: the class label, or the predicted values.
+
+Options:
+  --raw          Print the raw model outputs, also for classifiers
+  --header       The first line holds the column names
+  --no-header    The first line is a sample [default: header if no cell of it is a number]";
+
+const INSPECT_USAGE: &str = "\
+Usage: ml_lib inspect <model.json>";
+
+const EXPERIMENT_USAGE: &str = "\
+Usage: ml_lib experiment <config.toml | config.json | directory>... [--report <file>]
+
+Runs every experiment of the files (all .toml / .json files of a directory) in parallel
+and prints a Markdown table of the scores. Exits with 1 if an experiment failed.
+
+Options:
+  --report <file>    Also write the report, as .md, .csv or .json";
+
+#[derive(Debug)]
+pub enum CliError {
+    /// Bad command line, the usage text of the command follows the message
+    Usage { message: String, usage: &'static str },
+    /// The command could not be carried out (missing file, bad data, ...)
+    Failed(String),
+}
+
+impl CliError {
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            CliError::Usage { .. } => 2,
+            CliError::Failed(_) => 1,
+        }
+    }
+}
+
+impl fmt::Display for CliError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CliError::Usage { message, usage } => write!(f, "error: {}\n\n{}", message, usage),
+            CliError::Failed(message) => write!(f, "error: {}", message),
+        }
+    }
+}
+
+fn failed<E: fmt::Display>(context: &str) -> impl FnOnce(E) -> CliError + '_ {
+    move |e| CliError::Failed(format!("{}: {}", context, e))
+}
+
+/// Positional arguments and options of a command
+struct Args {
+    positionals: Vec<String>,
+    options: Vec<(String, Option<String>)>,
+    usage: &'static str,
+}
+
+impl Args {
+    /// `valued` options take a value, `switches` do not; anything else is an error
+    fn parse(args: &[String], valued: &[&str], switches: &[&str], usage: &'static str) -> Result<Args, CliError> {
+        let mut parsed = Args {
+            positionals: Vec::new(),
+            options: Vec::new(),
+            usage,
+        };
+        let mut iter = args.iter();
+        while let Some(arg) = iter.next() {
+            if arg == "-" || !arg.starts_with('-') {
+                parsed.positionals.push(arg.clone());
+                continue;
+            }
+            let (name, inline) = match arg.split_once('=') {
+                Some((n, v)) => (n, Some(v.to_string())),
+                None => (arg.as_str(), None),
+            };
+            let name = if name == "-o" { "--output" } else { name };
+            if valued.contains(&name) {
+                let value = match inline {
+                    Some(v) => v,
+                    None => iter.next().cloned().ok_or_else(|| parsed.error(format!("{} needs a value", name)))?,
+                };
+                parsed.options.push((name.to_string(), Some(value)));
+            } else if switches.contains(&name) && inline.is_none() {
+                parsed.options.push((name.to_string(), None));
+            } else {
+                return Err(parsed.error(format!("unexpected option {}", arg)));
+            }
+        }
+        Ok(parsed)
+    }
+
+    fn error(&self, message: String) -> CliError {
+        CliError::Usage {
+            message,
+            usage: self.usage,
+        }
+    }
+
+    fn has(&self, name: &str) -> bool {
+        self.options.iter().any(|(n, _)| n == name)
+    }
+
+    /// Last value given for an option
+    fn value(&self, name: &str) -> Option<&str> {
+        self.options
+            .iter()
+            .rev()
+            .find(|(n, _)| n == name)
+            .and_then(|(_, v)| v.as_deref())
+    }
+
+    fn required(&self, name: &str) -> Result<&str, CliError> {
+        self.value(name).ok_or_else(|| self.error(format!("missing {}", name)))
+    }
+
+    fn number<T: std::str::FromStr>(&self, name: &str, default: T) -> Result<T, CliError> {
+        match self.value(name) {
+            Some(v) => v
+                .parse()
+                .map_err(|_| self.error(format!("invalid value \"{}\" for {}", v, name))),
+            None => Ok(default),
+        }
+    }
+
+    /// Between `min` and `names.len()` positional arguments (`names` describe them)
+    fn positionals(&self, min: usize, names: &[&str]) -> Result<&[String], CliError> {
+        let count = self.positionals.len();
+        if count < min {
+            return Err(self.error(format!("missing {}", names[count])));
+        }
+        if count > names.len() {
+            return Err(self.error(format!("unexpected argument \"{}\"", self.positionals[names.len()])));
+        }
+        Ok(&self.positionals)
+    }
+}
+
+/// Run a subcommand, `args` starts after the command name
+pub fn run(command: &str, args: &[String]) -> Result<(), CliError> {
+    let usage = match command {
+        "train" => TRAIN_USAGE,
+        "evaluate" => EVALUATE_USAGE,
+        "predict" => PREDICT_USAGE,
+        "inspect" => INSPECT_USAGE,
+        "experiment" => EXPERIMENT_USAGE,
+        _ => {
+            return Err(CliError::Usage {
+                message: format!("unknown command \"{}\"", command),
+                usage: USAGE,
+            })
+        }
+    };
+    if args.iter().any(|a| a == "--help" || a == "-h") {
+        println!("{}", usage);
+        return Ok(());
+    }
+    match command {
+        "train" => train(args),
+        "evaluate" => evaluate(args),
+        "predict" => predict(args),
+        "inspect" => inspect(args),
+        _ => experiment(args),
+    }
+}
+
+fn train(args: &[String]) -> Result<(), CliError> {
+    let args = Args::parse(
+        args,
+        &[
+            "--task",
+            "--output",
+            "--model",
+            "--hidden",
+            "--iterations",
+            "--alpha",
+            "--scale",
+            "--target",
+            "--validation",
+            "--seed",
+        ],
+        &[],
+        TRAIN_USAGE,
+    )?;
+    let data_path = &args.positionals(1, &["the data file"])?[0];
+    let output = args.required("--output")?;
+    let is_classification = match args.required("--task")? {
+        "classification" => true,
+        "regression" => false,
+        other => return Err(args.error(format!("unknown task \"{}\"", other))),
+    };
+    let kind = args.value("--model").unwrap_or("mlp");
+    if kind != "mlp" && kind != "linear" {
+        return Err(args.error(format!("unknown model \"{}\"", kind)));
+    }
+    let hidden: Vec<usize> = args
+        .value("--hidden")
+        .unwrap_or("8")
+        .split(',')
+        .map(|s| s.trim().parse())
+        .collect::<Result<_, _>>()
+        .map_err(|_| args.error("--hidden takes comma separated layer sizes, e.g. 8,4".to_string()))?;
+    let iterations: usize = args.number("--iterations", 100_000)?;
+    let alpha: f64 = args.number("--alpha", 0.01)?;
+    let validation: f64 = args.number("--validation", 0.0)?;
+    let seed: u64 = args.number("--seed", 0)?;
+    if !(0.0..1.0).contains(&validation) {
+        return Err(args.error("--validation must be in [0, 1)".to_string()));
+    }
+    let scaler_kind = match args.value("--scale").unwrap_or("none") {
+        "none" => None,
+        "standard" => Some(ScalerKind::Standard),
+        "min-max" => Some(ScalerKind::MinMax { low: -1.0, high: 1.0 }),
+        "robust" => Some(ScalerKind::Robust),
+        "max-abs" => Some(ScalerKind::MaxAbs),
+        other => return Err(args.error(format!("unknown scaling \"{}\"", other))),
+    };
+
+    let encoding = if is_classification { LabelEncoding::Bipolar } else { LabelEncoding::Numeric };
+    let mut options = CsvOptions::new(encoding);
+    if let Some(target) = args.value("--target") {
+        options.targets = vec![match target.parse() {
+            Ok(i) => Column::Index(i),
+            Err(_) => Column::Name(target.to_string()),
+        }];
+    }
+    let data = load_csv(data_path, &options).map_err(failed(data_path))?;
+    if data.dataset.is_empty() {
+        return Err(CliError::Failed(format!("{}: no samples", data_path)));
+    }
+    if is_classification && data.classes.len() < 2 {
+        return Err(CliError::Failed(format!("{}: need at least two classes", data_path)));
+    }
+
+    let labels = class_labels(&data.dataset.targets);
+    let strategy = if is_classification { SplitStrategy::Stratified(&labels) } else { SplitStrategy::Random };
+    let split = train_test_split(&data.dataset.inputs, &data.dataset.targets, validation, strategy, seed);
+    if split.train_inputs.is_empty() {
+        return Err(CliError::Failed(format!(
+            "{}: no training samples left with --validation {}",
+            data_path, validation
+        )));
+    }
+    let scaler = scaler_kind.map(|kind| {
+        let mut scaler = Scaler::new(kind);
+        scaler.fit(&split.train_inputs);
+        scaler
+    });
+    let train_inputs = match &scaler {
+        Some(scaler) => scaler.transform_all(&split.train_inputs),
+        None => split.train_inputs.clone(),
+    };
+    let train_outputs = &split.train_outputs;
+
+    let input_dim = data.dataset.input_dim();
+    let output_dim = data.dataset.output_dim();
+    println!(
+        "Training {} on {} samples ({} features, {} outputs)",
+        kind,
+        train_inputs.len(),
+        input_dim,
+        output_dim
+    );
+    let model = if kind == "linear" {
+        let perceptrons = (0..output_dim)
+            .map(|o| {
+                let outputs: Vec<Vec<f64>> = train_outputs.iter().map(|y| vec![y[o]]).collect();
+                let mut p = LinearPerceptron::new(input_dim);
+                if is_classification {
+                    p.train_classification(&train_inputs, &outputs, iterations, alpha);
+                } else {
+                    p.train_regression(&train_inputs, &outputs, iterations, alpha);
+                }
+                p
+            })
+            .collect();
+        Model::Linear(perceptrons)
+    } else {
+        let mut layers = vec![input_dim];
+        layers.extend(hidden.into_iter().filter(|&h| h > 0));
+        layers.push(output_dim);
+        let mut mlp = MyMLP::new(&layers);
+        mlp.train(&train_inputs, train_outputs, is_classification, iterations, alpha);
+        Model::Mlp(mlp)
+    };
+
+    let mut saved = SavedModel {
+        model,
+        is_classification,
+        scaler,
+        feature_names: data.feature_names,
+        target_names: data.target_names,
+        classes: data.classes,
+    };
+    println!("\nTraining samples:");
+    report(&mut saved, &split.train_inputs, &split.train_outputs);
+    if !split.test_inputs.is_empty() {
+        println!("\nValidation samples:");
+        report(&mut saved, &split.test_inputs, &split.test_outputs);
+    }
+
+    saved.save(output).map_err(failed(output))?;
+    println!("\nModel saved to {}", output);
+    Ok(())
+}
+
+/// Print the metrics of the task
+fn report(model: &mut SavedModel, inputs: &[Vec<f64>], targets: &[Vec<f64>]) {
+    let predictions: Vec<Vec<f64>> = inputs.iter().map(|x| model.predict(x)).collect();
+    if model.is_classification {
+        println!("  accuracy          {:.4}", metrics::accuracy(&predictions, targets));
+        println!("  balanced accuracy {:.4}", metrics::balanced_accuracy(&predictions, targets));
+        println!("  macro F1          {:.4}", metrics::macro_f1(&predictions, targets));
+        println!("  confusion matrix (rows = true class, columns = predicted):");
+        let width = model.classes.iter().map(|c| c.len()).max().unwrap_or(0).max(6);
+        print!("  {:>width$}", "", width = width);
+        for class in &model.classes {
+            print!(" {:>width$}", class, width = width);
+        }
+        println!();
+        for (class, row) in model.classes.iter().zip(metrics::confusion_matrix(&predictions, targets)) {
+            print!("  {:>width$}", class, width = width);
+            for count in row {
+                print!(" {:>width$}", count, width = width);
+            }
+            println!();
+        }
+    } else {
+        println!("  MSE  {:.6}", metrics::mean_squared_error(&predictions, targets));
+        println!("  RMSE {:.6}", metrics::root_mean_squared_error(&predictions, targets));
+        println!("  MAE  {:.6}", metrics::mean_absolute_error(&predictions, targets));
+        println!("  R²   {:.6}", metrics::r2_score(&predictions, targets));
+    }
+}
+
+fn load_model(path: &str) -> Result<SavedModel, CliError> {
+    SavedModel::load(path).map_err(failed(path))
+}
+
+fn evaluate(args: &[String]) -> Result<(), CliError> {
+    let args = Args::parse(args, &[], &[], EVALUATE_USAGE)?;
+    let paths = args.positionals(2, &["the model file", "the test file"])?;
+    let mut model = load_model(&paths[0])?;
+    let data = model.load_csv(&paths[1]).map_err(failed(&paths[1]))?;
+    if data.is_empty() {
+        return Err(CliError::Failed(format!("{}: no samples", paths[1])));
+    }
+    println!("{} samples:", data.len());
+    report(&mut model, &data.inputs, &data.targets);
+    Ok(())
+}
+
+fn predict(args: &[String]) -> Result<(), CliError> {
+    let args = Args::parse(args, &[], &["--raw", "--header", "--no-header"], PREDICT_USAGE)?;
+    let paths = args.positionals(1, &["the model file", "the inputs file"])?;
+    if args.has("--header") && args.has("--no-header") {
+        return Err(args.error("--header and --no-header cannot be used together".to_string()));
+    }
+    let mut model = load_model(&paths[0])?;
+
+    let (name, text) = match paths.get(1).map(|p| p.as_str()) {
+        None | Some("-") => {
+            let mut text = String::new();
+            io::stdin().read_to_string(&mut text).map_err(failed("stdin"))?;
+            ("stdin".to_string(), text)
+        }
+        Some(path) => (path.to_string(), std::fs::read_to_string(path).map_err(failed(path))?),
+    };
+    // Files with a header: pick the training features by name
+    let mut options = CsvOptions::new(LabelEncoding::Numeric);
+    if args.has("--header") {
+        options.has_header = Some(true);
+    } else if args.has("--no-header") {
+        options.has_header = Some(false);
+    }
+    let first_line = text.lines().find(|l| !l.trim().is_empty()).unwrap_or("");
+    if parse_inputs(first_line, &options).map_err(failed(&name))?.0.is_some() {
+        options.features = Some(model.feature_names.iter().map(|n| Column::Name(n.clone())).collect());
+    }
+    let inputs = parse_inputs(&text, &options).map_err(failed(&name))?.1;
+    let expected = model.feature_names.len();
+    if let Some(x) = inputs.iter().find(|x| x.len() != expected) {
+        return Err(CliError::Failed(format!(
+            "{}: the model takes {} features, found {}",
+            name,
+            expected,
+            x.len()
+        )));
+    }
+
+    let raw = args.has("--raw") || !model.is_classification;
+    let stdout = io::stdout();
+    let mut out = stdout.lock();
+    let header = if raw && model.is_classification && model.classes.len() > 2 {
+        model.classes.join(",")
+    } else if raw && model.is_classification {
+        "output".to_string()
+    } else {
+        model.target_names.join(",")
+    };
+    let mut lines = vec![header];
+    for x in &inputs {
+        let output = model.predict(x);
+        lines.push(if raw {
+            output.iter().map(|v| v.to_string()).collect::<Vec<_>>().join(",")
+        } else {
+            model.class_name(&output).to_string()
+        });
+    }
+    for line in lines {
+        match writeln!(out, "{}", line) {
+            // Reader gone (e.g. piped into `head`): nothing left to do
+            Err(e) if e.kind() == io::ErrorKind::BrokenPipe => return Ok(()),
+            result => result.map_err(failed("stdout"))?,
+        }
+    }
+    Ok(())
+}
+
+fn inspect(args: &[String]) -> Result<(), CliError> {
+    let args = Args::parse(args, &[], &[], INSPECT_USAGE)?;
+    let path = &args.positionals(1, &["the model file"])?[0];
+    let model = load_model(path)?;
+
+    println!("Model:    {}", model.model.name());
+    println!("Task:     {}", if model.is_classification { "classification" } else { "regression" });
+    println!("Features: {} ({})", model.feature_names.len(), model.feature_names.join(", "));
+    println!("Targets:  {}", model.target_names.join(", "));
+    if model.is_classification {
+        println!("Classes:  {} ({})", model.classes.len(), model.classes.join(", "));
+    }
+    println!(
+        "Scaling:  {}",
+        model.scaler.as_ref().map_or("none".to_string(), |s| format!("{:?}", s.kind()))
+    );
+    let layers = model.model.layers();
+    println!(
+        "Layers:   {}",
+        layers.iter().map(|n| n.to_string()).collect::<Vec<_>>().join(" -> ")
+    );
+    println!("Parameters:");
+    for (l, count) in model.model.parameters_per_layer().iter().enumerate() {
+        println!("  layer {} ({} -> {}): {}", l + 1, layers[l], layers[l + 1], count);
+    }
+    println!("  total: {}", model.model.num_parameters());
+    Ok(())
+}
+
+fn experiment(args: &[String]) -> Result<(), CliError> {
+    let args = Args::parse(args, &["--report"], &[], EXPERIMENT_USAGE)?;
+    if args.positionals.is_empty() {
+        return Err(args.error("missing the config file".to_string()));
+    }
+    let mut configs = Vec::new();
+    for path in &args.positionals {
+        configs.extend(load_configs(path).map_err(failed(path))?);
+    }
+    run_configs(&configs, args.value("--report"))
+}
+
+/// Run experiments in parallel, print their report and write it to `report_path`
+pub fn run_configs(configs: &[ExperimentConfig], report_path: Option<&str>) -> Result<(), CliError> {
+    println!("Running {} experiments...", configs.len());
+
+    let mut report = Report::default();
+    let mut failures = 0;
+    for (config, result) in configs.iter().zip(run_experiments(configs)) {
+        match result {
+            Ok(r) => report.results.push(r),
+            Err(e) => {
+                eprintln!("error: experiment \"{}\": {}", config.name, e);
+                failures += 1;
+            }
+        }
+    }
+    println!("\n{}", report);
+    if let Some(path) = report_path {
+        report.write(path).map_err(failed(path))?;
+        println!("Report written to {}", path);
+    }
+    if failures > 0 {
+        return Err(CliError::Failed(format!("{} of {} experiments failed", failures, configs.len())));
+    }
+    Ok(())
+}
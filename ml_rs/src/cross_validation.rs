@@ -0,0 +1,226 @@
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+use std::thread;
+
+/// How the samples are divided into folds
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CvStrategy<'a> {
+    /// k folds of consecutive samples, shuffled first when a seed is given
+    KFold { k: usize, seed: Option<u64> },
+    /// k folds keeping the class proportions (`labels[k]` = class of sample k)
+    StratifiedKFold { k: usize, labels: &'a [usize], seed: u64 },
+    /// One fold per sample
+    LeaveOneOut,
+    /// Shuffled k-fold run `repeats` times with different shuffles
+    RepeatedKFold { k: usize, repeats: usize, seed: u64 },
+}
+
+/// Training and validation indices of one fold
+#[derive(Clone, Debug, PartialEq)]
+pub struct Fold {
+    /// Repetition index (always 0 except for `RepeatedKFold`)
+    pub repeat: usize,
+    pub index: usize,
+    pub train: Vec<usize>,
+    pub validation: Vec<usize>,
+}
+
+/// Score of the predictions of a model: (predictions, expected outputs) -> value
+pub type Scorer = fn(&[Vec<f64>], &[Vec<f64>]) -> f64;
+
+/// Scores of one fold, in the order of the scorers
+#[derive(Clone, Debug)]
+pub struct FoldScores {
+    pub repeat: usize,
+    pub fold: usize,
+    pub train_size: usize,
+    pub validation_size: usize,
+    pub scores: Vec<f64>,
+}
+
+#[derive(Clone, Debug)]
+pub struct CvResults {
+    pub metric_names: Vec<String>,
+    pub folds: Vec<FoldScores>,
+}
+
+impl CvResults {
+    fn metric_index(&self, metric: &str) -> usize {
+        self.metric_names
+            .iter()
+            .position(|m| m == metric)
+            .unwrap_or_else(|| panic!("Unknown metric {}", metric))
+    }
+
+    /// Mean of a metric over the folds
+    pub fn mean(&self, metric: &str) -> f64 {
+        let i = self.metric_index(metric);
+        self.folds.iter().map(|f| f.scores[i]).sum::<f64>() / self.folds.len() as f64
+    }
+
+    /// Standard deviation of a metric over the folds
+    pub fn std(&self, metric: &str) -> f64 {
+        let i = self.metric_index(metric);
+        let mean = self.mean(metric);
+        let variance = self.folds.iter().map(|f| (f.scores[i] - mean).powi(2)).sum::<f64>() / self.folds.len() as f64;
+        variance.sqrt()
+    }
+
+    /// (metric, mean, standard deviation) of every metric
+    pub fn summary(&self) -> Vec<(String, f64, f64)> {
+        self.metric_names
+            .iter()
+            .map(|m| (m.clone(), self.mean(m), self.std(m)))
+            .collect()
+    }
+}
+
+/// Training / validation indices of every fold of `strategy`
+pub fn folds(num_samples: usize, strategy: CvStrategy) -> Vec<Fold> {
+    let mut folds = Vec::new();
+    match strategy {
+        CvStrategy::KFold { k, seed } => {
+            let mut order: Vec<usize> = (0..num_samples).collect();
+            if let Some(seed) = seed {
+                order.shuffle(&mut StdRng::seed_from_u64(seed));
+            }
+            push_folds(&mut folds, 0, chunk_assignment(&order, k), k);
+        }
+        CvStrategy::StratifiedKFold { k, labels, seed } => {
+            assert_eq!(labels.len(), num_samples, "One label per sample is needed");
+            check_k(k, num_samples);
+            let mut rng = StdRng::seed_from_u64(seed);
+            let mut classes = labels.to_vec();
+            classes.sort();
+            classes.dedup();
+            // Members of each class dealt in turn to the folds, continuing from
+            // one class to the next so the fold sizes stay balanced
+            let mut assignment = vec![0; num_samples];
+            let mut next = 0;
+            for class in classes {
+                let mut members: Vec<usize> = (0..num_samples).filter(|&s| labels[s] == class).collect();
+                members.shuffle(&mut rng);
+                for s in members {
+                    assignment[s] = next % k;
+                    next += 1;
+                }
+            }
+            push_folds(&mut folds, 0, assignment, k);
+        }
+        CvStrategy::LeaveOneOut => {
+            check_k(num_samples, num_samples);
+            push_folds(&mut folds, 0, (0..num_samples).collect(), num_samples);
+        }
+        CvStrategy::RepeatedKFold { k, repeats, seed } => {
+            let mut rng = StdRng::seed_from_u64(seed);
+            for repeat in 0..repeats {
+                let mut order: Vec<usize> = (0..num_samples).collect();
+                order.shuffle(&mut rng);
+                push_folds(&mut folds, repeat, chunk_assignment(&order, k), k);
+            }
+        }
+    }
+    folds
+}
+
+/// Every fold needs at least one validation sample and one training sample
+fn check_k(k: usize, num_samples: usize) {
+    assert!(k >= 2 && k <= num_samples, "Need 2 <= k <= number of samples");
+}
+
+/// Fold of each sample when `order` is cut into k contiguous chunks
+fn chunk_assignment(order: &[usize], k: usize) -> Vec<usize> {
+    check_k(k, order.len());
+    let mut assignment = vec![0; order.len()];
+    for (position, &s) in order.iter().enumerate() {
+        assignment[s] = position * k / order.len();
+    }
+    assignment
+}
+
+fn push_folds(folds: &mut Vec<Fold>, repeat: usize, assignment: Vec<usize>, k: usize) {
+    for index in 0..k {
+        let (validation, train): (Vec<usize>, Vec<usize>) = (0..assignment.len()).partition(|&s| assignment[s] == index);
+        folds.push(Fold {
+            repeat,
+            index,
+            train,
+            validation,
+        });
+    }
+}
+
+/// Train one model per fold and score it on the held-out samples.
+///
+/// `fit` builds and trains a fresh model on the training part of a fold (so it carries the
+/// architecture and the training parameters), `predict` gives the output of a trained model.
+/// Folds run in parallel over the available cores.
+///
+/// ```ignore
+/// let results = cross_validate(
+///     &inputs,
+///     &outputs,
+///     CvStrategy::StratifiedKFold { k: 5, labels: &class_labels(&outputs), seed: 0 },
+///     |x, y| {
+///         let mut mlp = MyMLP::new(&[2, 4, 1]);
+///         mlp.train(x, y, true, 100_000, 0.05);
+///         mlp
+///     },
+///     |mlp, x| mlp.predict(x, true),
+///     &[("accuracy", metrics::accuracy)],
+/// );
+/// println!("{:.3} ± {:.3}", results.mean("accuracy"), results.std("accuracy"));
+/// ```
+pub fn cross_validate<M, F, P>(
+    inputs: &[Vec<f64>],
+    outputs: &[Vec<f64>],
+    strategy: CvStrategy,
+    fit: F,
+    predict: P,
+    scorers: &[(&str, Scorer)],
+) -> CvResults
+where
+    F: Fn(&[Vec<f64>], &[Vec<f64>]) -> M + Sync,
+    P: Fn(&mut M, &[f64]) -> Vec<f64> + Sync,
+{
+    assert_eq!(inputs.len(), outputs.len());
+    let jobs = folds(inputs.len(), strategy);
+
+    let run = |fold: &Fold| -> FoldScores {
+        let train_inputs: Vec<Vec<f64>> = fold.train.iter().map(|&s| inputs[s].clone()).collect();
+        let train_outputs: Vec<Vec<f64>> = fold.train.iter().map(|&s| outputs[s].clone()).collect();
+        let mut model = fit(&train_inputs, &train_outputs);
+
+        let predictions: Vec<Vec<f64>> = fold.validation.iter().map(|&s| predict(&mut model, &inputs[s])).collect();
+        let expected: Vec<Vec<f64>> = fold.validation.iter().map(|&s| outputs[s].clone()).collect();
+        FoldScores {
+            repeat: fold.repeat,
+            fold: fold.index,
+            train_size: fold.train.len(),
+            validation_size: fold.validation.len(),
+            scores: scorers.iter().map(|(_, score)| score(&predictions, &expected)).collect(),
+        }
+    };
+
+    let num_threads = thread::available_parallelism().map_or(1, |p| p.get());
+    let chunk_size = jobs.len().div_ceil(num_threads).max(1);
+    let mut results: Vec<FoldScores> = Vec::with_capacity(jobs.len());
+    thread::scope(|scope| {
+        let handles: Vec<_> = jobs
+            .chunks(chunk_size)
+            .map(|chunk| {
+                let run = &run;
+                scope.spawn(move || chunk.iter().map(run).collect::<Vec<_>>())
+            })
+            .collect();
+        for handle in handles {
+            results.extend(handle.join().unwrap());
+        }
+    });
+
+    CvResults {
+        metric_names: scorers.iter().map(|(name, _)| name.to_string()).collect(),
+        folds: results,
+    }
+}
@@ -0,0 +1,449 @@
+use crate::datasets::{Dataset, DatasetInfo, Task};
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+/// A column, by position (0-based) or by header name
+#[derive(Clone, Debug, PartialEq)]
+pub enum Column {
+    Index(usize),
+    Name(String),
+}
+
+/// How the target column is turned into `Vec<f64>` targets
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum LabelEncoding {
+    /// Numeric targets kept as they are (regression)
+    Numeric,
+    /// Class index as a single value: [0], [1], [2], ...
+    Index,
+    /// One value per class: 1 for the class, 0 elsewhere
+    OneHot,
+    /// ±1 like the course cases: a single ±1 value for two classes
+    /// (second class in sorted order = +1), one ±1 value per class otherwise.
+    /// Classes are sorted numerically when every label is a number (2 before 10).
+    Bipolar,
+}
+
+/// What to do with empty / NA / NaN / ? / null cells in the feature columns.
+/// Rows with a missing target are always dropped.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum MissingValues {
+    /// Fail with the line number of the first missing cell
+    Error,
+    /// Drop the rows with a missing cell
+    Drop,
+    /// Replace by the mean of the column
+    Mean,
+    /// Replace by the median of the column
+    Median,
+}
+
+#[derive(Clone, Debug)]
+pub struct CsvOptions {
+    /// Field separator, None = guessed from the first line (tab, ';' or ',')
+    pub delimiter: Option<char>,
+    /// None = header if no cell of the first line is a number
+    pub has_header: Option<bool>,
+    /// Feature columns, None = every column that is not a target
+    pub features: Option<Vec<Column>>,
+    /// Target columns (several only with `LabelEncoding::Numeric`)
+    pub targets: Vec<Column>,
+    pub label_encoding: LabelEncoding,
+    pub missing: MissingValues,
+}
+
+impl CsvOptions {
+    /// Last column as target, every other column as a feature
+    pub fn new(label_encoding: LabelEncoding) -> Self {
+        CsvOptions {
+            delimiter: None,
+            has_header: None,
+            features: None,
+            targets: Vec::new(),
+            label_encoding,
+            missing: MissingValues::Error,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum CsvError {
+    Io(std::io::Error),
+    /// Problem on a given line (1-based, as in a text editor)
+    Line { line: usize, message: String },
+    /// Problem with the options (unknown column, ...)
+    Invalid(String),
+}
+
+impl fmt::Display for CsvError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CsvError::Io(e) => write!(f, "I/O error: {}", e),
+            CsvError::Line { line, message } => write!(f, "line {}: {}", line, message),
+            CsvError::Invalid(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+impl std::error::Error for CsvError {}
+
+impl From<std::io::Error> for CsvError {
+    fn from(e: std::io::Error) -> Self {
+        CsvError::Io(e)
+    }
+}
+
+/// Loaded table: the dataset plus the names needed to interpret it
+#[derive(Clone, Debug)]
+pub struct CsvDataset {
+    pub dataset: Dataset,
+    pub feature_names: Vec<String>,
+    pub target_names: Vec<String>,
+    /// Class labels in encoding order (empty for numeric targets)
+    pub classes: Vec<String>,
+}
+
+/// Read a delimited text file (`.tsv` files default to tab separators)
+pub fn load_csv<P: AsRef<Path>>(path: P, options: &CsvOptions) -> Result<CsvDataset, CsvError> {
+    let path = path.as_ref();
+    let text = fs::read_to_string(path)?;
+
+    let mut options = options.clone();
+    if options.delimiter.is_none() && path.extension().is_some_and(|e| e == "tsv") {
+        options.delimiter = Some('\t');
+    }
+    let name = path
+        .file_stem()
+        .map_or_else(|| "csv".to_string(), |s| s.to_string_lossy().to_string());
+    parse_csv(&text, &name, &options)
+}
+
+/// Parse delimited text already in memory, `name` goes to the dataset metadata
+pub fn parse_csv(text: &str, name: &str, options: &CsvOptions) -> Result<CsvDataset, CsvError> {
+    let Table { header, has_header, rows } =
+        read_table(text, options)?.ok_or_else(|| CsvError::Invalid("empty file".to_string()))?;
+    let num_columns = header.len();
+
+    // Column selection
+    let resolve = |column: &Column| resolve_column(column, &header, has_header);
+    let target_cols: Vec<usize> = if options.targets.is_empty() {
+        vec![num_columns - 1]
+    } else {
+        options.targets.iter().map(resolve).collect::<Result<_, _>>()?
+    };
+    let feature_cols: Vec<usize> = match &options.features {
+        Some(cols) => cols.iter().map(resolve).collect::<Result<_, _>>()?,
+        None => (0..num_columns).filter(|c| !target_cols.contains(c)).collect(),
+    };
+    if target_cols.len() > 1 && options.label_encoding != LabelEncoding::Numeric {
+        return Err(CsvError::Invalid("several target columns need numeric targets".to_string()));
+    }
+
+    // Parse cells: features as Option<f64>, target as raw strings
+    let mut features: Vec<Vec<Option<f64>>> = Vec::new();
+    let mut raw_targets: Vec<Vec<String>> = Vec::new();
+    for (line, cells) in &rows {
+        let targets: Vec<String> = target_cols.iter().map(|&c| cells[c].trim().to_string()).collect();
+        if targets.iter().any(|t| is_missing(t)) {
+            continue;
+        }
+        if options.label_encoding == LabelEncoding::Numeric {
+            for (t, &c) in targets.iter().zip(target_cols.iter()) {
+                if t.parse::<f64>().is_err() {
+                    return Err(CsvError::Line {
+                        line: *line,
+                        message: format!("target \"{}\" in column \"{}\" is not a number", t, header[c]),
+                    });
+                }
+            }
+        }
+
+        let mut row = Vec::with_capacity(feature_cols.len());
+        for &c in &feature_cols {
+            let cell = cells[c].trim();
+            if is_missing(cell) {
+                if options.missing == MissingValues::Error {
+                    return Err(CsvError::Line {
+                        line: *line,
+                        message: format!("missing value in column \"{}\"", header[c]),
+                    });
+                }
+                row.push(None);
+            } else {
+                match cell.parse::<f64>() {
+                    Ok(v) => row.push(Some(v)),
+                    Err(_) => {
+                        return Err(CsvError::Line {
+                            line: *line,
+                            message: format!("\"{}\" in column \"{}\" is not a number", cell, header[c]),
+                        })
+                    }
+                }
+            }
+        }
+
+        if options.missing == MissingValues::Drop && row.iter().any(|v| v.is_none()) {
+            continue;
+        }
+        features.push(row);
+        raw_targets.push(targets);
+    }
+
+    // Imputation
+    let fill: Vec<f64> = (0..feature_cols.len())
+        .map(|j| {
+            let mut present: Vec<f64> = features.iter().filter_map(|r| r[j]).collect();
+            match options.missing {
+                MissingValues::Median => median(&mut present),
+                _ => present.iter().sum::<f64>() / present.len().max(1) as f64,
+            }
+        })
+        .collect();
+    let inputs: Vec<Vec<f64>> = features
+        .iter()
+        .map(|r| r.iter().zip(fill.iter()).map(|(v, f)| v.unwrap_or(*f)).collect())
+        .collect();
+
+    // Targets
+    let mut classes: Vec<String> = Vec::new();
+    let targets: Vec<Vec<f64>> = if options.label_encoding == LabelEncoding::Numeric {
+        raw_targets
+            .iter()
+            .map(|t| t.iter().map(|v| v.parse::<f64>().unwrap()).collect())
+            .collect()
+    } else {
+        for t in &raw_targets {
+            if !classes.contains(&t[0]) {
+                classes.push(t[0].clone());
+            }
+        }
+        sort_classes(&mut classes);
+        raw_targets
+            .iter()
+            .map(|t| {
+                let class = classes.iter().position(|c| *c == t[0]).unwrap();
+                encode_label(class, classes.len(), options.label_encoding)
+            })
+            .collect()
+    };
+
+    let task = if options.label_encoding == LabelEncoding::Numeric {
+        Task::Regression
+    } else {
+        Task::Classification
+    };
+    Ok(CsvDataset {
+        dataset: Dataset {
+            inputs,
+            targets,
+            metadata: DatasetInfo {
+                name: name.to_string(),
+                task,
+                num_classes: if classes.is_empty() { None } else { Some(classes.len()) },
+                label_encoding: match options.label_encoding {
+                    LabelEncoding::Numeric => None,
+                    encoding => Some(encoding),
+                },
+                seed: 0,
+                noise: 0.0,
+            },
+        },
+        feature_names: feature_cols.iter().map(|&c| header[c].clone()).collect(),
+        target_names: target_cols.iter().map(|&c| header[c].clone()).collect(),
+        classes,
+    })
+}
+
+/// Names of the selected columns (None when the file has no header) and the inputs
+pub type InputRows = (Option<Vec<String>>, Vec<Vec<f64>>);
+
+/// Parse rows of features only (samples to predict): every column, or the
+/// `options.features` columns; `targets` and `label_encoding` are ignored and missing
+/// cells are an error, so there is exactly one input per data line.
+pub fn parse_inputs(text: &str, options: &CsvOptions) -> Result<InputRows, CsvError> {
+    let Some(Table { header, has_header, rows }) = read_table(text, options)? else {
+        return Ok((None, Vec::new()));
+    };
+    let feature_cols: Vec<usize> = match &options.features {
+        Some(cols) => cols
+            .iter()
+            .map(|c| resolve_column(c, &header, has_header))
+            .collect::<Result<_, _>>()?,
+        None => (0..header.len()).collect(),
+    };
+
+    let mut inputs = Vec::with_capacity(rows.len());
+    for (line, cells) in &rows {
+        let mut row = Vec::with_capacity(feature_cols.len());
+        for &c in &feature_cols {
+            let cell = cells[c].trim();
+            match cell.parse::<f64>() {
+                Ok(v) if !is_missing(cell) => row.push(v),
+                _ => {
+                    return Err(CsvError::Line {
+                        line: *line,
+                        message: format!("\"{}\" in column \"{}\" is not a number", cell, header[c]),
+                    })
+                }
+            }
+        }
+        inputs.push(row);
+    }
+    let names = has_header.then(|| feature_cols.iter().map(|&c| header[c].clone()).collect());
+    Ok((names, inputs))
+}
+
+/// Non-empty lines split into cells, the header taken out
+struct Table {
+    /// Column names, `column_<i>` without header
+    header: Vec<String>,
+    has_header: bool,
+    /// (line number, cells) of the data lines, all `header.len()` wide
+    rows: Vec<(usize, Vec<String>)>,
+}
+
+/// Split the lines of `text` (None without any non-empty line). The header is the
+/// first line when `options.has_header` says so, or when none of its cells is a number.
+fn read_table(text: &str, options: &CsvOptions) -> Result<Option<Table>, CsvError> {
+    let first_line = text.lines().find(|l| !l.trim().is_empty()).unwrap_or("");
+    let delimiter = options.delimiter.unwrap_or_else(|| guess_delimiter(first_line));
+    let mut rows: Vec<(usize, Vec<String>)> = Vec::new();
+    for (i, line) in text.lines().enumerate() {
+        if !line.trim().is_empty() {
+            rows.push((i + 1, split_line(line, delimiter, i + 1)?));
+        }
+    }
+    if rows.is_empty() {
+        return Ok(None);
+    }
+
+    let num_columns = rows[0].1.len();
+    let has_header = options
+        .has_header
+        .unwrap_or_else(|| rows[0].1.iter().all(|c| !is_missing(c) && c.trim().parse::<f64>().is_err()));
+    let header: Vec<String> = if has_header {
+        rows.remove(0).1.iter().map(|c| c.trim().to_string()).collect()
+    } else {
+        (0..num_columns).map(|i| format!("column_{}", i)).collect()
+    };
+    if let Some((line, cells)) = rows.iter().find(|(_, cells)| cells.len() != num_columns) {
+        return Err(CsvError::Line {
+            line: *line,
+            message: format!("expected {} fields, found {}", num_columns, cells.len()),
+        });
+    }
+    Ok(Some(Table { header, has_header, rows }))
+}
+
+/// Position of a column in a row of `header.len()` fields
+fn resolve_column(column: &Column, header: &[String], has_header: bool) -> Result<usize, CsvError> {
+    match column {
+        Column::Index(i) if *i < header.len() => Ok(*i),
+        Column::Index(i) => Err(CsvError::Invalid(format!(
+            "column {} out of range ({} columns)",
+            i,
+            header.len()
+        ))),
+        Column::Name(n) => {
+            if !has_header {
+                return Err(CsvError::Invalid(format!("column \"{}\" selected by name but the file has no header", n)));
+            }
+            header
+                .iter()
+                .position(|h| h == n)
+                .ok_or_else(|| CsvError::Invalid(format!("unknown column \"{}\"", n)))
+        }
+    }
+}
+
+pub(crate) fn encode_label(class: usize, num_classes: usize, encoding: LabelEncoding) -> Vec<f64> {
+    match encoding {
+        LabelEncoding::Index | LabelEncoding::Numeric => vec![class as f64],
+        LabelEncoding::OneHot => (0..num_classes)
+            .map(|c| if c == class { 1.0 } else { 0.0 })
+            .collect(),
+        LabelEncoding::Bipolar if num_classes == 2 => vec![if class == 1 { 1.0 } else { -1.0 }],
+        LabelEncoding::Bipolar => (0..num_classes)
+            .map(|c| if c == class { 1.0 } else { -1.0 })
+            .collect(),
+    }
+}
+
+/// Numeric order when every label is a number ("2" before "10"), text order otherwise
+fn sort_classes(classes: &mut [String]) {
+    let number = |c: &String| c.trim().parse::<f64>();
+    if classes.iter().all(|c| number(c).is_ok()) {
+        classes.sort_by(|a, b| number(a).unwrap().total_cmp(&number(b).unwrap()));
+    } else {
+        classes.sort();
+    }
+}
+
+fn guess_delimiter(line: &str) -> char {
+    if line.contains('\t') {
+        '\t'
+    } else if line.contains(';') && !line.contains(',') {
+        ';'
+    } else {
+        ','
+    }
+}
+
+/// Split a line on `delimiter`, double quotes protect delimiters ("" = escaped quote)
+fn split_line(line: &str, delimiter: char, line_number: usize) -> Result<Vec<String>, CsvError> {
+    let mut cells = Vec::new();
+    let mut cell = String::new();
+    let mut in_quotes = false;
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        if in_quotes {
+            if c == '"' {
+                if chars.peek() == Some(&'"') {
+                    cell.push('"');
+                    chars.next();
+                } else {
+                    in_quotes = false;
+                }
+            } else {
+                cell.push(c);
+            }
+        } else if c == '"' {
+            in_quotes = true;
+        } else if c == delimiter {
+            cells.push(std::mem::take(&mut cell));
+        } else {
+            cell.push(c);
+        }
+    }
+    if in_quotes {
+        return Err(CsvError::Line {
+            line: line_number,
+            message: "unterminated quoted field".to_string(),
+        });
+    }
+    cells.push(cell);
+    Ok(cells)
+}
+
+fn is_missing(cell: &str) -> bool {
+    let cell = cell.trim();
+    cell.is_empty()
+        || cell == "?"
+        || cell.eq_ignore_ascii_case("na")
+        || cell.eq_ignore_ascii_case("nan")
+        || cell.eq_ignore_ascii_case("null")
+}
+
+fn median(values: &mut [f64]) -> f64 {
+    if values.is_empty() {
+        return 0.0;
+    }
+    values.sort_by(|a, b| a.total_cmp(b));
+    let mid = values.len() / 2;
+    if values.len().is_multiple_of(2) {
+        (values[mid - 1] + values[mid]) / 2.0
+    } else {
+        values[mid]
+    }
+}
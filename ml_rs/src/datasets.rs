@@ -0,0 +1,583 @@
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use crate::csv_loader::LabelEncoding;
+use serde::{Deserialize, Serialize};
+
+/// Kind of problem a dataset is made for
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Task {
+    Classification,
+    Regression,
+}
+
+/// Description of a generated dataset
+#[derive(Clone, Debug)]
+pub struct DatasetInfo {
+    pub name: String,
+    pub task: Task,
+    /// Number of classes (classification only)
+    pub num_classes: Option<usize>,
+    /// How the classes are encoded in the targets (classification only); tells
+    /// whether predictions need `metrics::to_bipolar` before scoring
+    pub label_encoding: Option<LabelEncoding>,
+    pub seed: u64,
+    /// Standard deviation of the Gaussian noise that was added
+    pub noise: f64,
+}
+
+/// Samples in the layout the models take:
+/// `inputs[k]` is a sample, `targets[k]` its expected output (the `outputs` of `train`)
+#[derive(Clone, Debug)]
+pub struct Dataset {
+    pub inputs: Vec<Vec<f64>>,
+    pub targets: Vec<Vec<f64>>,
+    pub metadata: DatasetInfo,
+}
+
+impl Dataset {
+    pub fn len(&self) -> usize {
+        self.inputs.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.inputs.is_empty()
+    }
+
+    pub fn input_dim(&self) -> usize {
+        self.inputs.first().map_or(0, |x| x.len())
+    }
+
+    pub fn output_dim(&self) -> usize {
+        self.targets.first().map_or(0, |y| y.len())
+    }
+}
+
+/// Python-style `x % 0.5`, always in [0, 0.5)
+pub fn mod_0_5_python_style(x: f64) -> f64 {
+    let m = x % 0.5;
+    if m < 0.0 { m + 0.5 } else { m }
+}
+
+/// Standard normal sample (Box-Muller)
+pub(crate) fn gaussian(rng: &mut StdRng) -> f64 {
+    let u1: f64 = 1.0 - rng.gen::<f64>();
+    let u2: f64 = rng.gen::<f64>();
+    (-2.0 * u1.ln()).sqrt() * (2.0 * std::f64::consts::PI * u2).cos()
+}
+
+fn add_noise(values: &mut [f64], noise: f64, rng: &mut StdRng) {
+    if noise > 0.0 {
+        for v in values.iter_mut() {
+            *v += noise * gaussian(rng);
+        }
+    }
+}
+
+fn info(name: &str, task: Task, num_classes: Option<usize>, seed: u64, noise: f64) -> DatasetInfo {
+    DatasetInfo {
+        name: name.to_string(),
+        task,
+        num_classes,
+        label_encoding: match task {
+            Task::Classification => Some(LabelEncoding::Bipolar),
+            Task::Regression => None,
+        },
+        seed,
+        noise,
+    }
+}
+
+/// Fixed course case: the base points are repeated until `num_samples` is reached,
+/// then noise is added to the inputs (classification) or to the targets (regression)
+fn repeat_fixed(
+    base_inputs: &[Vec<f64>],
+    base_targets: &[Vec<f64>],
+    info: DatasetInfo,
+    num_samples: usize,
+) -> Dataset {
+    let mut rng = StdRng::seed_from_u64(info.seed);
+    let mut inputs = Vec::with_capacity(num_samples);
+    let mut targets = Vec::with_capacity(num_samples);
+    for k in 0..num_samples {
+        let mut x = base_inputs[k % base_inputs.len()].clone();
+        let mut y = base_targets[k % base_targets.len()].clone();
+        match info.task {
+            Task::Classification => add_noise(&mut x, info.noise, &mut rng),
+            Task::Regression => add_noise(&mut y, info.noise, &mut rng),
+        }
+        inputs.push(x);
+        targets.push(y);
+    }
+
+    Dataset {
+        inputs,
+        targets,
+        metadata: info,
+    }
+}
+
+// #### CLASSIFICATION ####
+// Noise is added to the inputs after labelling.
+// The fixed cases give the original points with `num_samples` = 3 or 4 and `noise` = 0.
+
+/// Linear Simple: 3 points, (1, 1) -> +1, (2, 3) and (3, 3) -> -1
+pub fn linear_simple(seed: u64, num_samples: usize, noise: f64) -> Dataset {
+    repeat_fixed(
+        &[vec![1.0, 1.0], vec![2.0, 3.0], vec![3.0, 3.0]],
+        &[vec![1.0], vec![-1.0], vec![-1.0]],
+        info("Linear Simple", Task::Classification, Some(2), seed, noise),
+        num_samples,
+    )
+}
+
+/// Linear Multiple: first half uniform in [1, 1.9]^2 -> +1, second half in [2, 2.9]^2 -> -1
+/// (course case: 100 samples)
+pub fn linear_multiple(seed: u64, num_samples: usize, noise: f64) -> Dataset {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut inputs = Vec::with_capacity(num_samples);
+    let mut targets = Vec::with_capacity(num_samples);
+
+    let half = num_samples / 2;
+    for k in 0..num_samples {
+        let (offset, label) = if k < half { (1.0, 1.0) } else { (2.0, -1.0) };
+        let x = rng.gen::<f64>() * 0.9 + offset;
+        let y = rng.gen::<f64>() * 0.9 + offset;
+        inputs.push(vec![x, y]);
+        targets.push(vec![label]);
+    }
+    for x in inputs.iter_mut() {
+        add_noise(x, noise, &mut rng);
+    }
+
+    Dataset {
+        inputs,
+        targets,
+        metadata: info("Linear Multiple", Task::Classification, Some(2), seed, noise),
+    }
+}
+
+/// XOR: the 4 corners of the unit square, target 1 when exactly one coordinate is 1, else 0
+pub fn xor(seed: u64, num_samples: usize, noise: f64) -> Dataset {
+    repeat_fixed(
+        &[vec![0.0, 0.0], vec![0.0, 1.0], vec![1.0, 0.0], vec![1.0, 1.0]],
+        &[vec![0.0], vec![1.0], vec![1.0], vec![0.0]],
+        DatasetInfo {
+            label_encoding: Some(LabelEncoding::Index),
+            ..info("XOR", Task::Classification, Some(2), seed, noise)
+        },
+        num_samples,
+    )
+}
+
+/// Cross: uniform in [-1, 1]^2, +1 inside the |x| <= 0.3 or |y| <= 0.3 stripes, else -1
+/// (course case: 500 samples)
+pub fn cross(seed: u64, num_samples: usize, noise: f64) -> Dataset {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut inputs = Vec::with_capacity(num_samples);
+    let mut targets = Vec::with_capacity(num_samples);
+
+    for _ in 0..num_samples {
+        let x = rng.gen::<f64>() * 2.0 - 1.0;
+        let y = rng.gen::<f64>() * 2.0 - 1.0;
+
+        let inside_vertical_stripe = x.abs() <= 0.3;
+        let inside_horizontal_stripe = y.abs() <= 0.3;
+
+        let label = if inside_vertical_stripe || inside_horizontal_stripe {
+            1.0
+        } else {
+            -1.0
+        };
+
+        inputs.push(vec![x, y]);
+        targets.push(vec![label]);
+    }
+    for x in inputs.iter_mut() {
+        add_noise(x, noise, &mut rng);
+    }
+
+    Dataset {
+        inputs,
+        targets,
+        metadata: info("Cross", Task::Classification, Some(2), seed, noise),
+    }
+}
+
+/// Three Classes: `num_samples` draws uniform in [-1, 1]^2, three triangular regions
+/// separated by lines, draws outside every region are dropped (about 40 %), so the
+/// dataset is smaller than `num_samples`. Targets are [+1, -1, -1] style vectors.
+/// (course case: 500 draws, about 300 samples kept)
+pub fn three_classes(seed: u64, num_samples: usize, noise: f64) -> Dataset {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut inputs = Vec::with_capacity(num_samples);
+    let mut targets = Vec::with_capacity(num_samples);
+
+    for _ in 0..num_samples {
+        let x = rng.gen::<f64>() * 2.0 - 1.0;
+        let y = rng.gen::<f64>() * 2.0 - 1.0;
+
+        let v1 = -x - y - 0.5;
+        let v2 =  x - y - 0.5;
+
+        let label = if v1 > 0.0 && y < 0.0 && v2 < 0.0 {
+            Some(vec![ 1.0, -1.0, -1.0])
+        } else if v1 < 0.0 && y > 0.0 && v2 < 0.0 {
+            Some(vec![-1.0,  1.0, -1.0])
+        } else if v1 < 0.0 && y < 0.0 && v2 > 0.0 {
+            Some(vec![-1.0, -1.0,  1.0])
+        } else {
+            None
+        };
+
+        if let Some(lbl) = label {
+            inputs.push(vec![x, y]);
+            targets.push(lbl);
+        }
+    }
+    for x in inputs.iter_mut() {
+        add_noise(x, noise, &mut rng);
+    }
+
+    Dataset {
+        inputs,
+        targets,
+        metadata: info("Three Classes", Task::Classification, Some(3), seed, noise),
+    }
+}
+
+/// Multi Cross: uniform in [-1, 1]^2, 3 classes given by the position modulo 0.5
+/// (course case: 1000 samples)
+pub fn multi_cross(seed: u64, num_samples: usize, noise: f64) -> Dataset {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut inputs = Vec::with_capacity(num_samples);
+    let mut targets = Vec::with_capacity(num_samples);
+
+    for _ in 0..num_samples {
+        let x = rng.gen::<f64>() * 2.0 - 1.0;
+        let y = rng.gen::<f64>() * 2.0 - 1.0;
+
+        // Python-style remainder in [0, 0.5)
+        let xm = mod_0_5_python_style(x).abs();
+        let ym = mod_0_5_python_style(y).abs();
+
+        let label = if xm <= 0.25 && ym > 0.25 {
+            vec![ 1.0, -1.0, -1.0]
+        } else if xm > 0.25 && ym <= 0.25 {
+            vec![-1.0,  1.0, -1.0]
+        } else {
+            vec![-1.0, -1.0,  1.0]
+        };
+
+        inputs.push(vec![x, y]);
+        targets.push(label);
+    }
+    for x in inputs.iter_mut() {
+        add_noise(x, noise, &mut rng);
+    }
+
+    Dataset {
+        inputs,
+        targets,
+        metadata: info("Multi Cross", Task::Classification, Some(3), seed, noise),
+    }
+}
+
+// #### REGRESSION ####
+// Noise is added to the targets.
+// The course cases are given by `num_samples` = number of points and `noise` = 0.
+
+/// Linear Simple 2D: y = 2x on x = 1, 2
+pub fn linear_simple_2d(seed: u64, num_samples: usize, noise: f64) -> Dataset {
+    repeat_fixed(
+        &[vec![1.0], vec![2.0]],
+        &[vec![2.0], vec![4.0]],
+        info("Linear Simple 2D", Task::Regression, None, seed, noise),
+        num_samples,
+    )
+}
+
+/// Non-Linear Simple 2D: (1, 2), (2, 3), (3, 2.5)
+pub fn non_linear_simple_2d(seed: u64, num_samples: usize, noise: f64) -> Dataset {
+    repeat_fixed(
+        &[vec![1.0], vec![2.0], vec![3.0]],
+        &[vec![2.0], vec![3.0], vec![2.5]],
+        info("Non-Linear Simple 2D", Task::Regression, None, seed, noise),
+        num_samples,
+    )
+}
+
+/// Linear Simple 3D: 3 points of a plane
+pub fn linear_simple_3d(seed: u64, num_samples: usize, noise: f64) -> Dataset {
+    repeat_fixed(
+        &[vec![1.0, 1.0], vec![2.0, 2.0], vec![3.0, 1.0]],
+        &[vec![2.0], vec![3.0], vec![2.5]],
+        info("Linear Simple 3D", Task::Regression, None, seed, noise),
+        num_samples,
+    )
+}
+
+/// Linear Tricky 3D: 3 aligned points (x1 = x2), y = x1
+pub fn linear_tricky_3d(seed: u64, num_samples: usize, noise: f64) -> Dataset {
+    repeat_fixed(
+        &[vec![1.0, 1.0], vec![2.0, 2.0], vec![3.0, 3.0]],
+        &[vec![1.0], vec![2.0], vec![3.0]],
+        info("Linear Tricky 3D", Task::Regression, None, seed, noise),
+        num_samples,
+    )
+}
+
+/// Non-Linear Simple 3D: the 4 corners of the unit square with XOR-like targets
+pub fn non_linear_simple_3d(seed: u64, num_samples: usize, noise: f64) -> Dataset {
+    repeat_fixed(
+        &[vec![1.0, 0.0], vec![0.0, 1.0], vec![1.0, 1.0], vec![0.0, 0.0]],
+        &[vec![2.0], vec![1.0], vec![-2.0], vec![-1.0]],
+        info("Non-Linear Simple 3D", Task::Regression, None, seed, noise),
+        num_samples,
+    )
+}
+
+// #### SYNTHETIC ####
+// Parametric datasets to stress-test architectures beyond the course cases.
+// Classification: noise is added to the inputs after labelling, samples are spread
+// evenly over the classes. Two classes give a single ±1 target (second class = +1, as
+// in the CSV loader), more classes give one ±1 value per class.
+// Regression: noise is added to the targets.
+
+fn class_target(class: usize, num_classes: usize) -> Vec<f64> {
+    if num_classes == 2 {
+        vec![if class == 1 { 1.0 } else { -1.0 }]
+    } else {
+        (0..num_classes)
+            .map(|c| if c == class { 1.0 } else { -1.0 })
+            .collect()
+    }
+}
+
+/// Two interleaving half circles: upper moon centred on (0, 0), lower moon on (1, 0.5)
+pub fn two_moons(seed: u64, num_samples: usize, noise: f64) -> Dataset {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut inputs = Vec::with_capacity(num_samples);
+    let mut targets = Vec::with_capacity(num_samples);
+
+    for k in 0..num_samples {
+        let class = k % 2;
+        let t = rng.gen::<f64>() * std::f64::consts::PI;
+        let mut x = if class == 0 {
+            vec![t.cos(), t.sin()]
+        } else {
+            vec![1.0 - t.cos(), 0.5 - t.sin()]
+        };
+        add_noise(&mut x, noise, &mut rng);
+        inputs.push(x);
+        targets.push(class_target(class, 2));
+    }
+
+    Dataset {
+        inputs,
+        targets,
+        metadata: info("Two Moons", Task::Classification, Some(2), seed, noise),
+    }
+}
+
+/// Two concentric circles: outer radius 1 (-1), inner radius `factor` in (0, 1) (+1)
+pub fn concentric_circles(seed: u64, num_samples: usize, noise: f64, factor: f64) -> Dataset {
+    assert!(factor > 0.0 && factor < 1.0, "factor must be in (0, 1)");
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut inputs = Vec::with_capacity(num_samples);
+    let mut targets = Vec::with_capacity(num_samples);
+
+    for k in 0..num_samples {
+        let class = k % 2;
+        let radius = if class == 0 { 1.0 } else { factor };
+        let t = rng.gen::<f64>() * 2.0 * std::f64::consts::PI;
+        let mut x = vec![radius * t.cos(), radius * t.sin()];
+        add_noise(&mut x, noise, &mut rng);
+        inputs.push(x);
+        targets.push(class_target(class, 2));
+    }
+
+    Dataset {
+        inputs,
+        targets,
+        metadata: info("Concentric Circles", Task::Classification, Some(2), seed, noise),
+    }
+}
+
+/// `num_arms` interleaved spirals (1.5 turns each, radius up to 1), one class per arm
+pub fn spirals(seed: u64, num_samples: usize, noise: f64, num_arms: usize) -> Dataset {
+    assert!(num_arms >= 2, "Need at least two arms");
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut inputs = Vec::with_capacity(num_samples);
+    let mut targets = Vec::with_capacity(num_samples);
+
+    for k in 0..num_samples {
+        let class = k % num_arms;
+        let r = 0.05 + 0.95 * rng.gen::<f64>();
+        let angle = r * 3.0 * std::f64::consts::PI
+            + 2.0 * std::f64::consts::PI * class as f64 / num_arms as f64;
+        let mut x = vec![r * angle.cos(), r * angle.sin()];
+        add_noise(&mut x, noise, &mut rng);
+        inputs.push(x);
+        targets.push(class_target(class, num_arms));
+    }
+
+    Dataset {
+        inputs,
+        targets,
+        metadata: info("Spirals", Task::Classification, Some(num_arms), seed, noise),
+    }
+}
+
+/// `num_blobs` isotropic Gaussian blobs in 2-D, one class per blob.
+/// Centres are uniform in [-1, 1]^2 and `noise` is the spread (standard deviation) of each blob.
+pub fn blobs(seed: u64, num_samples: usize, noise: f64, num_blobs: usize) -> Dataset {
+    assert!(num_blobs >= 2, "Need at least two blobs");
+    let mut rng = StdRng::seed_from_u64(seed);
+    let centres: Vec<Vec<f64>> = (0..num_blobs)
+        .map(|_| vec![rng.gen::<f64>() * 2.0 - 1.0, rng.gen::<f64>() * 2.0 - 1.0])
+        .collect();
+
+    let mut inputs = Vec::with_capacity(num_samples);
+    let mut targets = Vec::with_capacity(num_samples);
+    for k in 0..num_samples {
+        let class = k % num_blobs;
+        let mut x = centres[class].clone();
+        add_noise(&mut x, noise, &mut rng);
+        inputs.push(x);
+        targets.push(class_target(class, num_blobs));
+    }
+
+    Dataset {
+        inputs,
+        targets,
+        metadata: info("Blobs", Task::Classification, Some(num_blobs), seed, noise),
+    }
+}
+
+/// Checkerboard of `num_cells` x `num_cells` squares over [-1, 1]^2, alternating ±1
+pub fn checkerboard(seed: u64, num_samples: usize, noise: f64, num_cells: usize) -> Dataset {
+    assert!(num_cells >= 1, "Need at least one cell");
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut inputs = Vec::with_capacity(num_samples);
+    let mut targets = Vec::with_capacity(num_samples);
+
+    for _ in 0..num_samples {
+        let x = rng.gen::<f64>() * 2.0 - 1.0;
+        let y = rng.gen::<f64>() * 2.0 - 1.0;
+        let cx = (((x + 1.0) / 2.0 * num_cells as f64) as usize).min(num_cells - 1);
+        let cy = (((y + 1.0) / 2.0 * num_cells as f64) as usize).min(num_cells - 1);
+
+        let mut sample = vec![x, y];
+        add_noise(&mut sample, noise, &mut rng);
+        inputs.push(sample);
+        targets.push(class_target((cx + cy) % 2, 2));
+    }
+
+    Dataset {
+        inputs,
+        targets,
+        metadata: info("Checkerboard", Task::Classification, Some(2), seed, noise),
+    }
+}
+
+/// Friedman #1: x uniform in [0, 1]^10 (only the first 5 features matter),
+/// y = 10 sin(pi x1 x2) + 20 (x3 - 0.5)^2 + 10 x4 + 5 x5
+pub fn friedman1(seed: u64, num_samples: usize, noise: f64) -> Dataset {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut inputs = Vec::with_capacity(num_samples);
+    let mut targets = Vec::with_capacity(num_samples);
+
+    for _ in 0..num_samples {
+        let x: Vec<f64> = (0..10).map(|_| rng.gen::<f64>()).collect();
+        let mut y = vec![
+            10.0 * (std::f64::consts::PI * x[0] * x[1]).sin()
+                + 20.0 * (x[2] - 0.5).powi(2)
+                + 10.0 * x[3]
+                + 5.0 * x[4],
+        ];
+        add_noise(&mut y, noise, &mut rng);
+        inputs.push(x);
+        targets.push(y);
+    }
+
+    Dataset {
+        inputs,
+        targets,
+        metadata: info("Friedman 1", Task::Regression, None, seed, noise),
+    }
+}
+
+/// Friedman #2 / #3 inputs: x1 in [0, 100], x2 in [40 pi, 560 pi], x3 in [0, 1], x4 in [1, 11]
+fn friedman_inputs(rng: &mut StdRng) -> Vec<f64> {
+    let pi = std::f64::consts::PI;
+    vec![
+        rng.gen::<f64>() * 100.0,
+        40.0 * pi + rng.gen::<f64>() * 520.0 * pi,
+        rng.gen::<f64>(),
+        1.0 + rng.gen::<f64>() * 10.0,
+    ]
+}
+
+/// Friedman #2: y = sqrt(x1^2 + (x2 x3 - 1 / (x2 x4))^2)
+pub fn friedman2(seed: u64, num_samples: usize, noise: f64) -> Dataset {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut inputs = Vec::with_capacity(num_samples);
+    let mut targets = Vec::with_capacity(num_samples);
+
+    for _ in 0..num_samples {
+        let x = friedman_inputs(&mut rng);
+        let mut y = vec![(x[0].powi(2) + (x[1] * x[2] - 1.0 / (x[1] * x[3])).powi(2)).sqrt()];
+        add_noise(&mut y, noise, &mut rng);
+        inputs.push(x);
+        targets.push(y);
+    }
+
+    Dataset {
+        inputs,
+        targets,
+        metadata: info("Friedman 2", Task::Regression, None, seed, noise),
+    }
+}
+
+/// Friedman #3: y = atan((x2 x3 - 1 / (x2 x4)) / x1)
+pub fn friedman3(seed: u64, num_samples: usize, noise: f64) -> Dataset {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut inputs = Vec::with_capacity(num_samples);
+    let mut targets = Vec::with_capacity(num_samples);
+
+    for _ in 0..num_samples {
+        let x = friedman_inputs(&mut rng);
+        let mut y = vec![((x[1] * x[2] - 1.0 / (x[1] * x[3])) / x[0]).atan()];
+        add_noise(&mut y, noise, &mut rng);
+        inputs.push(x);
+        targets.push(y);
+    }
+
+    Dataset {
+        inputs,
+        targets,
+        metadata: info("Friedman 3", Task::Regression, None, seed, noise),
+    }
+}
+
+/// Sinusoid: x uniform in [-pi, pi], y = sin(x)
+pub fn sinusoid(seed: u64, num_samples: usize, noise: f64) -> Dataset {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut inputs = Vec::with_capacity(num_samples);
+    let mut targets = Vec::with_capacity(num_samples);
+
+    for _ in 0..num_samples {
+        let x = (rng.gen::<f64>() * 2.0 - 1.0) * std::f64::consts::PI;
+        let mut y = vec![x.sin()];
+        add_noise(&mut y, noise, &mut rng);
+        inputs.push(vec![x]);
+        targets.push(y);
+    }
+
+    Dataset {
+        inputs,
+        targets,
+        metadata: info("Sinusoid", Task::Regression, None, seed, noise),
+    }
+}
@@ -0,0 +1,378 @@
+use crate::encoding::{argmax, encode_classes};
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+
+/// Impurity measure used to choose the splits
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Criterion {
+    /// Classification: 1 - sum_c p_c^2
+    Gini,
+    /// Classification: -sum_c p_c * log2(p_c)
+    Entropy,
+    /// Regression: mean squared error around the mean (summed over outputs)
+    Mse,
+}
+
+impl Criterion {
+    pub fn is_classification(&self) -> bool {
+        !matches!(self, Criterion::Mse)
+    }
+}
+
+/// Stopping rules of the tree growth
+#[derive(Clone, Copy, Debug)]
+pub struct TreeParams {
+    /// Maximum depth (root = depth 0)
+    pub max_depth: usize,
+    /// A node with fewer samples is not split
+    pub min_samples_split: usize,
+    /// Each child of a split must keep at least this many samples
+    pub min_samples_leaf: usize,
+    /// A split is kept only if it lowers the weighted impurity by at least this much
+    pub min_impurity_decrease: f64,
+}
+
+impl Default for TreeParams {
+    fn default() -> Self {
+        TreeParams {
+            max_depth: usize::MAX,
+            min_samples_split: 2,
+            min_samples_leaf: 1,
+            min_impurity_decrease: 0.0,
+        }
+    }
+}
+
+/// Training targets once encoded
+pub(crate) enum Targets<'a> {
+    /// Class index of each sample
+    Classes { labels: &'a [usize], num_classes: usize },
+    /// Raw target vectors
+    Values(&'a [Vec<f64>]),
+}
+
+enum Node {
+    /// Class probabilities (classification) or mean target (regression)
+    Leaf { value: Vec<f64> },
+    Split {
+        feature: usize,
+        threshold: f64,
+        left: usize,
+        right: usize,
+    },
+}
+
+/// CART decision tree (binary splits `x[feature] <= threshold`).
+///
+/// - Classification (`Gini` / `Entropy`): targets are a single value (±1, 0 / 1 or a
+///   class index) or one value per class, `predict` returns the target vector of the
+///   predicted class as given in training.
+/// - Regression (`Mse`): targets are any Vec<f64>, `predict` returns the leaf mean.
+pub struct DecisionTree {
+    criterion: Criterion,
+    params: TreeParams,
+    /// Number of features tried at each split (None = all of them)
+    max_features: Option<usize>,
+    seed: Option<u64>,
+    /// nodes[0] is the root
+    nodes: Vec<Node>,
+    /// Total impurity decrease brought by each feature
+    importances: Vec<f64>,
+    /// Target vector of each class (classification only)
+    pub(crate) class_outputs: Vec<Vec<f64>>,
+    /// Number of training samples (weights the impurity decreases)
+    num_samples: usize,
+}
+
+impl DecisionTree {
+    pub fn new(criterion: Criterion, params: TreeParams) -> Self {
+        DecisionTree {
+            criterion,
+            params,
+            max_features: None,
+            seed: None,
+            nodes: Vec::new(),
+            importances: Vec::new(),
+            class_outputs: Vec::new(),
+            num_samples: 0,
+        }
+    }
+
+    /// Tree that only looks at `max_features` random features per split (used by `RandomForest`)
+    pub(crate) fn with_feature_subsampling(
+        criterion: Criterion,
+        params: TreeParams,
+        max_features: Option<usize>,
+        seed: Option<u64>,
+    ) -> Self {
+        DecisionTree {
+            max_features,
+            seed,
+            ..Self::new(criterion, params)
+        }
+    }
+
+    /// Grow the tree on all samples
+    pub fn fit(&mut self, inputs: &[Vec<f64>], outputs: &[Vec<f64>]) {
+        assert_eq!(inputs.len(), outputs.len());
+        assert!(!inputs.is_empty(), "Need at least one sample");
+
+        let samples: Vec<usize> = (0..inputs.len()).collect();
+        if self.criterion.is_classification() {
+            let (labels, class_outputs) = encode_classes(outputs);
+            let num_classes = class_outputs.len();
+            self.class_outputs = class_outputs;
+            self.fit_samples(inputs, &Targets::Classes { labels: &labels, num_classes }, &samples);
+        } else {
+            self.fit_samples(inputs, &Targets::Values(outputs), &samples);
+        }
+    }
+
+    /// Grow the tree on `samples` (indices into `inputs`, repetitions allowed)
+    pub(crate) fn fit_samples(&mut self, inputs: &[Vec<f64>], targets: &Targets, samples: &[usize]) {
+        let mut rng = match self.seed {
+            Some(s) => StdRng::seed_from_u64(s),
+            None => StdRng::from_entropy(),
+        };
+
+        let num_features = inputs[0].len();
+        self.nodes.clear();
+        self.importances = vec![0.0; num_features];
+        self.num_samples = samples.len();
+
+        let mut samples = samples.to_vec();
+        self.grow(inputs, targets, &mut samples, 0, &mut rng);
+
+        let total: f64 = self.importances.iter().sum();
+        if total > 0.0 {
+            for imp in self.importances.iter_mut() {
+                *imp /= total;
+            }
+        }
+    }
+
+    /// Build the subtree over `samples`, returns its node id
+    fn grow(
+        &mut self,
+        inputs: &[Vec<f64>],
+        targets: &Targets,
+        samples: &mut [usize],
+        depth: usize,
+        rng: &mut StdRng,
+    ) -> usize {
+        let id = self.nodes.len();
+        let value = leaf_value(targets, samples);
+        let n = samples.len();
+        let impurity = self.impurity(&node_stats(targets, samples), n);
+
+        let can_split = depth < self.params.max_depth
+            && n >= self.params.min_samples_split
+            && n >= 2 * self.params.min_samples_leaf
+            && impurity > 0.0;
+
+        let split = if can_split {
+            self.best_split(inputs, targets, samples, impurity, rng)
+        } else {
+            None
+        };
+
+        match split {
+            Some((feature, threshold, decrease)) => {
+                self.importances[feature] += decrease;
+
+                samples.sort_by(|&a, &b| inputs[a][feature].total_cmp(&inputs[b][feature]));
+                let mid = samples.partition_point(|&i| inputs[i][feature] <= threshold);
+
+                self.nodes.push(Node::Leaf { value: Vec::new() });
+                let (left_samples, right_samples) = samples.split_at_mut(mid);
+                let left = self.grow(inputs, targets, left_samples, depth + 1, rng);
+                let right = self.grow(inputs, targets, right_samples, depth + 1, rng);
+                self.nodes[id] = Node::Split {
+                    feature,
+                    threshold,
+                    left,
+                    right,
+                };
+            }
+            None => self.nodes.push(Node::Leaf { value }),
+        }
+        id
+    }
+
+    /// (feature, threshold, n * impurity decrease) of the best split, if any is worth it
+    fn best_split(
+        &self,
+        inputs: &[Vec<f64>],
+        targets: &Targets,
+        samples: &[usize],
+        impurity: f64,
+        rng: &mut StdRng,
+    ) -> Option<(usize, f64, f64)> {
+        let n = samples.len();
+        let num_features = inputs[0].len();
+        let mut features: Vec<usize> = (0..num_features).collect();
+        if let Some(m) = self.max_features {
+            features.shuffle(rng);
+            features.truncate(m.clamp(1, num_features));
+        }
+
+        let total = node_stats(targets, samples);
+        let min_leaf = self.params.min_samples_leaf.max(1);
+        let mut best: Option<(usize, f64, f64)> = None;
+        let mut sorted = samples.to_vec();
+
+        for &f in &features {
+            sorted.sort_by(|&a, &b| inputs[a][f].total_cmp(&inputs[b][f]));
+
+            // Sweep the thresholds, moving one sample at a time to the left child
+            let mut left = vec![0.0; total.len()];
+            for pos in 0..n - 1 {
+                add_sample(targets, sorted[pos], &mut left);
+
+                let x = inputs[sorted[pos]][f];
+                let x_next = inputs[sorted[pos + 1]][f];
+                let n_left = pos + 1;
+                let n_right = n - n_left;
+                if x == x_next || n_left < min_leaf || n_right < min_leaf {
+                    continue;
+                }
+
+                let right: Vec<f64> = total.iter().zip(left.iter()).map(|(t, l)| t - l).collect();
+                let children = n_left as f64 * self.impurity(&left, n_left)
+                    + n_right as f64 * self.impurity(&right, n_right);
+                let decrease = n as f64 * impurity - children;
+
+                if best.is_none_or(|(_, _, d)| decrease > d) {
+                    best = Some((f, (x + x_next) / 2.0, decrease));
+                }
+            }
+        }
+
+        // Decrease weighted by the share of the training samples reaching this node
+        best.filter(|&(_, _, d)| {
+            d > 0.0 && d / self.num_samples as f64 >= self.params.min_impurity_decrease
+        })
+    }
+
+    /// Impurity of a node from its statistics (see `node_stats`)
+    fn impurity(&self, stats: &[f64], n: usize) -> f64 {
+        if n == 0 {
+            return 0.0;
+        }
+        let n = n as f64;
+        match self.criterion {
+            Criterion::Gini => 1.0 - stats.iter().map(|&c| (c / n) * (c / n)).sum::<f64>(),
+            Criterion::Entropy => -stats
+                .iter()
+                .filter(|&&c| c > 0.0)
+                .map(|&c| (c / n) * (c / n).log2())
+                .sum::<f64>(),
+            Criterion::Mse => {
+                // stats = [sum_1, sum_sq_1, sum_2, sum_sq_2, ...]
+                let mut mse = 0.0;
+                for pair in stats.chunks(2) {
+                    let mean = pair[0] / n;
+                    mse += (pair[1] / n - mean * mean).max(0.0);
+                }
+                mse
+            }
+        }
+    }
+
+    /// Leaf reached by `input`: class probabilities or mean target
+    fn leaf(&self, input: &[f64]) -> &[f64] {
+        assert!(!self.nodes.is_empty(), "DecisionTree must be fitted first");
+        let mut node = 0;
+        loop {
+            match &self.nodes[node] {
+                Node::Leaf { value } => return value,
+                Node::Split {
+                    feature,
+                    threshold,
+                    left,
+                    right,
+                } => {
+                    node = if input[*feature] <= *threshold { *left } else { *right };
+                }
+            }
+        }
+    }
+
+    /// Class probabilities (classification only)
+    pub fn predict_proba(&self, input: &[f64]) -> Vec<f64> {
+        assert!(self.criterion.is_classification(), "predict_proba needs a classification tree");
+        self.leaf(input).to_vec()
+    }
+
+    /// Predicted target vector: class output (classification) or leaf mean (regression)
+    pub fn predict(&self, input: &[f64]) -> Vec<f64> {
+        let value = self.leaf(input);
+        if self.criterion.is_classification() {
+            self.class_outputs[argmax(value)].clone()
+        } else {
+            value.to_vec()
+        }
+    }
+
+    /// Normalised impurity decrease brought by each feature (sums to 1, all 0 when the
+    /// tree never splits)
+    pub fn feature_importances(&self) -> &[f64] {
+        &self.importances
+    }
+
+    pub fn depth(&self) -> usize {
+        self.node_depth(0)
+    }
+
+    fn node_depth(&self, node: usize) -> usize {
+        match &self.nodes[node] {
+            Node::Leaf { .. } => 0,
+            Node::Split { left, right, .. } => 1 + self.node_depth(*left).max(self.node_depth(*right)),
+        }
+    }
+
+    pub fn num_leaves(&self) -> usize {
+        self.nodes
+            .iter()
+            .filter(|n| matches!(n, Node::Leaf { .. }))
+            .count()
+    }
+}
+
+/// Sufficient statistics of a node:
+/// class counts (classification) or [sum, sum of squares] per output (regression)
+fn node_stats(targets: &Targets, samples: &[usize]) -> Vec<f64> {
+    let len = match targets {
+        Targets::Classes { num_classes, .. } => *num_classes,
+        Targets::Values(values) => 2 * values[0].len(),
+    };
+    let mut stats = vec![0.0; len];
+    for &i in samples {
+        add_sample(targets, i, &mut stats);
+    }
+    stats
+}
+
+fn add_sample(targets: &Targets, i: usize, stats: &mut [f64]) {
+    match targets {
+        Targets::Classes { labels, .. } => stats[labels[i]] += 1.0,
+        Targets::Values(values) => {
+            for (k, &v) in values[i].iter().enumerate() {
+                stats[2 * k] += v;
+                stats[2 * k + 1] += v * v;
+            }
+        }
+    }
+}
+
+fn leaf_value(targets: &Targets, samples: &[usize]) -> Vec<f64> {
+    let n = samples.len() as f64;
+    match targets {
+        Targets::Classes { .. } => node_stats(targets, samples).iter().map(|c| c / n).collect(),
+        Targets::Values(_) => node_stats(targets, samples)
+            .chunks(2)
+            .map(|pair| pair[0] / n)
+            .collect(),
+    }
+}
@@ -0,0 +1,276 @@
+use std::cmp::Ordering;
+use std::fmt;
+
+/// What `transform` does with a category that was not seen by `fit`
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum UnknownCategory {
+    /// Return `EncodeError::UnknownCategory`
+    Error,
+    /// Encode as "no known category": all 0 (one-hot), all -1 (bipolar), -1 (label / ordinal)
+    Ignore,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum EncodeError {
+    /// Category not seen during `fit` (its `Debug` representation)
+    UnknownCategory(String),
+}
+
+impl fmt::Display for EncodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EncodeError::UnknownCategory(c) => write!(f, "unknown category {}", c),
+        }
+    }
+}
+
+impl std::error::Error for EncodeError {}
+
+/// Index of the largest value (first one on ties), the class picked from a
+/// one-hot / bipolar output vector
+pub fn argmax(v: &[f64]) -> usize {
+    let mut best = 0;
+    for (i, &x) in v.iter().enumerate() {
+        if x > v[best] {
+            best = i;
+        }
+    }
+    best
+}
+
+/// Whether a single-value target is the positive class. The cut is at 0, so ±1 and
+/// 0 / 1 targets both give two classes.
+pub fn is_positive_class(y: f64) -> bool {
+    y > 0.0
+}
+
+/// ±1 form of a single-value target
+pub fn bipolar_class(y: f64) -> f64 {
+    if is_positive_class(y) {
+        1.0
+    } else {
+        -1.0
+    }
+}
+
+/// Class index of each sample, and the target vector of each class (in order of
+/// first appearance). Classes are keyed on the exact target, so single-value targets
+/// give one class per distinct value: two for ±1 or 0 / 1, one per index for class indices.
+pub(crate) fn encode_classes(outputs: &[Vec<f64>]) -> (Vec<usize>, Vec<Vec<f64>>) {
+    let mut labels = Vec::with_capacity(outputs.len());
+    let mut class_outputs: Vec<Vec<f64>> = Vec::new();
+    for y in outputs {
+        let class = match class_outputs.iter().position(|c| c == y) {
+            Some(c) => c,
+            None => {
+                class_outputs.push(y.clone());
+                class_outputs.len() - 1
+            }
+        };
+        labels.push(class);
+    }
+    (labels, class_outputs)
+}
+
+/// Sorted distinct values
+fn categories<T: Clone + PartialOrd>(values: &[T]) -> Vec<T> {
+    let mut categories: Vec<T> = Vec::new();
+    for v in values {
+        if !categories.contains(v) {
+            categories.push(v.clone());
+        }
+    }
+    categories.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+    categories
+}
+
+/// Class label <-> class index (0..num_classes, labels in sorted order)
+#[derive(Clone, Debug)]
+pub struct LabelEncoder<T> {
+    classes: Vec<T>,
+    unknown: UnknownCategory,
+}
+
+impl<T: Clone + PartialOrd + fmt::Debug> LabelEncoder<T> {
+    pub fn new(unknown: UnknownCategory) -> Self {
+        LabelEncoder {
+            classes: Vec::new(),
+            unknown,
+        }
+    }
+
+    pub fn fit(&mut self, labels: &[T]) {
+        self.classes = categories(labels);
+    }
+
+    /// Index of `label`, None if unknown
+    pub fn index(&self, label: &T) -> Option<usize> {
+        self.classes.iter().position(|c| c == label)
+    }
+
+    /// Index of `label` as a number (-1 for an ignored unknown label)
+    pub fn transform(&self, label: &T) -> Result<f64, EncodeError> {
+        match (self.index(label), self.unknown) {
+            (Some(i), _) => Ok(i as f64),
+            (None, UnknownCategory::Ignore) => Ok(-1.0),
+            (None, UnknownCategory::Error) => Err(EncodeError::UnknownCategory(format!("{:?}", label))),
+        }
+    }
+
+    /// Label of a (possibly non-integer) model output: nearest class index, clamped to the classes
+    pub fn inverse_transform(&self, value: f64) -> &T {
+        assert!(!self.classes.is_empty(), "Encoder must be fitted first");
+        let i = value.round().clamp(0.0, (self.classes.len() - 1) as f64) as usize;
+        &self.classes[i]
+    }
+
+    pub fn classes(&self) -> &[T] {
+        &self.classes
+    }
+
+    pub fn num_classes(&self) -> usize {
+        self.classes.len()
+    }
+}
+
+/// Values used for "this class" / "another class"
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum OneHotStyle {
+    /// 1 / 0
+    ZeroOne,
+    /// +1 / -1, the targets of the course cases (tanh outputs, sign of a perceptron)
+    Bipolar,
+}
+
+/// Class label <-> one value per class
+#[derive(Clone, Debug)]
+pub struct OneHotEncoder<T> {
+    labels: LabelEncoder<T>,
+    style: OneHotStyle,
+}
+
+impl<T: Clone + PartialOrd + fmt::Debug> OneHotEncoder<T> {
+    pub fn new(style: OneHotStyle, unknown: UnknownCategory) -> Self {
+        OneHotEncoder {
+            labels: LabelEncoder::new(unknown),
+            style,
+        }
+    }
+
+    pub fn fit(&mut self, labels: &[T]) {
+        self.labels.fit(labels);
+    }
+
+    fn values(&self) -> (f64, f64) {
+        match self.style {
+            OneHotStyle::ZeroOne => (1.0, 0.0),
+            OneHotStyle::Bipolar => (1.0, -1.0),
+        }
+    }
+
+    pub fn transform(&self, label: &T) -> Result<Vec<f64>, EncodeError> {
+        let (on, off) = self.values();
+        let index = match (self.labels.index(label), self.labels.unknown) {
+            (Some(i), _) => Some(i),
+            (None, UnknownCategory::Ignore) => None,
+            (None, UnknownCategory::Error) => return Err(EncodeError::UnknownCategory(format!("{:?}", label))),
+        };
+        Ok((0..self.num_classes())
+            .map(|c| if Some(c) == index { on } else { off })
+            .collect())
+    }
+
+    pub fn transform_all(&self, labels: &[T]) -> Result<Vec<Vec<f64>>, EncodeError> {
+        labels.iter().map(|l| self.transform(l)).collect()
+    }
+
+    /// Single-value targets of the one-vs-rest problem of class `class_index`
+    /// (what each of the binary models of a one-vs-all scheme is trained on)
+    pub fn one_vs_rest(&self, labels: &[T], class_index: usize) -> Vec<Vec<f64>> {
+        assert!(class_index < self.num_classes(), "Class index out of range");
+        let (on, off) = self.values();
+        labels
+            .iter()
+            .map(|l| vec![if self.labels.index(l) == Some(class_index) { on } else { off }])
+            .collect()
+    }
+
+    /// Label of a model output vector: the class with the largest output
+    pub fn inverse_transform(&self, output: &[f64]) -> &T {
+        assert_eq!(output.len(), self.num_classes(), "Output size must match the number of classes");
+        &self.labels.classes[argmax(output)]
+    }
+
+    pub fn classes(&self) -> &[T] {
+        self.labels.classes()
+    }
+
+    pub fn num_classes(&self) -> usize {
+        self.labels.num_classes()
+    }
+}
+
+/// Categorical features -> one number per feature (category index).
+///
+/// Categories are sorted per feature unless given explicitly (e.g. small < medium < large).
+#[derive(Clone, Debug)]
+pub struct OrdinalEncoder<T> {
+    /// categories[j] = categories of feature j in encoding order
+    categories: Vec<Vec<T>>,
+    unknown: UnknownCategory,
+}
+
+impl<T: Clone + PartialOrd + fmt::Debug> OrdinalEncoder<T> {
+    pub fn new(unknown: UnknownCategory) -> Self {
+        OrdinalEncoder {
+            categories: Vec::new(),
+            unknown,
+        }
+    }
+
+    /// Fixed category order for each feature, no `fit` needed
+    pub fn with_categories(categories: Vec<Vec<T>>, unknown: UnknownCategory) -> Self {
+        OrdinalEncoder { categories, unknown }
+    }
+
+    /// `rows[k][j]` = category of feature j for sample k
+    pub fn fit(&mut self, rows: &[Vec<T>]) {
+        assert!(!rows.is_empty(), "Need at least one sample");
+        let d = rows[0].len();
+        self.categories = (0..d)
+            .map(|j| {
+                let column: Vec<T> = rows.iter().map(|r| r[j].clone()).collect();
+                categories(&column)
+            })
+            .collect();
+    }
+
+    pub fn transform(&self, row: &[T]) -> Result<Vec<f64>, EncodeError> {
+        assert_eq!(row.len(), self.categories.len(), "Row size must match the fitted features");
+        row.iter()
+            .zip(self.categories.iter())
+            .map(|(v, cats)| match (cats.iter().position(|c| c == v), self.unknown) {
+                (Some(i), _) => Ok(i as f64),
+                (None, UnknownCategory::Ignore) => Ok(-1.0),
+                (None, UnknownCategory::Error) => Err(EncodeError::UnknownCategory(format!("{:?}", v))),
+            })
+            .collect()
+    }
+
+    pub fn transform_all(&self, rows: &[Vec<T>]) -> Result<Vec<Vec<f64>>, EncodeError> {
+        rows.iter().map(|r| self.transform(r)).collect()
+    }
+
+    /// Categories of encoded values (rounded and clamped to the known indices)
+    pub fn inverse_transform(&self, encoded: &[f64]) -> Vec<T> {
+        encoded
+            .iter()
+            .zip(self.categories.iter())
+            .map(|(&v, cats)| cats[v.round().clamp(0.0, (cats.len() - 1) as f64) as usize].clone())
+            .collect()
+    }
+
+    pub fn categories(&self) -> &[Vec<T>] {
+        &self.categories
+    }
+}
@@ -0,0 +1,548 @@
+// Experiments described in TOML or JSON files instead of code.
+//
+// A file holds one experiment, or several as `[[experiment]]` tables (a JSON array or
+// an `"experiment"` list in JSON):
+//
+//     [[experiment]]
+//     name = "MLP on XOR"
+//     seed = 0
+//     metrics = ["accuracy"]
+//     dataset = { generator = "xor", num_samples = 4 }
+//     model = { type = "mlp", layers = [2, 2, 1] }
+//     training = { iterations = 500000, alpha = 0.1, schedule = { type = "step", every = 250000, factor = 0.5 } }
+//
+// The seed drives the generated dataset, the validation split, the initial weights and
+// the order the samples are drawn in, so a config always gives the same result.
+
+use crate::csv_loader::{load_csv, Column, CsvError, CsvOptions, LabelEncoding};
+use crate::cross_validation::Scorer;
+use crate::datasets::{self, Dataset, Task};
+use crate::linear_perceptron::LinearPerceptron;
+use crate::metrics;
+use crate::naive_multi_layer_perceptron::MyMLP;
+use crate::saved_model::Model;
+use crate::scaling::{Scaler, ScalerKind};
+use crate::split::{class_labels, train_test_split, SplitStrategy};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::Instant;
+
+#[derive(Debug)]
+pub enum ExperimentError {
+    Io(io::Error),
+    /// A config file that is not valid TOML / JSON or does not describe experiments
+    Config { path: PathBuf, message: String },
+    Csv(CsvError),
+    /// Inconsistent settings (unknown generator or metric, layers not matching the data, ...)
+    Invalid(String),
+}
+
+impl fmt::Display for ExperimentError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExperimentError::Io(e) => write!(f, "I/O error: {}", e),
+            ExperimentError::Config { path, message } => write!(f, "{}: {}", path.display(), message),
+            ExperimentError::Csv(e) => write!(f, "dataset file: {}", e),
+            ExperimentError::Invalid(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+impl std::error::Error for ExperimentError {}
+
+impl From<io::Error> for ExperimentError {
+    fn from(e: io::Error) -> Self {
+        ExperimentError::Io(e)
+    }
+}
+
+impl From<CsvError> for ExperimentError {
+    fn from(e: CsvError) -> Self {
+        ExperimentError::Csv(e)
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ExperimentConfig {
+    pub name: String,
+    #[serde(default)]
+    pub seed: u64,
+    /// Taken from the generator when not given, required for dataset files
+    pub task: Option<Task>,
+    pub dataset: DatasetConfig,
+    pub model: ModelConfig,
+    #[serde(default)]
+    pub training: TrainingConfig,
+    /// Names of `metrics::by_name`, default accuracy (classification) or mse and r2
+    #[serde(default)]
+    pub metrics: Vec<String>,
+}
+
+/// Either a generator of `datasets` or a CSV file
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct DatasetConfig {
+    /// Name of a generator function, e.g. "xor", "cross", "two_moons", "friedman1"
+    pub generator: Option<String>,
+    #[serde(default)]
+    pub num_samples: usize,
+    #[serde(default)]
+    pub noise: f64,
+    /// Seed of the generator, default the experiment seed
+    pub seed: Option<u64>,
+    /// Extra argument of concentric_circles (factor), spirals (arms), blobs (blobs)
+    /// and checkerboard (cells)
+    pub param: Option<f64>,
+    /// CSV file, relative to the config file
+    pub path: Option<PathBuf>,
+    /// Target column of the file (header name or 0-based position), default the last one
+    pub target: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ModelConfig {
+    /// One perceptron per output
+    Linear,
+    /// Neurons per layer, input and output included (as `MyMLP::new`)
+    Mlp { layers: Vec<usize> },
+}
+
+/// Minimised loss; the models only implement the squared error so far
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Loss {
+    #[default]
+    Mse,
+}
+
+/// How the weights are updated; the models only implement plain SGD so far
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Optimizer {
+    /// Stochastic gradient descent, one random sample per step
+    #[default]
+    Sgd,
+}
+
+/// Learning rate over the iterations
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Schedule {
+    #[default]
+    Constant,
+    /// Multiplied by `factor` every `every` iterations
+    Step { every: usize, factor: f64 },
+    /// Halved every `half_life` iterations, smoothly
+    Exponential { half_life: f64 },
+    /// alpha / (1 + decay * iteration)
+    InverseTime { decay: f64 },
+}
+
+impl Schedule {
+    /// Learning rate at `iteration` (0-based) for an initial rate `alpha`
+    pub fn rate(&self, alpha: f64, iteration: usize) -> f64 {
+        match *self {
+            Schedule::Constant => alpha,
+            Schedule::Step { every, factor } => alpha * factor.powi((iteration / every.max(1)) as i32),
+            Schedule::Exponential { half_life } => alpha * 0.5f64.powf(iteration as f64 / half_life),
+            Schedule::InverseTime { decay } => alpha / (1.0 + decay * iteration as f64),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TrainingConfig {
+    pub iterations: usize,
+    /// Initial learning rate
+    pub alpha: f64,
+    pub loss: Loss,
+    pub optimizer: Optimizer,
+    pub schedule: Schedule,
+    /// Fraction of the samples held out and scored, 0 = training scores only
+    pub validation: f64,
+    /// Input scaling fitted on the training samples
+    pub scaling: Option<ScalerKind>,
+}
+
+impl Default for TrainingConfig {
+    fn default() -> Self {
+        TrainingConfig {
+            iterations: 100_000,
+            alpha: 0.01,
+            loss: Loss::Mse,
+            optimizer: Optimizer::Sgd,
+            schedule: Schedule::Constant,
+            validation: 0.0,
+            scaling: None,
+        }
+    }
+}
+
+/// Outcome of one experiment
+#[derive(Clone, Debug, Serialize)]
+pub struct ExperimentResult {
+    pub name: String,
+    pub dataset: String,
+    /// Model kind and layer sizes, e.g. "mlp 2-4-1"
+    pub model: String,
+    pub task: Task,
+    pub train_size: usize,
+    pub validation_size: usize,
+    pub iterations: usize,
+    pub metric_names: Vec<String>,
+    pub train_scores: Vec<f64>,
+    /// None without validation samples
+    pub validation_scores: Option<Vec<f64>>,
+    pub seconds: f64,
+}
+
+/// Read the experiments of a .toml / .json file, or of every such file of a directory
+/// (in name order)
+pub fn load_configs<P: AsRef<Path>>(path: P) -> Result<Vec<ExperimentConfig>, ExperimentError> {
+    let path = path.as_ref();
+    if path.is_dir() {
+        let mut files: Vec<PathBuf> = fs::read_dir(path)?
+            .map(|entry| entry.map(|e| e.path()))
+            .collect::<Result<_, _>>()?;
+        files.retain(|f| f.extension().is_some_and(|e| e == "toml" || e == "json"));
+        files.sort();
+        let mut configs = Vec::new();
+        for file in files {
+            configs.extend(load_configs(file)?);
+        }
+        return Ok(configs);
+    }
+
+    parse_configs(&fs::read_to_string(path)?, path)
+}
+
+/// Experiments of a .toml / .json text; `path` tells the format, names the config in
+/// errors and is where relative dataset files are looked up from
+pub fn parse_configs<P: AsRef<Path>>(text: &str, path: P) -> Result<Vec<ExperimentConfig>, ExperimentError> {
+    let path = path.as_ref();
+    let config_error = |message: String| ExperimentError::Config {
+        path: path.to_path_buf(),
+        message,
+    };
+    let mut configs: Vec<ExperimentConfig> = if path.extension().is_some_and(|e| e == "json") {
+        let value: serde_json::Value = serde_json::from_str(text).map_err(|e| config_error(e.to_string()))?;
+        let list = match value {
+            serde_json::Value::Object(mut map) if map.contains_key("experiment") => map.remove("experiment").unwrap(),
+            serde_json::Value::Array(_) => value,
+            one => serde_json::Value::Array(vec![one]),
+        };
+        serde_json::from_value(list).map_err(|e| config_error(e.to_string()))?
+    } else {
+        let mut table: toml::Table = toml::from_str(text).map_err(|e| config_error(e.to_string()))?;
+        match table.remove("experiment") {
+            Some(list) => list.try_into().map_err(|e: toml::de::Error| config_error(e.to_string()))?,
+            None => vec![toml::Value::Table(table)
+                .try_into()
+                .map_err(|e: toml::de::Error| config_error(e.to_string()))?],
+        }
+    };
+
+    // Dataset files are relative to the config file
+    let dir = path.parent().unwrap_or(Path::new(""));
+    for config in &mut configs {
+        if let Some(file) = &config.dataset.path {
+            if file.is_relative() {
+                config.dataset.path = Some(dir.join(file));
+            }
+        }
+    }
+    Ok(configs)
+}
+
+/// Generate or load the samples of an experiment, with their task
+fn load_dataset(config: &ExperimentConfig) -> Result<(Dataset, Task), ExperimentError> {
+    let d = &config.dataset;
+    match (&d.generator, &d.path) {
+        (Some(generator), None) => {
+            if d.num_samples == 0 {
+                return Err(ExperimentError::Invalid("dataset.num_samples must be at least 1".to_string()));
+            }
+            let (seed, n, noise) = (d.seed.unwrap_or(config.seed), d.num_samples, d.noise);
+            // Counts are whole numbers of at least `min`
+            let count = |default: usize, min: usize, what: &str| match d.param {
+                None => Ok(default),
+                Some(p) if p.fract() == 0.0 && p >= min as f64 => Ok(p as usize),
+                Some(p) => Err(ExperimentError::Invalid(format!(
+                    "dataset.param ({}) must be an integer of at least {}, got {}",
+                    what, min, p
+                ))),
+            };
+            let dataset = match generator.as_str() {
+                "linear_simple" => datasets::linear_simple(seed, n, noise),
+                "linear_multiple" => datasets::linear_multiple(seed, n, noise),
+                "xor" => datasets::xor(seed, n, noise),
+                "cross" => datasets::cross(seed, n, noise),
+                "three_classes" => datasets::three_classes(seed, n, noise),
+                "multi_cross" => datasets::multi_cross(seed, n, noise),
+                "linear_simple_2d" => datasets::linear_simple_2d(seed, n, noise),
+                "non_linear_simple_2d" => datasets::non_linear_simple_2d(seed, n, noise),
+                "linear_simple_3d" => datasets::linear_simple_3d(seed, n, noise),
+                "linear_tricky_3d" => datasets::linear_tricky_3d(seed, n, noise),
+                "non_linear_simple_3d" => datasets::non_linear_simple_3d(seed, n, noise),
+                "two_moons" => datasets::two_moons(seed, n, noise),
+                "concentric_circles" => {
+                    let factor = d.param.unwrap_or(0.5);
+                    if !(factor > 0.0 && factor < 1.0) {
+                        return Err(ExperimentError::Invalid(format!(
+                            "dataset.param (factor) must be in (0, 1), got {}",
+                            factor
+                        )));
+                    }
+                    datasets::concentric_circles(seed, n, noise, factor)
+                }
+                "spirals" => datasets::spirals(seed, n, noise, count(2, 2, "arms")?),
+                "blobs" => datasets::blobs(seed, n, noise, count(3, 2, "blobs")?),
+                "checkerboard" => datasets::checkerboard(seed, n, noise, count(4, 1, "cells")?),
+                "friedman1" => datasets::friedman1(seed, n, noise),
+                "friedman2" => datasets::friedman2(seed, n, noise),
+                "friedman3" => datasets::friedman3(seed, n, noise),
+                "sinusoid" => datasets::sinusoid(seed, n, noise),
+                other => return Err(ExperimentError::Invalid(format!("unknown dataset generator \"{}\"", other))),
+            };
+            let task = config.task.unwrap_or(dataset.metadata.task);
+            Ok((dataset, task))
+        }
+        (None, Some(path)) => {
+            let task = config
+                .task
+                .ok_or_else(|| ExperimentError::Invalid("task is required with a dataset file".to_string()))?;
+            let encoding = match task {
+                Task::Classification => LabelEncoding::Bipolar,
+                Task::Regression => LabelEncoding::Numeric,
+            };
+            let mut options = CsvOptions::new(encoding);
+            if let Some(target) = &d.target {
+                options.targets = vec![match target.parse() {
+                    Ok(i) => Column::Index(i),
+                    Err(_) => Column::Name(target.clone()),
+                }];
+            }
+            Ok((load_csv(path, &options)?.dataset, task))
+        }
+        _ => Err(ExperimentError::Invalid(
+            "the dataset needs either a generator or a path".to_string(),
+        )),
+    }
+}
+
+/// Train and score the model of one experiment
+pub fn run_experiment(config: &ExperimentConfig) -> Result<ExperimentResult, ExperimentError> {
+    let start = Instant::now();
+    let invalid = |message: String| Err(ExperimentError::Invalid(message));
+    let (dataset, task) = load_dataset(config)?;
+    if dataset.is_empty() {
+        return invalid("the dataset has no samples".to_string());
+    }
+    let is_classification = task == Task::Classification;
+
+    let metric_names: Vec<String> = if !config.metrics.is_empty() {
+        config.metrics.clone()
+    } else if is_classification {
+        vec!["accuracy".to_string()]
+    } else {
+        vec!["mse".to_string(), "r2".to_string()]
+    };
+    let mut scorers: Vec<Scorer> = Vec::with_capacity(metric_names.len());
+    for name in &metric_names {
+        match metrics::by_name(name) {
+            Some(scorer) => scorers.push(scorer),
+            None => return invalid(format!("unknown metric \"{}\"", name)),
+        }
+    }
+
+    let training = &config.training;
+    if training.iterations == 0 || training.alpha <= 0.0 {
+        return invalid("training.iterations and training.alpha must be positive".to_string());
+    }
+    if !(0.0..1.0).contains(&training.validation) {
+        return invalid("training.validation must be in [0, 1)".to_string());
+    }
+
+    let labels = class_labels(&dataset.targets);
+    let strategy = if is_classification { SplitStrategy::Stratified(&labels) } else { SplitStrategy::Random };
+    let split = train_test_split(&dataset.inputs, &dataset.targets, training.validation, strategy, config.seed);
+    let scaler = training.scaling.map(|kind| {
+        let mut scaler = Scaler::new(kind);
+        scaler.fit(&split.train_inputs);
+        scaler
+    });
+    let scale = |inputs: &[Vec<f64>]| match &scaler {
+        Some(scaler) => scaler.transform_all(inputs),
+        None => inputs.to_vec(),
+    };
+    if split.train_inputs.is_empty() {
+        return invalid("training.validation leaves no training samples".to_string());
+    }
+    let train_inputs = scale(&split.train_inputs);
+    let validation_inputs = scale(&split.test_inputs);
+
+    let (input_dim, output_dim) = (dataset.input_dim(), dataset.output_dim());
+    let mut model = match &config.model {
+        ModelConfig::Linear => Model::Linear(
+            (0..output_dim)
+                .map(|o| LinearPerceptron::with_seed(input_dim, config.seed.wrapping_add(o as u64)))
+                .collect(),
+        ),
+        ModelConfig::Mlp { layers } => {
+            if layers.len() < 2 || layers.contains(&0) {
+                return invalid("model.layers needs at least two non-empty layers".to_string());
+            }
+            if layers[0] != input_dim || layers[layers.len() - 1] != output_dim {
+                return invalid(format!(
+                    "model.layers must start with {} inputs and end with {} outputs for this dataset",
+                    input_dim, output_dim
+                ));
+            }
+            Model::Mlp(MyMLP::with_seed(layers, config.seed))
+        }
+    };
+
+    // Sample order from its own stream, independent of the initial weights
+    let mut rng = StdRng::seed_from_u64(config.seed ^ 0x5EED);
+    for it in 0..training.iterations {
+        let k = rng.gen_range(0..train_inputs.len());
+        let alpha = training.schedule.rate(training.alpha, it);
+        model.train_step(&train_inputs[k], &split.train_outputs[k], is_classification, alpha);
+    }
+
+    let mut score = |inputs: &[Vec<f64>], outputs: &[Vec<f64>]| -> Vec<f64> {
+        let mut predictions: Vec<Vec<f64>> = inputs.iter().map(|x| model.predict(x, is_classification)).collect();
+        if let (Some(encoding), Some(num_classes)) = (dataset.metadata.label_encoding, dataset.metadata.num_classes) {
+            predictions = metrics::to_bipolar(&predictions, encoding, num_classes);
+        }
+        scorers.iter().map(|s| s(&predictions, outputs)).collect()
+    };
+    let train_scores = score(&train_inputs, &split.train_outputs);
+    let validation_scores = if validation_inputs.is_empty() {
+        None
+    } else {
+        Some(score(&validation_inputs, &split.test_outputs))
+    };
+
+    let layers: Vec<String> = model.layers().iter().map(|n| n.to_string()).collect();
+    Ok(ExperimentResult {
+        name: config.name.clone(),
+        dataset: dataset.metadata.name.clone(),
+        model: format!("{} {}", model.name(), layers.join("-")),
+        task,
+        train_size: train_inputs.len(),
+        validation_size: validation_inputs.len(),
+        iterations: training.iterations,
+        metric_names,
+        train_scores,
+        validation_scores,
+        seconds: start.elapsed().as_secs_f64(),
+    })
+}
+
+/// Run experiments in parallel over the available cores, results in the order of `configs`
+pub fn run_experiments(configs: &[ExperimentConfig]) -> Vec<Result<ExperimentResult, ExperimentError>> {
+    let num_threads = thread::available_parallelism().map_or(1, |p| p.get());
+    let chunk_size = configs.len().div_ceil(num_threads).max(1);
+    let mut results = Vec::with_capacity(configs.len());
+    thread::scope(|scope| {
+        let handles: Vec<_> = configs
+            .chunks(chunk_size)
+            .map(|chunk| scope.spawn(move || chunk.iter().map(run_experiment).collect::<Vec<_>>()))
+            .collect();
+        for handle in handles {
+            results.extend(handle.join().unwrap());
+        }
+    });
+    results
+}
+
+/// Results of several experiments, as Markdown, CSV or JSON
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct Report {
+    pub results: Vec<ExperimentResult>,
+}
+
+impl Report {
+    /// One row per experiment, scores as "train / validation"
+    pub fn to_markdown(&self) -> String {
+        let mut md = String::from("| experiment | dataset | model | samples | iterations | scores (train / validation) | seconds |\n");
+        md.push_str("|---|---|---|---|---|---|---|\n");
+        for r in &self.results {
+            let scores: Vec<String> = r
+                .metric_names
+                .iter()
+                .enumerate()
+                .map(|(m, name)| match &r.validation_scores {
+                    Some(v) => format!("{} {:.4} / {:.4}", name, r.train_scores[m], v[m]),
+                    None => format!("{} {:.4}", name, r.train_scores[m]),
+                })
+                .collect();
+            md.push_str(&format!(
+                "| {} | {} | {} | {} + {} | {} | {} | {:.2} |\n",
+                r.name,
+                r.dataset,
+                r.model,
+                r.train_size,
+                r.validation_size,
+                r.iterations,
+                scores.join(", "),
+                r.seconds
+            ));
+        }
+        md
+    }
+
+    /// One row per experiment and metric
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("experiment,dataset,model,metric,train,validation,seconds\n");
+        for r in &self.results {
+            for (m, name) in r.metric_names.iter().enumerate() {
+                csv.push_str(&format!(
+                    "\"{}\",\"{}\",\"{}\",{},{},{},{:.3}\n",
+                    r.name.replace('"', "\"\""),
+                    r.dataset,
+                    r.model,
+                    name,
+                    r.train_scores[m],
+                    r.validation_scores.as_ref().map_or(String::new(), |v| v[m].to_string()),
+                    r.seconds
+                ));
+            }
+        }
+        csv
+    }
+
+    /// Format from the extension: .md, .csv or .json
+    pub fn write<P: AsRef<Path>>(&self, path: P) -> Result<(), ExperimentError> {
+        let path = path.as_ref();
+        let text = match path.extension().and_then(|e| e.to_str()) {
+            Some("md") => self.to_markdown(),
+            Some("csv") => self.to_csv(),
+            Some("json") => serde_json::to_string_pretty(self).map_err(io::Error::other)?,
+            _ => {
+                return Err(ExperimentError::Invalid(format!(
+                    "unsupported report format {} (use .md, .csv or .json)",
+                    path.display()
+                )))
+            }
+        };
+        fs::write(path, text)?;
+        Ok(())
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_markdown())
+    }
+}
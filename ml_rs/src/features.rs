@@ -0,0 +1,209 @@
+use crate::datasets::gaussian;
+use crate::kmeans::{squared_distance, KMeans};
+use crate::linear_perceptron::LinearPerceptron;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+/// Maps a raw input to a (usually larger) feature vector
+pub trait FeatureMap {
+    fn transform(&self, input: &[f64]) -> Vec<f64>;
+
+    fn transform_all(&self, inputs: &[Vec<f64>]) -> Vec<Vec<f64>> {
+        inputs.iter().map(|x| self.transform(x)).collect()
+    }
+}
+
+/// Any `Fn(&[f64]) -> Vec<f64>` is a user-defined feature map,
+/// e.g. `|x: &[f64]| vec![x[0], x[1], x[0] * x[1]]`
+impl<F: Fn(&[f64]) -> Vec<f64>> FeatureMap for F {
+    fn transform(&self, input: &[f64]) -> Vec<f64> {
+        self(input)
+    }
+}
+
+/// Every monomial of the inputs of degree 1 to `degree`
+/// (x1, x2, x1², x1 x2, x2², ... for degree 2), ordered by degree.
+///
+/// With `interaction_only`, monomials repeating a feature (x1², x1² x2, ...) are left out.
+/// No constant term: the linear models already have a bias.
+#[derive(Clone, Debug)]
+pub struct PolynomialFeatures {
+    degree: usize,
+    interaction_only: bool,
+}
+
+impl PolynomialFeatures {
+    pub fn new(degree: usize, interaction_only: bool) -> Self {
+        assert!(degree >= 1, "Degree must be at least 1");
+        PolynomialFeatures {
+            degree,
+            interaction_only,
+        }
+    }
+
+    /// Feature indices of each monomial for inputs of size `input_dim`
+    pub fn terms(&self, input_dim: usize) -> Vec<Vec<usize>> {
+        let mut terms = Vec::new();
+        let mut current: Vec<Vec<usize>> = vec![Vec::new()];
+        for _ in 0..self.degree {
+            let mut next = Vec::new();
+            for term in &current {
+                // Non-decreasing indices (strictly increasing for interaction only)
+                let first = match term.last() {
+                    None => 0,
+                    Some(&last) if self.interaction_only => last + 1,
+                    Some(&last) => last,
+                };
+                for j in first..input_dim {
+                    let mut t = term.clone();
+                    t.push(j);
+                    next.push(t);
+                }
+            }
+            terms.extend(next.iter().cloned());
+            current = next;
+        }
+        terms
+    }
+}
+
+impl FeatureMap for PolynomialFeatures {
+    fn transform(&self, input: &[f64]) -> Vec<f64> {
+        self.terms(input.len())
+            .iter()
+            .map(|t| t.iter().map(|&j| input[j]).product())
+            .collect()
+    }
+}
+
+/// Random Fourier features (Rahimi & Recht): z(x) = sqrt(2 / D) cos(w · x + b)
+/// with w ~ N(0, 2 gamma I) and b ~ U[0, 2 pi], so that z(x) · z(y) approximates
+/// the RBF kernel exp(-gamma ||x - y||²) used by `svm::Kernel::Rbf`.
+#[derive(Clone, Debug)]
+pub struct RandomFourierFeatures {
+    /// weights[k] = w of the k-th feature
+    weights: Vec<Vec<f64>>,
+    offsets: Vec<f64>,
+}
+
+impl RandomFourierFeatures {
+    pub fn new(input_dim: usize, num_features: usize, gamma: f64, seed: u64) -> Self {
+        assert!(gamma > 0.0, "Gamma must be positive");
+        let mut rng = StdRng::seed_from_u64(seed);
+        let std = (2.0 * gamma).sqrt();
+        let weights = (0..num_features)
+            .map(|_| (0..input_dim).map(|_| std * gaussian(&mut rng)).collect())
+            .collect();
+        let offsets = (0..num_features)
+            .map(|_| rng.gen_range(0.0..2.0 * std::f64::consts::PI))
+            .collect();
+        RandomFourierFeatures { weights, offsets }
+    }
+}
+
+impl FeatureMap for RandomFourierFeatures {
+    fn transform(&self, input: &[f64]) -> Vec<f64> {
+        let scale = (2.0 / self.weights.len() as f64).sqrt();
+        self.weights
+            .iter()
+            .zip(self.offsets.iter())
+            .map(|(w, &b)| {
+                assert_eq!(w.len(), input.len(), "Input size must match the feature map");
+                let dot: f64 = w.iter().zip(input.iter()).map(|(a, x)| a * x).sum();
+                scale * (dot + b).cos()
+            })
+            .collect()
+    }
+}
+
+/// Gaussian RBF features: one exp(-gamma ||x - c||²) per centre c.
+///
+/// Centres usually come from a fitted `KMeans`; a linear model on these features is
+/// an RBF network.
+#[derive(Clone, Debug)]
+pub struct RbfFeatures {
+    centres: Vec<Vec<f64>>,
+    gamma: f64,
+}
+
+impl RbfFeatures {
+    pub fn new(centres: Vec<Vec<f64>>, gamma: f64) -> Self {
+        assert!(!centres.is_empty(), "Need at least one centre");
+        assert!(gamma > 0.0, "Gamma must be positive");
+        RbfFeatures { centres, gamma }
+    }
+
+    /// Centres = the centroids of a fitted `KMeans`
+    pub fn from_kmeans(kmeans: &KMeans, gamma: f64) -> Self {
+        assert!(!kmeans.centroids().is_empty(), "KMeans must be fitted first");
+        Self::new(kmeans.centroids().to_vec(), gamma)
+    }
+
+    pub fn centres(&self) -> &[Vec<f64>] {
+        &self.centres
+    }
+}
+
+impl FeatureMap for RbfFeatures {
+    fn transform(&self, input: &[f64]) -> Vec<f64> {
+        self.centres
+            .iter()
+            .map(|c| (-self.gamma * squared_distance(c, input)).exp())
+            .collect()
+    }
+}
+
+/// `LinearPerceptron` trained on expanded features: linear in the features,
+/// non-linear in the raw inputs (e.g. degree 2 polynomial features solve XOR)
+pub struct ExpandedPerceptron<F: FeatureMap> {
+    map: F,
+    input_dim: usize,
+    model: LinearPerceptron,
+}
+
+impl<F: FeatureMap> ExpandedPerceptron<F> {
+    pub fn new(map: F, input_dim: usize) -> Self {
+        let feature_dim = map.transform(&vec![0.0; input_dim]).len();
+        ExpandedPerceptron {
+            map,
+            input_dim,
+            model: LinearPerceptron::new(feature_dim),
+        }
+    }
+
+    fn expand(&self, input: &[f64]) -> Vec<f64> {
+        assert_eq!(input.len(), self.input_dim);
+        self.map.transform(input)
+    }
+
+    pub fn predict_raw(&self, input: &[f64]) -> f64 {
+        self.model.predict_raw(&self.expand(input))
+    }
+
+    pub fn predict_class(&self, input: &[f64]) -> f64 {
+        self.model.predict_class(&self.expand(input))
+    }
+
+    pub fn predict_regression(&self, input: &[f64]) -> f64 {
+        self.model.predict_regression(&self.expand(input))
+    }
+
+    pub fn train_classification(&mut self, inputs: &[Vec<f64>], outputs: &[Vec<f64>], num_iter: usize, alpha: f64) {
+        let features = self.map.transform_all(inputs);
+        self.model.train_classification(&features, outputs, num_iter, alpha);
+    }
+
+    pub fn train_regression(&mut self, inputs: &[Vec<f64>], outputs: &[Vec<f64>], num_iter: usize, alpha: f64) {
+        let features = self.map.transform_all(inputs);
+        self.model.train_regression(&features, outputs, num_iter, alpha);
+    }
+
+    pub fn feature_map(&self) -> &F {
+        &self.map
+    }
+
+    /// The underlying linear model (its weights are indexed by feature)
+    pub fn model(&self) -> &LinearPerceptron {
+        &self.model
+    }
+}
@@ -0,0 +1,119 @@
+// Numerical check of the `MyMLP` backpropagation.
+//
+// The analytic gradient of the loss 0.5 * ||output - expected||² (the one the training
+// steps follow) is compared with central differences
+// (loss(w + epsilon) - loss(w - epsilon)) / (2 * epsilon) for every weight, biases
+// included. A correct backpropagation gives relative errors around 1e-7 or below with
+// epsilon = 1e-5; a wrong index or derivative gives errors close to 1.
+
+use crate::naive_multi_layer_perceptron::MyMLP;
+
+/// Relative errors of gradients smaller than this are measured against it instead
+/// (both gradients ~0 is a match, not a 0 / 0)
+const GRADIENT_FLOOR: f64 = 1e-8;
+
+/// Both gradients of one weight W[layer][from][to] (from = 0 is the bias)
+#[derive(Clone, Debug, PartialEq)]
+pub struct WeightCheck {
+    pub layer: usize,
+    pub from: usize,
+    pub to: usize,
+    pub analytic: f64,
+    pub numerical: f64,
+    /// |analytic - numerical| / max(|analytic|, |numerical|)
+    pub relative_error: f64,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct GradientCheck {
+    /// One entry per trainable weight, layer by layer
+    pub weights: Vec<WeightCheck>,
+}
+
+impl GradientCheck {
+    pub fn max_relative_error(&self) -> f64 {
+        self.weights.iter().map(|w| w.relative_error).fold(0.0, f64::max)
+    }
+
+    /// Largest relative error of each weight layer (index 0 = layer 1)
+    pub fn max_relative_error_per_layer(&self) -> Vec<f64> {
+        let num_layers = self.weights.iter().map(|w| w.layer).max().unwrap_or(0);
+        let mut errors = vec![0.0; num_layers];
+        for w in &self.weights {
+            errors[w.layer - 1] = f64::max(errors[w.layer - 1], w.relative_error);
+        }
+        errors
+    }
+
+    /// Weight with the largest relative error
+    pub fn worst(&self) -> Option<&WeightCheck> {
+        self.weights
+            .iter()
+            .max_by(|a, b| a.relative_error.total_cmp(&b.relative_error))
+    }
+
+    pub fn passes(&self, tolerance: f64) -> bool {
+        self.max_relative_error() <= tolerance
+    }
+}
+
+/// Loss minimised by the training steps on one sample: 0.5 * ||output - expected||²
+pub fn sample_loss(mlp: &mut MyMLP, inputs: &[f64], expected_outputs: &[f64], is_classification: bool) -> f64 {
+    let outputs = mlp.predict(inputs, is_classification);
+    assert_eq!(outputs.len(), expected_outputs.len(), "Need one expected value per output");
+    0.5 * outputs
+        .iter()
+        .zip(expected_outputs.iter())
+        .map(|(o, e)| (o - e).powi(2))
+        .sum::<f64>()
+}
+
+/// Compare the backpropagated gradient of `sample_loss` with central differences of
+/// step `epsilon` (1e-5 is a good default), for every weight of `mlp` (left unchanged)
+pub fn check_gradients(
+    mlp: &MyMLP,
+    inputs: &[f64],
+    expected_outputs: &[f64],
+    is_classification: bool,
+    epsilon: f64,
+) -> GradientCheck {
+    assert!(epsilon > 0.0, "Need a positive step");
+    let mut mlp = mlp.clone();
+    let d = mlp.layers().to_vec();
+
+    let outputs = mlp.forward(inputs, is_classification);
+    let output_grad: Vec<f64> = outputs
+        .iter()
+        .zip(expected_outputs.iter())
+        .map(|(o, e)| o - e)
+        .collect();
+    let analytic = mlp.backward(&output_grad).weights;
+
+    let mut weights = Vec::new();
+    for l in 1..d.len() {
+        for i in 0..=d[l - 1] {
+            // j = 0 is the constant bias neuron, not a weight
+            for j in 1..=d[l] {
+                let original = mlp.W[l][i][j];
+                mlp.W[l][i][j] = original + epsilon;
+                let plus = sample_loss(&mut mlp, inputs, expected_outputs, is_classification);
+                mlp.W[l][i][j] = original - epsilon;
+                let minus = sample_loss(&mut mlp, inputs, expected_outputs, is_classification);
+                mlp.W[l][i][j] = original;
+
+                let numerical = (plus - minus) / (2.0 * epsilon);
+                let analytic = analytic[l][i][j];
+                let scale = analytic.abs().max(numerical.abs()).max(GRADIENT_FLOOR);
+                weights.push(WeightCheck {
+                    layer: l,
+                    from: i,
+                    to: j,
+                    analytic,
+                    numerical,
+                    relative_error: (analytic - numerical).abs() / scale,
+                });
+            }
+        }
+    }
+    GradientCheck { weights }
+}
@@ -0,0 +1,167 @@
+use crate::cross_validation::Scorer;
+use crate::metrics;
+use crate::plot::{plot_lines, ChartOptions, PlotError};
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// Paired inputs and expected outputs
+type Samples<'a> = (&'a [Vec<f64>], &'a [Vec<f64>]);
+
+/// What to record during training and how often
+#[derive(Clone, Debug)]
+pub struct Monitor<'a> {
+    /// Record every `every` iterations (and after the last one)
+    pub every: usize,
+    /// Held-out samples scored next to the training samples
+    pub validation: Option<Samples<'a>>,
+    /// Metrics recorded on top of the loss
+    pub metrics: Vec<(String, Scorer)>,
+}
+
+impl<'a> Monitor<'a> {
+    pub fn new(every: usize) -> Self {
+        assert!(every > 0, "Need to record at least every iteration");
+        Monitor {
+            every,
+            validation: None,
+            metrics: Vec::new(),
+        }
+    }
+
+    /// Also score the model on held-out samples ("validation_..." series)
+    pub fn validation(mut self, inputs: &'a [Vec<f64>], outputs: &'a [Vec<f64>]) -> Self {
+        assert_eq!(inputs.len(), outputs.len());
+        self.validation = Some((inputs, outputs));
+        self
+    }
+
+    /// Also record `metric` ("train_<name>" and "validation_<name>" series)
+    pub fn metric(mut self, name: &str, metric: Scorer) -> Self {
+        self.metrics.push((name.to_string(), metric));
+        self
+    }
+
+    /// Whether iteration `it` (0-based) of `num_iter` is recorded
+    pub fn is_due(&self, it: usize, num_iter: usize) -> bool {
+        (it + 1).is_multiple_of(self.every) || it + 1 == num_iter
+    }
+
+    /// Score the model on the training (and validation) samples and append a record.
+    ///
+    /// The loss is the mean squared error, the quantity the gradient steps minimise.
+    pub fn record<F>(
+        &self,
+        history: &mut TrainingHistory,
+        iteration: usize,
+        train_inputs: &[Vec<f64>],
+        train_outputs: &[Vec<f64>],
+        mut predict: F,
+    ) where
+        F: FnMut(&[f64]) -> Vec<f64>,
+    {
+        let mut values = Vec::new();
+        let mut score = |prefix: &str, inputs: &[Vec<f64>], outputs: &[Vec<f64>]| {
+            let predictions: Vec<Vec<f64>> = inputs.iter().map(|x| predict(x)).collect();
+            values.push((format!("{}_loss", prefix), metrics::mean_squared_error(&predictions, outputs)));
+            for (name, metric) in &self.metrics {
+                values.push((format!("{}_{}", prefix, name), metric(&predictions, outputs)));
+            }
+        };
+        score("train", train_inputs, train_outputs);
+        if let Some((inputs, outputs)) = self.validation {
+            score("validation", inputs, outputs);
+        }
+        history.push(iteration, &values);
+    }
+}
+
+/// Series of values recorded over the iterations of a training run
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TrainingHistory {
+    /// Iteration (1-based) of each record
+    pub iterations: Vec<usize>,
+    pub names: Vec<String>,
+    /// values[s][r] = series `names[s]` at record r
+    pub values: Vec<Vec<f64>>,
+}
+
+impl TrainingHistory {
+    pub fn new() -> Self {
+        TrainingHistory::default()
+    }
+
+    /// Append a record, series not seen before start at this record
+    /// (earlier records are NaN)
+    pub fn push(&mut self, iteration: usize, values: &[(String, f64)]) {
+        let records = self.iterations.len();
+        self.iterations.push(iteration);
+        for (name, value) in values {
+            let s = match self.names.iter().position(|n| n == name) {
+                Some(s) => s,
+                None => {
+                    self.names.push(name.clone());
+                    self.values.push(vec![f64::NAN; records]);
+                    self.names.len() - 1
+                }
+            };
+            self.values[s].push(*value);
+        }
+        for series in &mut self.values {
+            series.resize(records + 1, f64::NAN);
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.iterations.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.iterations.is_empty()
+    }
+
+    /// Values of a series, one per record
+    pub fn series(&self, name: &str) -> Option<&[f64]> {
+        self.names.iter().position(|n| n == name).map(|s| self.values[s].as_slice())
+    }
+
+    /// Last recorded value of a series
+    pub fn last(&self, name: &str) -> Option<f64> {
+        self.series(name).and_then(|v| v.last().cloned())
+    }
+
+    /// One row per record: iteration followed by every series
+    pub fn to_csv(&self) -> String {
+        let mut csv = format!("iteration,{}\n", self.names.join(","));
+        for (r, iteration) in self.iterations.iter().enumerate() {
+            let values: Vec<String> = self
+                .values
+                .iter()
+                .map(|v| if v[r].is_nan() { String::new() } else { v[r].to_string() })
+                .collect();
+            csv.push_str(&format!("{},{}\n", iteration, values.join(",")));
+        }
+        csv
+    }
+
+    pub fn write_csv<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        fs::write(path, self.to_csv())
+    }
+
+    /// Line chart of some series against the iteration (.png or .svg), e.g.
+    /// `&["train_loss", "validation_loss"]`; an empty list plots every series, an unknown
+    /// name is a `PlotError::UnknownSeries`
+    pub fn plot<P: AsRef<Path>>(&self, path: P, names: &[&str], options: &ChartOptions) -> Result<(), PlotError> {
+        let names: Vec<&str> = if names.is_empty() {
+            self.names.iter().map(|n| n.as_str()).collect()
+        } else {
+            names.to_vec()
+        };
+        let series = names
+            .iter()
+            .map(|&n| self.series(n).map(|s| (n, s)).ok_or_else(|| PlotError::UnknownSeries(n.to_string())))
+            .collect::<Result<Vec<(&str, &[f64])>, _>>()?;
+        let x: Vec<f64> = self.iterations.iter().map(|&i| i as f64).collect();
+        plot_lines(path, &x, &series, options)
+    }
+}
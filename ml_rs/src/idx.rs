@@ -0,0 +1,221 @@
+use crate::csv_loader::LabelEncoding;
+use crate::datasets::{Dataset, DatasetInfo, Task};
+use flate2::read::GzDecoder;
+use std::fmt;
+use std::fs;
+use std::io::Read;
+use std::path::Path;
+
+/// How pixel values (0..=255 for ubyte files) are scaled
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Normalization {
+    /// Raw values
+    None,
+    /// value / 255 -> [0, 1]
+    ZeroOne,
+    /// value / 127.5 - 1 -> [-1, 1], matches the tanh activations of `MyMLP`
+    MinusOneOne,
+}
+
+#[derive(Debug)]
+pub enum IdxError {
+    Io(std::io::Error),
+    /// The file is not a valid IDX file
+    Format(String),
+}
+
+impl fmt::Display for IdxError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IdxError::Io(e) => write!(f, "I/O error: {}", e),
+            IdxError::Format(message) => write!(f, "invalid IDX file: {}", message),
+        }
+    }
+}
+
+impl std::error::Error for IdxError {}
+
+impl From<std::io::Error> for IdxError {
+    fn from(e: std::io::Error) -> Self {
+        IdxError::Io(e)
+    }
+}
+
+/// Content of an IDX file: an n-dimensional array stored row-major
+#[derive(Clone, Debug)]
+pub struct IdxArray {
+    /// Size of each dimension, dims[0] = number of items
+    pub dims: Vec<usize>,
+    /// All values, converted to f64
+    pub data: Vec<f64>,
+}
+
+impl IdxArray {
+    /// Number of values per item (product of the dimensions after the first one)
+    pub fn item_size(&self) -> usize {
+        self.dims[1..].iter().product()
+    }
+
+    /// Item `k` flattened (e.g. a 28 x 28 image as 784 values)
+    pub fn item(&self, k: usize) -> &[f64] {
+        let size = self.item_size();
+        &self.data[k * size..(k + 1) * size]
+    }
+}
+
+/// Read an IDX file, gzip-compressed or not (detected from the content)
+pub fn read_idx<P: AsRef<Path>>(path: P) -> Result<IdxArray, IdxError> {
+    let raw = fs::read(path)?;
+    let bytes = if raw.starts_with(&[0x1f, 0x8b]) {
+        let mut decoded = Vec::new();
+        GzDecoder::new(&raw[..]).read_to_end(&mut decoded)?;
+        decoded
+    } else {
+        raw
+    };
+    parse_idx(&bytes)
+}
+
+/// Parse the bytes of an (uncompressed) IDX file.
+///
+/// Header: two zero bytes, the value type, the number of dimensions,
+/// then each dimension as a big-endian u32.
+pub fn parse_idx(bytes: &[u8]) -> Result<IdxArray, IdxError> {
+    if bytes.len() < 4 || bytes[0] != 0 || bytes[1] != 0 {
+        return Err(IdxError::Format("bad magic number".to_string()));
+    }
+    let value_size: usize = match bytes[2] {
+        0x08 | 0x09 => 1,
+        0x0B => 2,
+        0x0C | 0x0D => 4,
+        0x0E => 8,
+        t => return Err(IdxError::Format(format!("unknown value type 0x{:02X}", t))),
+    };
+    let num_dims = bytes[3] as usize;
+    if num_dims == 0 {
+        return Err(IdxError::Format("no dimensions".to_string()));
+    }
+
+    let header_len = 4 + 4 * num_dims;
+    if bytes.len() < header_len {
+        return Err(IdxError::Format("truncated header".to_string()));
+    }
+    let dims: Vec<usize> = bytes[4..header_len]
+        .chunks(4)
+        .map(|c| u32::from_be_bytes([c[0], c[1], c[2], c[3]]) as usize)
+        .collect();
+
+    // A crafted header must not overflow the size computation
+    let expected = dims
+        .iter()
+        .try_fold(value_size, |size, &d| size.checked_mul(d))
+        .and_then(|size| size.checked_add(header_len))
+        .ok_or_else(|| IdxError::Format(format!("dimensions {:?} are too large", dims)))?;
+    if bytes.len() != expected {
+        return Err(IdxError::Format(format!(
+            "expected {} bytes for dimensions {:?}, found {}",
+            expected,
+            dims,
+            bytes.len()
+        )));
+    }
+
+    let body = &bytes[header_len..];
+    let data = match bytes[2] {
+        0x08 => body.iter().map(|&b| b as f64).collect(),
+        0x09 => body.iter().map(|&b| b as i8 as f64).collect(),
+        0x0B => body
+            .chunks(2)
+            .map(|c| i16::from_be_bytes([c[0], c[1]]) as f64)
+            .collect(),
+        0x0C => body
+            .chunks(4)
+            .map(|c| i32::from_be_bytes([c[0], c[1], c[2], c[3]]) as f64)
+            .collect(),
+        0x0D => body
+            .chunks(4)
+            .map(|c| f32::from_be_bytes([c[0], c[1], c[2], c[3]]) as f64)
+            .collect(),
+        _ => body
+            .chunks(8)
+            .map(|c| f64::from_be_bytes([c[0], c[1], c[2], c[3], c[4], c[5], c[6], c[7]]))
+            .collect(),
+    };
+
+    Ok(IdxArray { dims, data })
+}
+
+/// Load an MNIST-style image / label pair into a `Dataset`.
+///
+/// Each image is flattened to one `Vec<f64>` sample (the layout `MyMLP::train` takes),
+/// labels 0..=max are encoded with `label_encoding` (`Numeric` is the same as `Index`,
+/// `Bipolar` always gives one ±1 value per class).
+pub fn load_mnist<P: AsRef<Path>, Q: AsRef<Path>>(
+    images_path: P,
+    labels_path: Q,
+    normalization: Normalization,
+    label_encoding: LabelEncoding,
+) -> Result<Dataset, IdxError> {
+    let images = read_idx(&images_path)?;
+    let labels = read_idx(&labels_path)?;
+    if images.dims.len() < 2 {
+        return Err(IdxError::Format("images need at least 2 dimensions".to_string()));
+    }
+    if labels.dims.len() != 1 {
+        return Err(IdxError::Format("labels must be 1-dimensional".to_string()));
+    }
+    if images.dims[0] != labels.dims[0] {
+        return Err(IdxError::Format(format!(
+            "{} images but {} labels",
+            images.dims[0], labels.dims[0]
+        )));
+    }
+
+    let num_classes = labels.data.iter().fold(0.0f64, |m, &l| m.max(l)) as usize + 1;
+    let mut inputs = Vec::with_capacity(images.dims[0]);
+    let mut targets = Vec::with_capacity(images.dims[0]);
+    for k in 0..images.dims[0] {
+        inputs.push(
+            images
+                .item(k)
+                .iter()
+                .map(|&v| match normalization {
+                    Normalization::None => v,
+                    Normalization::ZeroOne => v / 255.0,
+                    Normalization::MinusOneOne => v / 127.5 - 1.0,
+                })
+                .collect(),
+        );
+
+        let class = labels.data[k] as usize;
+        targets.push(match label_encoding {
+            LabelEncoding::Numeric | LabelEncoding::Index => vec![class as f64],
+            LabelEncoding::OneHot => (0..num_classes)
+                .map(|c| if c == class { 1.0 } else { 0.0 })
+                .collect(),
+            LabelEncoding::Bipolar => (0..num_classes)
+                .map(|c| if c == class { 1.0 } else { -1.0 })
+                .collect(),
+        });
+    }
+
+    let name = images_path
+        .as_ref()
+        .file_name()
+        .map_or_else(|| "idx".to_string(), |s| s.to_string_lossy().to_string());
+    Ok(Dataset {
+        inputs,
+        targets,
+        metadata: DatasetInfo {
+            name,
+            task: Task::Classification,
+            num_classes: Some(num_classes),
+            label_encoding: Some(match label_encoding {
+                LabelEncoding::Numeric => LabelEncoding::Index,
+                encoding => encoding,
+            }),
+            seed: 0,
+            noise: 0.0,
+        },
+    })
+}
@@ -0,0 +1,210 @@
+use crate::csv_loader::{encode_label, LabelEncoding};
+use crate::datasets::{Dataset, DatasetInfo, Task};
+use crate::idx::Normalization;
+use image::imageops::FilterType;
+use crate::split::{train_test_indices, SplitStrategy};
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Channels kept from each image
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ColorMode {
+    /// 1 value per pixel
+    Grayscale,
+    /// 3 values per pixel (r, g, b)
+    Rgb,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct ImageFolderOptions {
+    /// Every image is resized (without keeping the aspect ratio) to width x height
+    pub width: u32,
+    pub height: u32,
+    pub color: ColorMode,
+    pub normalization: Normalization,
+    pub label_encoding: LabelEncoding,
+}
+
+#[derive(Debug)]
+pub enum ImageFolderError {
+    Io(std::io::Error),
+    /// An image could not be decoded
+    Decode { path: PathBuf, message: String },
+    /// The folder layout is not usable (no class folder, no image, ...)
+    Invalid(String),
+}
+
+impl fmt::Display for ImageFolderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ImageFolderError::Io(e) => write!(f, "I/O error: {}", e),
+            ImageFolderError::Decode { path, message } => {
+                write!(f, "cannot decode {}: {}", path.display(), message)
+            }
+            ImageFolderError::Invalid(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+impl std::error::Error for ImageFolderError {}
+
+impl From<std::io::Error> for ImageFolderError {
+    fn from(e: std::io::Error) -> Self {
+        ImageFolderError::Io(e)
+    }
+}
+
+/// Images of a `root/<class name>/<image>` tree
+#[derive(Clone, Debug)]
+pub struct ImageFolder {
+    /// One flattened image per sample, row by row (r, g, b interleaved for RGB)
+    pub dataset: Dataset,
+    /// Class names (folder names) in index order
+    pub classes: Vec<String>,
+    /// Class index of each sample
+    pub labels: Vec<usize>,
+    /// File of each sample
+    pub paths: Vec<PathBuf>,
+}
+
+/// Load every PNG / JPEG image of `root/<class>/`.
+///
+/// Classes are the sub-folder names sorted alphabetically, images are read in
+/// file name order, so the result only depends on the content of the tree.
+pub fn load_image_folder<P: AsRef<Path>>(
+    root: P,
+    options: &ImageFolderOptions,
+) -> Result<ImageFolder, ImageFolderError> {
+    let root = root.as_ref();
+    let mut class_dirs: Vec<PathBuf> = Vec::new();
+    for entry in fs::read_dir(root)? {
+        let path = entry?.path();
+        if path.is_dir() && !is_hidden(&path) {
+            class_dirs.push(path);
+        }
+    }
+    class_dirs.sort();
+    if class_dirs.is_empty() {
+        return Err(ImageFolderError::Invalid(format!(
+            "no class folder in {}",
+            root.display()
+        )));
+    }
+
+    let classes: Vec<String> = class_dirs
+        .iter()
+        .map(|d| d.file_name().unwrap().to_string_lossy().to_string())
+        .collect();
+
+    let mut inputs = Vec::new();
+    let mut targets = Vec::new();
+    let mut labels = Vec::new();
+    let mut paths = Vec::new();
+    for (class, dir) in class_dirs.iter().enumerate() {
+        let mut files: Vec<PathBuf> = Vec::new();
+        for entry in fs::read_dir(dir)? {
+            let path = entry?.path();
+            if path.is_file() && !is_hidden(&path) && is_image(&path) {
+                files.push(path);
+            }
+        }
+        files.sort();
+
+        for path in files {
+            inputs.push(load_image(&path, options)?);
+            targets.push(encode_label(class, classes.len(), options.label_encoding));
+            labels.push(class);
+            paths.push(path);
+        }
+    }
+    if inputs.is_empty() {
+        return Err(ImageFolderError::Invalid(format!(
+            "no PNG / JPEG image under {}",
+            root.display()
+        )));
+    }
+
+    let name = root
+        .file_name()
+        .map_or_else(|| "images".to_string(), |s| s.to_string_lossy().to_string());
+    Ok(ImageFolder {
+        dataset: Dataset {
+            inputs,
+            targets,
+            metadata: DatasetInfo {
+                name,
+                task: Task::Classification,
+                num_classes: Some(classes.len()),
+                label_encoding: Some(match options.label_encoding {
+                    LabelEncoding::Numeric => LabelEncoding::Index,
+                    encoding => encoding,
+                }),
+                seed: 0,
+                noise: 0.0,
+            },
+        },
+        classes,
+        labels,
+        paths,
+    })
+}
+
+/// Decode, convert, resize and flatten one image
+pub fn load_image(path: &Path, options: &ImageFolderOptions) -> Result<Vec<f64>, ImageFolderError> {
+    let img = image::open(path).map_err(|e| ImageFolderError::Decode {
+        path: path.to_path_buf(),
+        message: e.to_string(),
+    })?;
+    let img = img.resize_exact(options.width, options.height, FilterType::Triangle);
+
+    let raw: Vec<u8> = match options.color {
+        ColorMode::Grayscale => img.to_luma8().into_raw(),
+        ColorMode::Rgb => img.to_rgb8().into_raw(),
+    };
+    Ok(raw
+        .iter()
+        .map(|&v| match options.normalization {
+            Normalization::None => v as f64,
+            Normalization::ZeroOne => v as f64 / 255.0,
+            Normalization::MinusOneOne => v as f64 / 127.5 - 1.0,
+        })
+        .collect())
+}
+
+impl ImageFolder {
+    /// Stratified train / test split: `test_ratio` of each class (rounded) goes to the test set
+    pub fn train_test_split(&self, test_ratio: f64, seed: u64) -> (Dataset, Dataset) {
+        assert!((0.0..=1.0).contains(&test_ratio), "test_ratio must be in [0, 1]");
+        let indices = train_test_indices(
+            self.labels.len(),
+            test_ratio,
+            SplitStrategy::Stratified(&self.labels),
+            seed,
+        );
+        (self.subset(&indices.train, seed), self.subset(&indices.test, seed))
+    }
+
+    fn subset(&self, indices: &[usize], seed: u64) -> Dataset {
+        Dataset {
+            inputs: indices.iter().map(|&k| self.dataset.inputs[k].clone()).collect(),
+            targets: indices.iter().map(|&k| self.dataset.targets[k].clone()).collect(),
+            metadata: DatasetInfo {
+                seed,
+                ..self.dataset.metadata.clone()
+            },
+        }
+    }
+}
+
+fn is_hidden(path: &Path) -> bool {
+    path.file_name()
+        .is_some_and(|n| n.to_string_lossy().starts_with('.'))
+}
+
+fn is_image(path: &Path) -> bool {
+    path.extension().is_some_and(|e| {
+        let e = e.to_string_lossy().to_lowercase();
+        e == "png" || e == "jpg" || e == "jpeg"
+    })
+}
@@ -0,0 +1,173 @@
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+/// k-means clustering with k-means++ seeding.
+///
+/// Works on any `Vec<f64>` samples (2-D points, flattened Connect Four boards, ...).
+/// The fitted centroids can also be used as centres for RBF features
+/// (`features::RbfFeatures::from_kmeans`).
+pub struct KMeans {
+    k: usize,
+    /// Seed of the random generator (None = different run every time)
+    seed: Option<u64>,
+    centroids: Vec<Vec<f64>>,
+    inertia: f64,
+}
+
+impl KMeans {
+    pub fn new(k: usize, seed: Option<u64>) -> Self {
+        assert!(k >= 1, "Need at least one cluster");
+        KMeans {
+            k,
+            seed,
+            centroids: Vec::new(),
+            inertia: f64::INFINITY,
+        }
+    }
+
+    /// Run Lloyd's algorithm `n_init` times from k-means++ seeds and keep the best inertia
+    ///
+    /// - `inputs`: Vec of samples, each sample is a Vec<f64> of the same length
+    /// - `max_iter`: maximum number of Lloyd iterations per run
+    /// - `tol`: stop a run when no centroid moves more than `tol`
+    /// - `n_init`: number of restarts
+    pub fn fit(&mut self, inputs: &[Vec<f64>], max_iter: usize, tol: f64, n_init: usize) {
+        assert!(
+            inputs.len() >= self.k,
+            "Need at least as many samples as clusters"
+        );
+        let mut rng = match self.seed {
+            Some(s) => StdRng::seed_from_u64(s),
+            None => StdRng::from_entropy(),
+        };
+
+        self.inertia = f64::INFINITY;
+        for _ in 0..n_init.max(1) {
+            let mut centroids = kmeans_plus_plus(inputs, self.k, &mut rng);
+            let inertia = lloyd(inputs, &mut centroids, max_iter, tol);
+            if inertia < self.inertia {
+                self.inertia = inertia;
+                self.centroids = centroids;
+            }
+        }
+    }
+
+    /// Index of the closest centroid
+    pub fn predict(&self, input: &[f64]) -> usize {
+        assert!(!self.centroids.is_empty(), "KMeans must be fitted first");
+        closest(&self.centroids, input).0
+    }
+
+    /// Euclidean distance from `input` to each centroid
+    pub fn transform(&self, input: &[f64]) -> Vec<f64> {
+        assert!(!self.centroids.is_empty(), "KMeans must be fitted first");
+        self.centroids
+            .iter()
+            .map(|c| squared_distance(c, input).sqrt())
+            .collect()
+    }
+
+    pub fn centroids(&self) -> &[Vec<f64>] {
+        &self.centroids
+    }
+
+    /// Sum of squared distances of the training samples to their closest centroid
+    pub fn inertia(&self) -> f64 {
+        self.inertia
+    }
+}
+
+pub(crate) fn squared_distance(a: &[f64], b: &[f64]) -> f64 {
+    assert_eq!(a.len(), b.len(), "Samples must have the same size");
+    let mut sum = 0.0;
+    for (&x, &y) in a.iter().zip(b.iter()) {
+        sum += (x - y) * (x - y);
+    }
+    sum
+}
+
+/// (index, squared distance) of the closest centroid
+fn closest(centroids: &[Vec<f64>], input: &[f64]) -> (usize, f64) {
+    let mut best = (0, f64::INFINITY);
+    for (c, centroid) in centroids.iter().enumerate() {
+        let d = squared_distance(centroid, input);
+        if d < best.1 {
+            best = (c, d);
+        }
+    }
+    best
+}
+
+/// k-means++: first centre uniformly, then each new centre with probability
+/// proportional to the squared distance to the closest centre already chosen
+fn kmeans_plus_plus(inputs: &[Vec<f64>], k: usize, rng: &mut StdRng) -> Vec<Vec<f64>> {
+    let mut centroids = Vec::with_capacity(k);
+    centroids.push(inputs[rng.gen_range(0..inputs.len())].clone());
+
+    let mut dist: Vec<f64> = inputs
+        .iter()
+        .map(|x| squared_distance(&centroids[0], x))
+        .collect();
+
+    while centroids.len() < k {
+        let total: f64 = dist.iter().sum();
+        let next = if total > 0.0 {
+            let mut r = rng.gen::<f64>() * total;
+            let mut idx = inputs.len() - 1;
+            for (i, &d) in dist.iter().enumerate() {
+                if r < d {
+                    idx = i;
+                    break;
+                }
+                r -= d;
+            }
+            idx
+        } else {
+            // All remaining samples coincide with a centre
+            rng.gen_range(0..inputs.len())
+        };
+
+        centroids.push(inputs[next].clone());
+        let last = centroids.len() - 1;
+        for (d, x) in dist.iter_mut().zip(inputs.iter()) {
+            *d = d.min(squared_distance(&centroids[last], x));
+        }
+    }
+
+    centroids
+}
+
+/// Lloyd iterations, returns the final inertia
+fn lloyd(inputs: &[Vec<f64>], centroids: &mut [Vec<f64>], max_iter: usize, tol: f64) -> f64 {
+    let dim = inputs[0].len();
+    let k = centroids.len();
+
+    for _ in 0..max_iter {
+        let mut sums = vec![vec![0.0; dim]; k];
+        let mut counts = vec![0usize; k];
+        for x in inputs {
+            let (c, _) = closest(centroids, x);
+            counts[c] += 1;
+            for (s, &v) in sums[c].iter_mut().zip(x.iter()) {
+                *s += v;
+            }
+        }
+
+        let mut max_shift: f64 = 0.0;
+        for c in 0..k {
+            // Empty cluster: keep the previous centroid
+            if counts[c] == 0 {
+                continue;
+            }
+            let new_c: Vec<f64> = sums[c].iter().map(|s| s / counts[c] as f64).collect();
+            max_shift = max_shift.max(squared_distance(&new_c, &centroids[c]).sqrt());
+            centroids[c] = new_c;
+        }
+
+        if max_shift <= tol {
+            break;
+        }
+    }
+
+    inputs.iter().map(|x| closest(centroids, x).1).sum()
+}
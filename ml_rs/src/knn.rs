@@ -0,0 +1,362 @@
+use crate::encoding::encode_classes;
+
+/// Distance between two samples
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Distance {
+    Euclidean,
+    Manhattan,
+    /// Minkowski distance of order p (p >= 1)
+    Minkowski(f64),
+    /// 1 - cos(a, b)
+    Cosine,
+}
+
+impl Distance {
+    pub fn compute(&self, a: &[f64], b: &[f64]) -> f64 {
+        assert_eq!(a.len(), b.len(), "Samples must have the same size");
+        match *self {
+            Distance::Euclidean => {
+                let mut sum = 0.0;
+                for (&x, &y) in a.iter().zip(b.iter()) {
+                    sum += (x - y) * (x - y);
+                }
+                sum.sqrt()
+            }
+            Distance::Manhattan => {
+                let mut sum = 0.0;
+                for (&x, &y) in a.iter().zip(b.iter()) {
+                    sum += (x - y).abs();
+                }
+                sum
+            }
+            Distance::Minkowski(p) => {
+                let mut sum = 0.0;
+                for (&x, &y) in a.iter().zip(b.iter()) {
+                    sum += (x - y).abs().powf(p);
+                }
+                sum.powf(1.0 / p)
+            }
+            Distance::Cosine => {
+                let mut ab = 0.0;
+                let mut aa = 0.0;
+                let mut bb = 0.0;
+                for (&x, &y) in a.iter().zip(b.iter()) {
+                    ab += x * y;
+                    aa += x * x;
+                    bb += y * y;
+                }
+                if aa == 0.0 || bb == 0.0 {
+                    1.0
+                } else {
+                    1.0 - ab / (aa.sqrt() * bb.sqrt())
+                }
+            }
+        }
+    }
+}
+
+/// How the k neighbours contribute to a prediction
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Weighting {
+    /// Every neighbour counts the same
+    Uniform,
+    /// Neighbours weighted by 1 / distance (exact matches take over)
+    Distance,
+}
+
+/// How neighbours are searched
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Algorithm {
+    /// Compare with every training sample, works for every distance and any dimension
+    BruteForce,
+    /// KD-tree with at most `leaf_size` samples per leaf.
+    /// Only for Euclidean / Manhattan / Minkowski; cosine falls back to brute force.
+    KdTree { leaf_size: usize },
+}
+
+enum KdNode {
+    Leaf {
+        indices: Vec<usize>,
+    },
+    Split {
+        axis: usize,
+        value: f64,
+        left: usize,
+        right: usize,
+    },
+}
+
+/// Training samples plus an optional KD-tree over them
+struct NeighbourIndex {
+    distance: Distance,
+    points: Vec<Vec<f64>>,
+    /// nodes[0] is the root, empty for brute force
+    nodes: Vec<KdNode>,
+}
+
+impl NeighbourIndex {
+    fn new(points: &[Vec<f64>], distance: Distance, algorithm: Algorithm) -> Self {
+        assert!(!points.is_empty(), "Need at least one sample");
+        let mut index = NeighbourIndex {
+            distance,
+            points: points.to_vec(),
+            nodes: Vec::new(),
+        };
+
+        if let Algorithm::KdTree { leaf_size } = algorithm {
+            if distance != Distance::Cosine {
+                let mut indices: Vec<usize> = (0..points.len()).collect();
+                index.build(&mut indices, leaf_size.max(1));
+            }
+        }
+        index
+    }
+
+    /// Build the subtree over `indices`, returns its node id
+    fn build(&mut self, indices: &mut [usize], leaf_size: usize) -> usize {
+        let id = self.nodes.len();
+        if indices.len() <= leaf_size {
+            self.nodes.push(KdNode::Leaf {
+                indices: indices.to_vec(),
+            });
+            return id;
+        }
+
+        // Split on the axis with the largest spread, at the median
+        let dim = self.points[0].len();
+        let mut axis = 0;
+        let mut best_spread = -1.0;
+        for a in 0..dim {
+            let mut lo = f64::INFINITY;
+            let mut hi = f64::NEG_INFINITY;
+            for &i in indices.iter() {
+                lo = lo.min(self.points[i][a]);
+                hi = hi.max(self.points[i][a]);
+            }
+            if hi - lo > best_spread {
+                best_spread = hi - lo;
+                axis = a;
+            }
+        }
+
+        if best_spread <= 0.0 {
+            // All samples identical
+            self.nodes.push(KdNode::Leaf {
+                indices: indices.to_vec(),
+            });
+            return id;
+        }
+
+        indices.sort_by(|&a, &b| self.points[a][axis].total_cmp(&self.points[b][axis]));
+        let mid = indices.len() / 2;
+        let value = self.points[indices[mid]][axis];
+
+        // Placeholder, filled once the children exist
+        self.nodes.push(KdNode::Leaf {
+            indices: Vec::new(),
+        });
+        let (left_idx, right_idx) = indices.split_at_mut(mid);
+        let left = self.build(left_idx, leaf_size);
+        let right = self.build(right_idx, leaf_size);
+        self.nodes[id] = KdNode::Split {
+            axis,
+            value,
+            left,
+            right,
+        };
+        id
+    }
+
+    /// The `k` closest training samples as (distance, index), closest first
+    fn query(&self, input: &[f64], k: usize) -> Vec<(f64, usize)> {
+        let mut best = Vec::with_capacity(k + 1);
+        if self.nodes.is_empty() {
+            for i in 0..self.points.len() {
+                self.push_candidate(&mut best, input, i, k);
+            }
+        } else {
+            self.search(0, input, k, &mut best);
+        }
+        best
+    }
+
+    fn search(&self, node: usize, input: &[f64], k: usize, best: &mut Vec<(f64, usize)>) {
+        match &self.nodes[node] {
+            KdNode::Leaf { indices } => {
+                for &i in indices {
+                    self.push_candidate(best, input, i, k);
+                }
+            }
+            KdNode::Split {
+                axis,
+                value,
+                left,
+                right,
+            } => {
+                let diff = input[*axis] - value;
+                let (near, far) = if diff < 0.0 { (*left, *right) } else { (*right, *left) };
+                self.search(near, input, k, best);
+                // |diff| is a lower bound of the distance to any sample on the other side
+                if best.len() < k || diff.abs() < best[best.len() - 1].0 {
+                    self.search(far, input, k, best);
+                }
+            }
+        }
+    }
+
+    fn push_candidate(&self, best: &mut Vec<(f64, usize)>, input: &[f64], i: usize, k: usize) {
+        let d = self.distance.compute(&self.points[i], input);
+        if best.len() == k && d >= best[k - 1].0 {
+            return;
+        }
+        let pos = best.partition_point(|&(bd, _)| bd <= d);
+        best.insert(pos, (d, i));
+        best.truncate(k);
+    }
+}
+
+/// Weights of the neighbours returned by `NeighbourIndex::query`
+fn neighbour_weights(neighbours: &[(f64, usize)], weighting: Weighting) -> Vec<f64> {
+    match weighting {
+        Weighting::Uniform => vec![1.0; neighbours.len()],
+        Weighting::Distance => {
+            if neighbours.iter().any(|&(d, _)| d == 0.0) {
+                neighbours
+                    .iter()
+                    .map(|&(d, _)| if d == 0.0 { 1.0 } else { 0.0 })
+                    .collect()
+            } else {
+                neighbours.iter().map(|&(d, _)| 1.0 / d).collect()
+            }
+        }
+    }
+}
+
+/// k-nearest-neighbours classifier.
+///
+/// Targets can be a single value (±1 like `LinearPerceptron`, 0 / 1 or a class index)
+/// or one value per class (like the 3-class tests); `predict` returns the target vector
+/// of the predicted class as given in training.
+pub struct KNNClassifier {
+    k: usize,
+    distance: Distance,
+    weighting: Weighting,
+    algorithm: Algorithm,
+    index: Option<NeighbourIndex>,
+    /// class index of each training sample
+    labels: Vec<usize>,
+    /// target vector of each class, as seen in the training outputs
+    class_outputs: Vec<Vec<f64>>,
+}
+
+impl KNNClassifier {
+    pub fn new(k: usize, distance: Distance, weighting: Weighting, algorithm: Algorithm) -> Self {
+        assert!(k >= 1, "k must be at least 1");
+        KNNClassifier {
+            k,
+            distance,
+            weighting,
+            algorithm,
+            index: None,
+            labels: Vec::new(),
+            class_outputs: Vec::new(),
+        }
+    }
+
+    /// Store the training samples (and build the search index)
+    pub fn fit(&mut self, inputs: &[Vec<f64>], outputs: &[Vec<f64>]) {
+        assert_eq!(inputs.len(), outputs.len());
+
+        let (labels, class_outputs) = encode_classes(outputs);
+        self.labels = labels;
+        self.class_outputs = class_outputs;
+
+        self.index = Some(NeighbourIndex::new(inputs, self.distance, self.algorithm));
+    }
+
+    /// Weighted vote of the k neighbours for each class
+    pub fn predict_scores(&self, input: &[f64]) -> Vec<f64> {
+        let index = self.index.as_ref().expect("KNNClassifier must be fitted first");
+        let neighbours = index.query(input, self.k);
+        let weights = neighbour_weights(&neighbours, self.weighting);
+
+        let mut scores = vec![0.0; self.class_outputs.len()];
+        for (&(_, i), w) in neighbours.iter().zip(weights.iter()) {
+            scores[self.labels[i]] += w;
+        }
+        scores
+    }
+
+    /// Predicted target vector (same layout as the training outputs)
+    pub fn predict(&self, input: &[f64]) -> Vec<f64> {
+        let scores = self.predict_scores(input);
+        let mut best = 0;
+        for (c, &s) in scores.iter().enumerate() {
+            if s > scores[best] {
+                best = c;
+            }
+        }
+        self.class_outputs[best].clone()
+    }
+
+    /// The k nearest training samples as (distance, index), closest first
+    pub fn kneighbors(&self, input: &[f64]) -> Vec<(f64, usize)> {
+        let index = self.index.as_ref().expect("KNNClassifier must be fitted first");
+        index.query(input, self.k)
+    }
+}
+
+/// k-nearest-neighbours regressor: (weighted) mean of the neighbours' targets
+pub struct KNNRegressor {
+    k: usize,
+    distance: Distance,
+    weighting: Weighting,
+    algorithm: Algorithm,
+    index: Option<NeighbourIndex>,
+    targets: Vec<Vec<f64>>,
+}
+
+impl KNNRegressor {
+    pub fn new(k: usize, distance: Distance, weighting: Weighting, algorithm: Algorithm) -> Self {
+        assert!(k >= 1, "k must be at least 1");
+        KNNRegressor {
+            k,
+            distance,
+            weighting,
+            algorithm,
+            index: None,
+            targets: Vec::new(),
+        }
+    }
+
+    pub fn fit(&mut self, inputs: &[Vec<f64>], outputs: &[Vec<f64>]) {
+        assert_eq!(inputs.len(), outputs.len());
+        self.targets = outputs.to_vec();
+        self.index = Some(NeighbourIndex::new(inputs, self.distance, self.algorithm));
+    }
+
+    pub fn predict(&self, input: &[f64]) -> Vec<f64> {
+        let index = self.index.as_ref().expect("KNNRegressor must be fitted first");
+        let neighbours = index.query(input, self.k);
+        let weights = neighbour_weights(&neighbours, self.weighting);
+
+        let mut out = vec![0.0; self.targets[0].len()];
+        let mut total = 0.0;
+        for (&(_, i), &w) in neighbours.iter().zip(weights.iter()) {
+            for (o, &t) in out.iter_mut().zip(self.targets[i].iter()) {
+                *o += w * t;
+            }
+            total += w;
+        }
+        for o in out.iter_mut() {
+            *o /= total;
+        }
+        out
+    }
+
+    /// The k nearest training samples as (distance, index), closest first
+    pub fn kneighbors(&self, input: &[f64]) -> Vec<(f64, usize)> {
+        let index = self.index.as_ref().expect("KNNRegressor must be fitted first");
+        index.query(input, self.k)
+    }
+}
@@ -1,2 +1,25 @@
 pub mod naive_multi_layer_perceptron;
 pub mod linear_perceptron;
+pub mod svm;
+pub mod kmeans;
+pub mod knn;
+pub mod decision_tree;
+pub mod random_forest;
+pub mod pca;
+pub mod datasets;
+pub mod csv_loader;
+pub mod idx;
+pub mod image_folder;
+pub mod augmentation;
+pub mod scaling;
+pub mod features;
+pub mod encoding;
+pub mod split;
+pub mod cross_validation;
+pub mod metrics;
+pub mod search;
+pub mod plot;
+pub mod history;
+pub mod gradient_check;
+pub mod saved_model;
+pub mod experiment;
@@ -1,10 +1,13 @@
-use rand::Rng;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
 
 /// Simple linear model / perceptron:
 /// y_hat = w · x + b
 ///
 /// - For classification: interpret `y_hat.signum()` as the predicted class
 /// - For regression: use `y_hat` directly
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct LinearPerceptron {
     weights: Vec<f64>,
     bias: f64,
@@ -15,7 +18,15 @@ impl LinearPerceptron {
     /// Create a new linear perceptron with `input_dim` inputs
     /// Weights are initialized randomly in [-0.5, 0.5], bias = 0
     pub fn new(input_dim: usize) -> Self {
-        let mut rng = rand::thread_rng();
+        Self::with_rng(input_dim, &mut rand::thread_rng())
+    }
+
+    /// Same as `new`, with reproducible initial weights
+    pub fn with_seed(input_dim: usize, seed: u64) -> Self {
+        Self::with_rng(input_dim, &mut StdRng::seed_from_u64(seed))
+    }
+
+    fn with_rng<R: Rng>(input_dim: usize, rng: &mut R) -> Self {
         let mut weights = Vec::with_capacity(input_dim);
         for _ in 0..input_dim {
             weights.push(rng.gen_range(-0.5..0.5));
@@ -38,6 +49,14 @@ impl LinearPerceptron {
         sum
     }
 
+    pub fn weights(&self) -> &[f64] {
+        &self.weights
+    }
+
+    pub fn bias(&self) -> f64 {
+        self.bias
+    }
+
     /// For classification: return sign of the raw output (in {-1.0, 0.0, 1.0})
     pub fn predict_class(&self, input: &[f64]) -> f64 {
         self.predict_raw(input).signum()
@@ -48,6 +67,17 @@ impl LinearPerceptron {
         self.predict_raw(input)
     }
 
+    /// One SGD step on the squared error of a single sample (both training methods
+    /// repeat it on random samples)
+    pub fn train_step(&mut self, input: &[f64], target: f64, alpha: f64) {
+        // gradient of 0.5 * (y_hat - y)^2 wrt w_i: (y_hat - y) * x_i
+        let error = self.predict_raw(input) - target;
+        for (w, &x_i) in self.weights.iter_mut().zip(input.iter()) {
+            *w -= alpha * error * x_i;
+        }
+        self.bias -= alpha * error;
+    }
+
     /// Train using simple SGD on squared error for *classification* targets
     ///
     /// - `inputs`: Vec of samples, each sample is a Vec<f64> of length `input_dim`
@@ -63,26 +93,7 @@ impl LinearPerceptron {
         num_iter: usize,
         alpha: f64,
     ) {
-        assert_eq!(inputs.len(), outputs.len());
-        let mut rng = rand::thread_rng();
-
-        for _ in 0..num_iter {
-            let k = rng.gen_range(0..inputs.len());
-            let x = &inputs[k];
-            let y = outputs[k][0];
-
-            // prediction
-            let y_hat = self.predict_raw(x);
-
-            // gradient of 0.5 * (y_hat - y)^2 wrt w_i: (y_hat - y) * x_i
-            let error = y_hat - y;
-
-            // update weights and bias
-            for i in 0..self.input_dim {
-                self.weights[i] -= alpha * error * x[i];
-            }
-            self.bias -= alpha * error;
-        }
+        self.train_with_rng(&mut rand::thread_rng(), inputs, outputs, num_iter, alpha);
     }
 
     /// Train using simple SGD on squared error for *regression* targets
@@ -93,21 +104,23 @@ impl LinearPerceptron {
         num_iter: usize,
         alpha: f64,
     ) {
-        assert_eq!(inputs.len(), outputs.len());
-        let mut rng = rand::thread_rng();
+        self.train_with_rng(&mut rand::thread_rng(), inputs, outputs, num_iter, alpha);
+    }
 
+    /// SGD steps on samples drawn from `rng` (both training methods use it with the
+    /// thread generator): a seeded generator makes the training reproducible
+    pub fn train_with_rng<R: Rng>(
+        &mut self,
+        rng: &mut R,
+        inputs: &[Vec<f64>],
+        outputs: &[Vec<f64>],
+        num_iter: usize,
+        alpha: f64,
+    ) {
+        assert_eq!(inputs.len(), outputs.len());
         for _ in 0..num_iter {
             let k = rng.gen_range(0..inputs.len());
-            let x = &inputs[k];
-            let y = outputs[k][0];
-
-            let y_hat = self.predict_raw(x);
-            let error = y_hat - y;
-
-            for i in 0..self.input_dim {
-                self.weights[i] -= alpha * error * x[i];
-            }
-            self.bias -= alpha * error;
+            self.train_step(&inputs[k], outputs[k][0], alpha);
         }
     }
 }
@@ -1,931 +1,354 @@
-mod linear_perceptron;
-mod naive_multi_layer_perceptron;
-
-use linear_perceptron::LinearPerceptron;
-use naive_multi_layer_perceptron::MyMLP;
-use rand::{Rng, SeedableRng};
-use rand::rngs::StdRng;
+mod cli;
+
+use ml_lib::datasets::{self, Dataset};
+use ml_lib::experiment::{parse_configs, ModelConfig};
+use ml_lib::decision_tree::{Criterion, DecisionTree, TreeParams};
+use ml_lib::knn::{Algorithm, Distance, KNNClassifier, KNNRegressor, Weighting};
+use ml_lib::svm::{BinarySVM, Kernel, MultiClassSVM, MultiClassStrategy};
+use ml_lib::random_forest::RandomForest;
 use std::time::{SystemTime, UNIX_EPOCH};
 use std::env;
+use std::process;
 
-fn mod_0_5_python_style(x: f64) -> f64 {
-    let m = x % 0.5;
-    if m < 0.0 { m + 0.5 } else { m }
-}
-
-fn generate_test_2_dataset() -> (Vec<Vec<f64>>, Vec<Vec<f64>>) {
-    // Time-based seed → different dataset each run
-    let seed = SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .unwrap()
-        .as_nanos() as u64;
-
-    let mut rng = StdRng::seed_from_u64(seed);
-
-    let mut inputs = Vec::new();
-    let mut outputs = Vec::new();
-
-    // First cluster: around (1,1), label +1
-    for _ in 0..50 {
-        let x = rng.gen::<f64>() * 0.9 + 1.0;
-        let y = rng.gen::<f64>() * 0.9 + 1.0;
-        inputs.push(vec![x, y]);
-        outputs.push(vec![1.0]);
-    }
-
-    // Second cluster: around (2,2), label -1
-    for _ in 0..50 {
-        let x = rng.gen::<f64>() * 0.9 + 2.0;
-        let y = rng.gen::<f64>() * 0.9 + 2.0;
-        inputs.push(vec![x, y]);
-        outputs.push(vec![-1.0]);
-    }
-
-    (inputs, outputs)
-}
-
-fn generate_test_4_dataset() -> (Vec<Vec<f64>>, Vec<Vec<f64>>) {
-    // Time-based seed → new dataset every run
-    let seed = SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .unwrap()
-        .as_nanos() as u64;
-
-    let mut rng = StdRng::seed_from_u64(seed);
-
-    let mut inputs = Vec::new();
-    let mut outputs = Vec::new();
-
-    for _ in 0..500 {
-        // X in [-1, +1]
-        let x = rng.gen::<f64>() * 2.0 - 1.0;
-        let y = rng.gen::<f64>() * 2.0 - 1.0;
-
-        let inside_vertical_stripe = x.abs() <= 0.3;
-        let inside_horizontal_stripe = y.abs() <= 0.3;
-
-        let label = if inside_vertical_stripe || inside_horizontal_stripe {
-            1.0
-        } else {
-            -1.0
-        };
-
-        inputs.push(vec![x, y]);
-        outputs.push(vec![label]);
-    }
-
-    (inputs, outputs)
-}
-
-fn generate_test_5_dataset() -> (Vec<Vec<f64>>, Vec<Vec<f64>>) {
-    // Time-based seed → new dataset every run
-    let seed = SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .unwrap()
-        .as_nanos() as u64;
-
-    let mut rng = StdRng::seed_from_u64(seed);
-
-    let mut inputs = Vec::new();
-    let mut outputs = Vec::new();
-
-    for _ in 0..500 {
-        let x = rng.gen::<f64>() * 2.0 - 1.0;
-        let y = rng.gen::<f64>() * 2.0 - 1.0;
-
-        let v1 = -x - y - 0.5;
-        let v2 =  x - y - 0.5;
-
-        let label = if v1 > 0.0 && y < 0.0 && v2 < 0.0 {
-            Some(vec![ 1.0, -1.0, -1.0])
-        } else if v1 < 0.0 && y > 0.0 && v2 < 0.0 {
-            Some(vec![-1.0,  1.0, -1.0])
-        } else if v1 < 0.0 && y < 0.0 && v2 > 0.0 {
-            Some(vec![-1.0, -1.0,  1.0])
-        } else {
-            None
-        };
-
-        if let Some(lbl) = label {
-            inputs.push(vec![x, y]);
-            outputs.push(lbl);
-        }
-    }
-
-    (inputs, outputs)
-}
+type Generator = fn(u64, usize, f64) -> Dataset;
 
-fn generate_test_6_dataset() -> (Vec<Vec<f64>>, Vec<Vec<f64>>) {
-    // Time-based seed → new dataset each run
-    let seed = SystemTime::now()
+/// Time-based seed → different dataset each run
+fn time_seed() -> u64 {
+    SystemTime::now()
         .duration_since(UNIX_EPOCH)
         .unwrap()
-        .as_nanos() as u64;
-
-    let mut rng = StdRng::seed_from_u64(seed);
-
-    let mut inputs = Vec::with_capacity(1000);
-    let mut outputs = Vec::with_capacity(1000);
-
-    for _ in 0..1000 {
-        // X in [-1, 1]^2
-        let x = rng.gen::<f64>() * 2.0 - 1.0;
-        let y = rng.gen::<f64>() * 2.0 - 1.0;
-
-        // Python-style remainder in [0, 0.5)
-        let xm = mod_0_5_python_style(x).abs();
-        let ym = mod_0_5_python_style(y).abs();
-
-        let label = if xm <= 0.25 && ym > 0.25 {
-            vec![ 1.0, -1.0, -1.0]
-        } else if xm > 0.25 && ym <= 0.25 {
-            vec![-1.0,  1.0, -1.0]
-        } else {
-            vec![-1.0, -1.0,  1.0]
-        };
-
-        inputs.push(vec![x, y]);
-        outputs.push(label);
-    }
-
-    (inputs, outputs)
+        .as_nanos() as u64
 }
 
 fn main() {
     let args: Vec<String> = env::args().skip(1).collect();
 
-    if (args.contains(&"-c".to_string()) || args.contains(&"--classification".to_string())) && args.contains(&"--linear".to_string()) {
-        println!("Running linear classification tests...");
-        run_linear_classification_tests();
-        return;
-    }
-
-    if (args.contains(&"-c".to_string()) || args.contains(&"--classification".to_string())) && args.contains(&"--mlp".to_string()) {
-        println!("Running MLP classification tests...");
-        run_mlp_classification_tests();
-        return;
-    }
-
-    if (args.contains(&"-r".to_string()) || args.contains(&"--regression".to_string())) && args.contains(&"--linear".to_string()) {
-        println!("Running linear regression tests...");
-        run_linear_regression_tests();
-        return;
-    }
-
-    if (args.contains(&"-r".to_string()) || args.contains(&"--regression".to_string())) && args.contains(&"--mlp".to_string()) {
-        println!("Running MLP regression tests...");
-        run_mlp_regression_tests();
-        return;
+    let result = match args.first().map(|a| a.as_str()) {
+        None | Some("--help") | Some("-h") | Some("help") => {
+            println!("{}", cli::USAGE);
+            Ok(())
+        }
+        Some("course") => run_course(&args[1..]),
+        // Course flags without the `course` command, as before the subcommands
+        Some(flag) if flag.starts_with('-') => run_course(&args),
+        Some(command) => cli::run(command, &args[1..]),
+    };
+    if let Err(e) = result {
+        eprintln!("{}", e);
+        process::exit(e.exit_code());
     }
-
-    // Default behaviour
-    println!("No mode specified. Use --classification or --regression.");
 }
 
-fn run_linear_classification_tests() {
-    use linear_perceptron::LinearPerceptron;
-
-    // #### LINEAR CLASSIFICATION ####
-
-    // ## Test 1: Linear Simple (OK)
-    println!("\n=== Linear Test 1: Linear Simple ===\n");
-    let inputs = vec![
-        vec![1.0, 1.0],
-        vec![2.0, 3.0],
-        vec![3.0, 3.0],
-    ];
-
-    let outputs = vec![
-        vec![1.0],
-        vec![-1.0],
-        vec![-1.0],
-    ];
-
-    let mut lin = LinearPerceptron::new(2);
-
-    let num_iter = 50_000;
-    let alpha = 0.1;
-
-    println!("\nTraining...");
-    lin.train_classification(&inputs, &outputs, num_iter, alpha);
-
-    println!("\nResults:");
-    for (x, y) in inputs.iter().zip(outputs.iter()) {
-        let raw = lin.predict_raw(x);
-        let p = lin.predict_class(x);
-        println!("x={:?}, y={}, raw={:.2}, pred_sign={:.2}", x, y[0], raw, p);
+/// Course test cases, e.g. `--classification --mlp`
+fn run_course(args: &[String]) -> Result<(), cli::CliError> {
+    if args.iter().any(|a| a == "--help" || a == "-h") {
+        println!("{}", COURSE_USAGE);
+        return Ok(());
     }
 
-    // ## Test 2: Linear Multiple (OK)
-    println!("\n=== Linear Test 2: Linear Multiple ===\n");
-    let (inputs, outputs) = generate_test_2_dataset();
-
-    let mut lin = LinearPerceptron::new(2);
-
-    let num_iter = 50_000;
-    let alpha = 0.1;
-
-    println!("Training...");
-    lin.train_classification(&inputs, &outputs, num_iter, alpha);
-
-    println!("\nResults:");
-    for i in 0..10 {
-        let idx = i * 10;
-        let raw = lin.predict_raw(&inputs[idx]);
-        let p = lin.predict_class(&inputs[idx]);
-        println!(
-            "x={:?}, y={}, raw={:.2}, pred_sign={:.2}",
-            inputs[idx], outputs[idx][0], raw, p
-        );
+    // The linear model and the MLP run the course configs of experiments/
+    let classification = args.iter().any(|a| a == "-c" || a == "--classification");
+    let regression = args.iter().any(|a| a == "-r" || a == "--regression");
+    let model = ["linear", "mlp"].into_iter().find(|m| args.contains(&format!("--{}", m)));
+    if let Some(model) = model.filter(|_| classification || regression) {
+        let task = if classification { "classification" } else { "regression" };
+        return run_course_configs(task, model);
     }
 
-    // println!("\nPrediction comparison:");
-    // let mut correct = 0;
-    // let mut incorrect = 0;
-    // for i in 0..100 {
-    //     let raw = lin.predict_raw(&inputs[i]);
-    //     let p = lin.predict_class(&inputs[i]);
-    //     if (p >= 0.0 && outputs[i][0] == 1.0) || (p < 0.0 && outputs[i][0] == -1.0) {
-    //         correct += 1;
-    //     } else {
-    //         incorrect += 1;
-    //     }
-    // }
-    // println!("Correct: {}, Incorrect: {}", correct, incorrect);
-
-    // ## Test 3: XOR (KO)
-    println!("\n=== Linear Test 3: XOR ===\n");
-    let inputs = vec![
-        vec![0.0, 0.0],
-        vec![0.0, 1.0],
-        vec![1.0, 0.0],
-        vec![1.0, 1.0],
-    ];
-
-    let outputs = vec![
-        vec![0.0],
-        vec![1.0],
-        vec![1.0],
-        vec![0.0],
-    ];
-
-    let mut lin = LinearPerceptron::new(2);
-
-    let num_iter = 500_000;
-    let alpha = 0.1;
-
-    println!("\nTraining...");
-    lin.train_classification(&inputs, &outputs, num_iter, alpha);
-
-    println!("\nResults:");
-    for (x, y) in inputs.iter().zip(outputs.iter()) {
-        let raw = lin.predict_raw(x);
-        let p = lin.predict_class(x);
-        println!("x={:?}, y={}, raw={:.2}", x, y[0], raw);
-    }
-
-    // ## Test 4: Cross (KO)
-    println!("\n=== Linear Test 4: Cross ===\n");
-    let (inputs, outputs) = generate_test_4_dataset();
-
-    let mut lin = LinearPerceptron::new(2);
-
-    let num_iter = 500_000;
-    let alpha = 0.05;
-
-    println!("Training...");
-    lin.train_classification(&inputs, &outputs, num_iter, alpha);
-
-    println!("\nResults:");
-    for i in 0..50 {
-        let idx = i * 10;
-        let raw = lin.predict_raw(&inputs[idx]);
-        let p = lin.predict_class(&inputs[idx]);
-        println!(
-            "x={:?}, y={}, raw={:.2}, pred_sign={:.2}",
-            inputs[idx], outputs[idx][0], raw, p
-        );
+    if (args.contains(&"-c".to_string()) || args.contains(&"--classification".to_string())) && args.contains(&"--svm".to_string()) {
+        println!("Running SVM classification tests...");
+        run_svm_classification_tests();
+        return Ok(());
     }
 
-    // println!("\nPrediction comparison:");
-    // let mut correct = 0;
-    // let mut incorrect = 0;
-    // for i in 0..500 {
-    //     let raw = lin.predict_raw(&inputs[i]);
-    //     let p = lin.predict_class(&inputs[i]);
-    //     if (p >= 0.0 && outputs[i][0] == 1.0) || (p < 0.0 && outputs[i][0] == -1.0) {
-    //         correct += 1;
-    //     } else {
-    //         incorrect += 1;
-    //     }
-    // }
-    // println!("Correct: {}, Incorrect: {}", correct, incorrect);
-
-    // ## Test 5: Three Classes (OK with one-vs-all linear perceptrons)
-    println!("\n=== Linear Test 5: Three Classes ===\n");
-    let (inputs, outputs) = generate_test_5_dataset();
-
-    // One-vs-all: 3 separate linear perceptrons, each sees +/-1 for its own class
-    let mut lin1 = LinearPerceptron::new(2);
-    let mut lin2 = LinearPerceptron::new(2);
-    let mut lin3 = LinearPerceptron::new(2);
-
-    // Build outputs for each classifier
-    let mut outputs1 = Vec::with_capacity(outputs.len());
-    let mut outputs2 = Vec::with_capacity(outputs.len());
-    let mut outputs3 = Vec::with_capacity(outputs.len());
-    for y in &outputs {
-        outputs1.push(vec![y[0]]);
-        outputs2.push(vec![y[1]]);
-        outputs3.push(vec![y[2]]);
+    if (args.contains(&"-c".to_string()) || args.contains(&"--classification".to_string())) && args.contains(&"--knn".to_string()) {
+        println!("Running KNN classification tests...");
+        run_knn_classification_tests();
+        return Ok(());
     }
 
-    let num_iter = 500_000;
-    let alpha = 0.05;
-
-    println!("Training...");
-    lin1.train_classification(&inputs, &outputs1, num_iter, alpha);
-    lin2.train_classification(&inputs, &outputs2, num_iter, alpha);
-    lin3.train_classification(&inputs, &outputs3, num_iter, alpha);
-
-    println!("\nResults:");
-    for i in 0..30 {
-        let idx = i * 10;
-        let p = vec![
-            lin1.predict_class(&inputs[idx]),
-            lin2.predict_class(&inputs[idx]),
-            lin3.predict_class(&inputs[idx]),
-        ];
-        println!(
-            "x={:?}, y={:?}, pred={:.2?}",
-            inputs[idx], outputs[idx], p
-        );
+    if (args.contains(&"-r".to_string()) || args.contains(&"--regression".to_string())) && args.contains(&"--knn".to_string()) {
+        println!("Running KNN regression tests...");
+        run_knn_regression_tests();
+        return Ok(());
     }
 
-    // println!("\nPrediction comparison:");
-    // let mut correct = 0;
-    // let mut incorrect = 0;
-    // for i in 0..inputs.len() {
-    //     let p = vec![
-    //         lin1.predict_class(&inputs[i]),
-    //         lin2.predict_class(&inputs[i]),
-    //         lin3.predict_class(&inputs[i]),
-    //     ];
-
-    //     let predicted_class = if p[0] > p[1] && p[0] > p[2] {
-    //         vec![1.0, -1.0, -1.0]
-    //     } else if p[1] > p[0] && p[1] > p[2] {
-    //         vec![-1.0, 1.0, -1.0]
-    //     } else {
-    //         vec![-1.0, -1.0, 1.0]
-    //     };
-
-    //     if predicted_class == outputs[i] {
-    //         correct += 1;
-    //     } else {
-    //         incorrect += 1;
-    //     }
-    // }
-    // println!("Correct: {}, Incorrect: {}", correct, incorrect);
-
-    // ## Test 6: Multi Cross (KO)
-    println!("\n=== Linear Test 6: Multi Cross ===\n");
-    let (inputs, outputs) = generate_test_6_dataset();
-
-    let mut lin1 = LinearPerceptron::new(2);
-    let mut lin2 = LinearPerceptron::new(2);
-    let mut lin3 = LinearPerceptron::new(2);
-
-    let mut outputs1 = Vec::with_capacity(outputs.len());
-    let mut outputs2 = Vec::with_capacity(outputs.len());
-    let mut outputs3 = Vec::with_capacity(outputs.len());
-    for y in &outputs {
-        outputs1.push(vec![y[0]]);
-        outputs2.push(vec![y[1]]);
-        outputs3.push(vec![y[2]]);
+    if (args.contains(&"-c".to_string()) || args.contains(&"--classification".to_string())) && args.contains(&"--tree".to_string()) {
+        println!("Running decision tree / random forest classification tests...");
+        run_tree_classification_tests();
+        return Ok(());
     }
 
-    let num_iter = 10_000_000;
-    let alpha = 0.005;
-
-    println!("Training...");
-    lin1.train_classification(&inputs, &outputs1, num_iter, alpha);
-    lin2.train_classification(&inputs, &outputs2, num_iter, alpha);
-    lin3.train_classification(&inputs, &outputs3, num_iter, alpha);
-
-    println!("\nResults:");
-    for i in 0..30 {
-        let idx = i * 10;
-        let p = vec![
-            lin1.predict_class(&inputs[idx]),
-            lin2.predict_class(&inputs[idx]),
-            lin3.predict_class(&inputs[idx]),
-        ];
-        println!(
-            "x={:?}, y={:?}, pred={:.2?}",
-            inputs[idx], outputs[idx], p
-        );
+    if (args.contains(&"-r".to_string()) || args.contains(&"--regression".to_string())) && args.contains(&"--tree".to_string()) {
+        println!("Running decision tree / random forest regression tests...");
+        run_tree_regression_tests();
+        return Ok(());
     }
 
-    // println!("\nPrediction comparison:");
-    // let mut correct = 0;
-    // let mut incorrect = 0;
-    // for i in 0..inputs.len() {
-    //     let p = vec![
-    //         lin1.predict_class(&inputs[i]),
-    //         lin2.predict_class(&inputs[i]),
-    //         lin3.predict_class(&inputs[i]),
-    //     ];
-
-    //     let predicted_class = if p[0] > p[1] && p[0] > p[2] {
-    //         vec![1.0, -1.0, -1.0]
-    //     } else if p[1] > p[0] && p[1] > p[2] {
-    //         vec![-1.0, 1.0, -1.0]
-    //     } else {
-    //         vec![-1.0, -1.0, 1.0]
-    //     };
-
-    //     if predicted_class == outputs[i] {
-    //         correct += 1;
-    //     } else {
-    //         incorrect += 1;
-    //     }
-    // }
-    // println!("Correct: {}, Incorrect: {}", correct, incorrect);
+    Err(cli::CliError::Usage {
+        message: "no test case selected".to_string(),
+        usage: COURSE_USAGE,
+    })
 }
 
-fn run_linear_regression_tests() {
-    use linear_perceptron::LinearPerceptron;
-
-    // #### LINEAR REGRESSION ####
-
-    // Test 1: Linear Simple 2D (OK)
-    println!("\n=== Test 1: Linear Simple 2D ===\n");
-    let inputs = vec![
-        vec![1.0],
-        vec![2.0],
-    ];
-
-    let outputs = vec![
-        vec![2.0],
-        vec![4.0],
-    ];
-
-    let mut lin = LinearPerceptron::new(1);
-
-    let num_iter = 50_000;
-    let alpha = 0.1;
-
-    println!("\nTraining...");
-    lin.train_regression(&inputs, &outputs, num_iter, alpha);
-
-    println!("\nResults:");
-    for (x, y) in inputs.iter().zip(outputs.iter()) {
-        let p = lin.predict_regression(x);
-        println!("x={:?}, y={}, pred={:.2}", x, y[0], p);
-    }
-
-    // Test 2: Non-Linear Simple 2D (KO)
-    println!("\n=== Test 2: Non-Linear Simple 2D ===\n");
-    let inputs = vec![
-        vec![1.0],
-        vec![2.0],
-        vec![3.0],
-    ];
-
-    let outputs = vec![
-        vec![2.0],
-        vec![3.0],
-        vec![2.5],
-    ];
-
-    let mut lin = LinearPerceptron::new(1);
-
-    let num_iter = 100_000;
-    let alpha = 0.05;
-
-    println!("\nTraining...");
-    lin.train_regression(&inputs, &outputs, num_iter, alpha);
+const COURSE_USAGE: &str = "\
+Usage: ml_lib course <--classification | -c | --regression | -r> <model>
+
+Models: --linear, --mlp, --knn, --tree (classification and regression), --svm (classification)
+
+--linear and --mlp run the cases of experiments/course_classification.toml and
+experiments/course_regression.toml (built into the binary) and report their scores.";
+
+/// Course configs built into the binary
+const COURSE_CLASSIFICATION: &str = include_str!("../experiments/course_classification.toml");
+const COURSE_REGRESSION: &str = include_str!("../experiments/course_regression.toml");
+
+/// Run the `model` ("linear" or "mlp") cases of the bundled course config of `task`
+fn run_course_configs(task: &str, model: &str) -> Result<(), cli::CliError> {
+    let text = if task == "classification" { COURSE_CLASSIFICATION } else { COURSE_REGRESSION };
+    let path = format!("experiments/course_{}.toml", task);
+    let mut configs = parse_configs(text, &path).map_err(|e| cli::CliError::Failed(e.to_string()))?;
+    configs.retain(|c| match c.model {
+        ModelConfig::Linear => model == "linear",
+        ModelConfig::Mlp { .. } => model == "mlp",
+    });
+    println!("Running {} {} tests...", model, task);
+    cli::run_configs(&configs, None)
+}
 
-    println!("\nResults:");
+/// Geometric margin 2 * min_k y_k (w · x_k + b) / ||w|| of a linear separator
+fn linear_margin(weights: &[f64], bias: f64, inputs: &[Vec<f64>], outputs: &[Vec<f64>]) -> f64 {
+    let norm = weights.iter().map(|w| w * w).sum::<f64>().sqrt();
+    let mut min_dist = f64::INFINITY;
     for (x, y) in inputs.iter().zip(outputs.iter()) {
-        let p = lin.predict_regression(x);
-        println!("x={:?}, y={}, pred={:.2}", x, y[0], p);
+        let raw = bias + weights.iter().zip(x.iter()).map(|(w, x)| w * x).sum::<f64>();
+        min_dist = min_dist.min(y[0] * raw / norm);
     }
+    2.0 * min_dist
+}
 
-    // Test 3: Linear Simple 3D (OK)
-    println!("\n=== Test 3: Linear Simple 3D ===\n");
-    let inputs = vec![
-        vec![1.0, 1.0],
-        vec![2.0, 2.0],
-        vec![3.0, 1.0],
-    ];
-
-    let outputs = vec![
-        vec![2.0],
-        vec![3.0],
-        vec![2.5],
-    ];
-
-    let mut lin = LinearPerceptron::new(2);
-
-    let num_iter = 50_000;
-    let alpha = 0.1;
-
-    println!("\nTraining...");
-    lin.train_regression(&inputs, &outputs, num_iter, alpha);
+fn run_svm_classification_tests() {
+    use ml_lib::linear_perceptron::LinearPerceptron;
 
-    println!("\nResults:");
-    for (x, y) in inputs.iter().zip(outputs.iter()) {
-        let p = lin.predict_regression(x);
-        println!("x={:?}, y={}, pred={:.2}", x, y[0], p);
-    }
+    // #### SVM CLASSIFICATION ####
 
-    // Test 4: Linear Tricky 3D (OK)
-    println!("\n=== Test 4: Linear Tricky 3D ===\n");
-    let inputs = vec![
-        vec![1.0, 1.0],
-        vec![2.0, 2.0],
-        vec![3.0, 3.0],
-    ];
+    // ## Test 1: Linear Simple (hard margin)
+    println!("\n=== SVM Test 1: Linear Simple ===\n");
+    let Dataset { inputs, targets: outputs, .. } = datasets::linear_simple(0, 3, 0.0);
 
-    let outputs = vec![
-        vec![1.0],
-        vec![2.0],
-        vec![3.0],
-    ];
+    let mut svm = BinarySVM::hard_margin(Kernel::Linear);
+    svm.train(&inputs, &outputs, 10_000, 1e-3);
 
     let mut lin = LinearPerceptron::new(2);
-
-    let num_iter = 100_000;
-    let alpha = 0.1;
-
-    println!("\nTraining...");
-    lin.train_regression(&inputs, &outputs, num_iter, alpha);
+    lin.train_classification(&inputs, &outputs, 50_000, 0.1);
 
     println!("\nResults:");
     for (x, y) in inputs.iter().zip(outputs.iter()) {
-        let p = lin.predict_regression(x);
-        println!("x={:?}, y={}, pred={:.2}", x, y[0], p);
+        println!("x={:?}, y={}, f={:.2}, pred={:.2}", x, y[0], svm.decision_function(x), svm.predict_class(x));
     }
+    println!("Support vectors: {:?}", svm.support_vectors());
+    println!("Dual coefficients: {:.3?}", svm.dual_coefficients());
+    println!("SVM margin: {:.3}", svm.margin());
+    println!("Perceptron margin: {:.3}", linear_margin(lin.weights(), lin.bias(), &inputs, &outputs));
 
-    // Test 5: Non-Linear Simple 3D (KO)
-    println!("\n=== Test 5: Non-Linear Simple 3D ===\n");
-    let inputs = vec![
-        vec![1.0, 0.0],
-        vec![0.0, 1.0],
-        vec![1.0, 1.0],
-        vec![0.0, 0.0],
-    ];
+    // ## Test 2: Linear Multiple (hard margin)
+    println!("\n=== SVM Test 2: Linear Multiple ===\n");
+    let Dataset { inputs, targets: outputs, .. } = datasets::linear_multiple(time_seed(), 100, 0.0);
 
-    let outputs = vec![
-        vec![2.0],
-        vec![1.0],
-        vec![-2.0],
-        vec![-1.0],
-    ];
+    let mut svm = BinarySVM::hard_margin(Kernel::Linear);
+    svm.train(&inputs, &outputs, 10_000, 1e-3);
 
     let mut lin = LinearPerceptron::new(2);
+    lin.train_classification(&inputs, &outputs, 50_000, 0.1);
 
-    let num_iter = 200_000;
-    let alpha = 0.01;
+    println!("Support vectors: {}", svm.support_vectors().len());
+    println!("SVM accuracy: {:.2}", svm_accuracy(&svm, &inputs, &outputs));
+    println!("SVM margin: {:.3}", svm.margin());
+    println!("Perceptron margin: {:.3}", linear_margin(lin.weights(), lin.bias(), &inputs, &outputs));
 
-    println!("\nTraining...");
-    lin.train_regression(&inputs, &outputs, num_iter, alpha);
+    // ## Test 3: XOR (polynomial kernel)
+    println!("\n=== SVM Test 3: XOR ===\n");
+    let Dataset { inputs, targets, .. } = datasets::xor(0, 4, 0.0);
 
-    println!("\nResults:");
-    for (x, y) in inputs.iter().zip(outputs.iter()) {
-        let p = lin.predict_regression(x);
-        println!("x={:?}, y={}, pred={:.2}", x, y[0], p);
-    }
-}
+    // The SVM only looks at the sign of the target: {0, 1} -> {-1, 1}
+    let outputs: Vec<Vec<f64>> = targets.iter().map(|y| vec![2.0 * y[0] - 1.0]).collect();
 
-fn run_mlp_classification_tests() {
-    // #### CLASSIFICATION ####
-
-    // ## Test 1: Linear Simple
-    println!("\n=== Test 1: Linear Simple ===\n");
-    let inputs = vec![
-            vec![1.0, 1.0],
-            vec![2.0, 3.0],
-            vec![3.0, 3.0],
-    ];
-    
-    let outputs = vec![
-        vec![1.0],
-        vec![-1.0],
-        vec![-1.0],
-    ];
-
-    let mut mlp = MyMLP::new(&[2, 1]); // input, hidden, output
-
-    let num_iter = 50_000; // iterations
-    let alpha = 0.1; // learning rate
-
-    println!("\nTraining...");
-    mlp.train(&inputs, &outputs, true, num_iter, alpha);
+    let mut svm = BinarySVM::hard_margin(Kernel::Polynomial { degree: 2, gamma: 1.0, coef0: 1.0 });
+    svm.train(&inputs, &outputs, 10_000, 1e-3);
 
     println!("\nResults:");
     for (x, y) in inputs.iter().zip(outputs.iter()) {
-        let p = mlp.predict(x, true)[0];
-        println!("x={:?}, y={}, pred={:.2}", x, y[0], p);
+        println!("x={:?}, y={}, f={:.2}, pred={:.2}", x, y[0], svm.decision_function(x), svm.predict_class(x));
     }
 
-    // ## Test 2: Linear Multiple
-    println!("\n=== Test 2: Linear Multiple ===\n");
-    let (inputs, outputs) = generate_test_2_dataset();
+    // ## Test 4: Cross (RBF kernel, soft margin)
+    println!("\n=== SVM Test 4: Cross ===\n");
+    let Dataset { inputs, targets: outputs, .. } = datasets::cross(time_seed(), 500, 0.0);
 
-    let mut mlp = MyMLP::new(&[2, 1]);
+    let mut svm = BinarySVM::new(Kernel::Rbf { gamma: 5.0 }, 10.0);
+    svm.train(&inputs, &outputs, 100_000, 1e-3);
 
-    let num_iter = 50_000;
-    let alpha = 0.1;
+    println!("Support vectors: {}", svm.support_vectors().len());
+    println!("SVM accuracy: {:.2}", svm_accuracy(&svm, &inputs, &outputs));
 
-    println!("Training...");
-    mlp.train(&inputs, &outputs, true, num_iter, alpha);
+    // ## Test 5: Three Classes (linear kernel, one-vs-rest)
+    println!("\n=== SVM Test 5: Three Classes ===\n");
+    let Dataset { inputs, targets: outputs, .. } = datasets::three_classes(time_seed(), 500, 0.0);
 
-    println!("\nResults:");
-    for i in 0..10 {
-        let p = mlp.predict(&inputs[i*10], true)[0];
-        println!("x={:?}, y={}, pred={:.2}", inputs[i*10], outputs[i*10][0], p);
-    }
+    let mut svm = MultiClassSVM::new(3, Kernel::Linear, 10.0, MultiClassStrategy::OneVsRest);
+    svm.train(&inputs, &outputs, 100_000, 1e-3);
 
-    // ## Test 3: XOR
-    println!("\n=== Test 3: XOR ===\n");
-    let inputs = vec![
-        vec![0.0, 0.0],
-        vec![0.0, 1.0],
-        vec![1.0, 0.0],
-        vec![1.0, 1.0],
-    ];
+    println!("Accuracy: {:.2}", multi_svm_accuracy(&svm, &inputs, &outputs));
 
-    let outputs = vec![
-        vec![0.0],
-        vec![1.0],
-        vec![1.0],
-        vec![0.0],
-    ];
-
-    let mut mlp = MyMLP::new(&[2, 2, 1]);
+    // ## Test 6: Multi Cross (RBF kernel, one-vs-one)
+    println!("\n=== SVM Test 6: Multi Cross ===\n");
+    let Dataset { inputs, targets: outputs, .. } = datasets::multi_cross(time_seed(), 1000, 0.0);
 
-    let num_iter = 500_000;
-    let alpha = 0.1;
+    let mut svm = MultiClassSVM::new(3, Kernel::Rbf { gamma: 20.0 }, 10.0, MultiClassStrategy::OneVsOne);
+    svm.train(&inputs, &outputs, 100_000, 1e-3);
 
-    println!("\nTraining...");
-    mlp.train(&inputs, &outputs, true, num_iter, alpha);
+    println!("Accuracy: {:.2}", multi_svm_accuracy(&svm, &inputs, &outputs));
+}
 
-    println!("\nResults:");
+fn svm_accuracy(svm: &BinarySVM, inputs: &[Vec<f64>], outputs: &[Vec<f64>]) -> f64 {
+    let mut correct = 0;
     for (x, y) in inputs.iter().zip(outputs.iter()) {
-        let p = mlp.predict(x, true)[0];
-        println!("x={:?}, y={}, pred={:.2}", x, y[0], p);
-    }
-
-    // ## Test 4: Cross
-    println!("\n=== Test 4: Cross ===\n");
-    let (inputs, outputs) = generate_test_4_dataset();
-
-    let mut mlp = MyMLP::new(&[2, 4, 1]);
-
-    let num_iter = 500_000;
-    let alpha = 0.05;
-
-    println!("Training...");
-    mlp.train(&inputs, &outputs, true, num_iter, alpha);
-
-    println!("\nResults:");
-    for i in 0..50 {
-        let p = mlp.predict(&inputs[i*10], true)[0];
-        println!("x={:?}, y={}, pred={:.2}", inputs[i*10], outputs[i*10][0], p);
-    }
-
-    // println!("\nPrediction comparison:");
-    // let mut correct = 0;
-    // let mut incorrect = 0;
-    // for i in 0..500 {
-    //     let p = mlp.predict(&inputs[i], true)[0];
-    //     if (p >= 0.0 && outputs[i][0] == 1.0) || (p < 0.0 && outputs[i][0] == -1.0) {
-    //         correct += 1;
-    //     } else {
-    //         incorrect += 1;
-    //     }
-    // }
-    // println!("Correct: {}, Incorrect: {}", correct, incorrect);
-
-    // ## Test 5: Three Classes
-    println!("\n=== Test 5: Three Classes ===\n");
-    let (inputs, outputs) = generate_test_5_dataset();
-    let mut mlp = MyMLP::new(&[2, 3]);
-
-    let num_iter = 500_000;
-    let alpha = 0.05;
-
-    println!("Training...");
-    mlp.train(&inputs, &outputs, true, num_iter, alpha);
-
-    println!("\nResults:");
-    for i in 0..30 {
-        let p = mlp.predict(&inputs[i*10], true);
-        println!("x={:?}, y={:?}, pred={:.2?}", inputs[i*10], outputs[i*10], p);
+        if svm.predict_class(x) == y[0].signum() {
+            correct += 1;
+        }
     }
+    correct as f64 / inputs.len() as f64
+}
 
-    // println!("\nPrediction comparison:");
-    // let mut correct = 0;
-    // let mut incorrect = 0;
-    // for i in 0..inputs.len() {
-    //     let p = mlp.predict(&inputs[i], true);
-    //     let predicted_class = if p[0] > p[1] && p[0] > p[2] {
-    //         vec![1.0, -1.0, -1.0]
-    //     } else if p[1] > p[0] && p[1] > p[2] {
-    //         vec![-1.0, 1.0, -1.0]
-    //     } else {
-    //         vec![-1.0, -1.0, 1.0]
-    //     };
-
-    //     if predicted_class == outputs[i] {
-    //         correct += 1;
-    //     } else {
-    //         incorrect += 1;
-    //     }
-    // }
-    // println!("Correct: {}, Incorrect: {}", correct, incorrect);
-
-    // ## Test 6: Multi Cross
-    println!("\n=== Test 6: Multi Cross ===\n");
-    let (inputs, outputs) = generate_test_6_dataset();
-    let mut mlp = MyMLP::new(&[2, 16, 16, 3]);
-
-    let num_iter = 10_000_000;
-    let alpha = 0.005;
-
-    println!("Training...");
-    mlp.train(&inputs, &outputs, true, num_iter, alpha);
-
-    println!("\nResults:");
-    for i in 0..30 {
-        let p = mlp.predict(&inputs[i*10], true);
-        println!("x={:?}, y={:?}, pred={:.2?}", inputs[i*10], outputs[i*10], p);
+fn multi_svm_accuracy(svm: &MultiClassSVM, inputs: &[Vec<f64>], outputs: &[Vec<f64>]) -> f64 {
+    let mut correct = 0;
+    for (x, y) in inputs.iter().zip(outputs.iter()) {
+        if svm.predict(x) == *y {
+            correct += 1;
+        }
     }
-
-    // println!("\nPrediction comparison:");
-    // let mut correct = 0;
-    // let mut incorrect = 0;
-    // for i in 0..inputs.len() {
-    //     let p = mlp.predict(&inputs[i], true);
-    //     let predicted_class = if p[0] > p[1] && p[0] > p[2] {
-    //         vec![1.0, -1.0, -1.0]
-    //     } else if p[1] > p[0] && p[1] > p[2] {
-    //         vec![-1.0, 1.0, -1.0]
-    //     } else {
-    //         vec![-1.0, -1.0, 1.0]
-    //     };
-
-    //     if predicted_class == outputs[i] {
-    //         correct += 1;
-    //     } else {
-    //         incorrect += 1;
-    //     }
-    // }
-    // println!("Correct: {}, Incorrect: {}", correct, incorrect);
+    correct as f64 / inputs.len() as f64
 }
 
-fn run_mlp_regression_tests() {
-    // #### REGRESSION ####
-    // Test 1: Linear Simple 2D
-    println!("\n=== Regression Test 1: Linear Simple 2D ===\n");
-    let inputs = vec![
-        vec![1.0],
-        vec![2.0],
-    ];
+fn run_knn_classification_tests() {
+    // #### KNN CLASSIFICATION ####
+    // Each generated dataset is drawn twice: the second draw is used as a test set.
 
-    let outputs = vec![
-        vec![2.0],
-        vec![4.0],
+    let cases: Vec<(Generator, usize)> = vec![
+        (datasets::linear_multiple, 100),
+        (datasets::cross, 500),
+        (datasets::three_classes, 500),
+        (datasets::multi_cross, 1000),
     ];
 
-    let mut mlp = MyMLP::new(&[1, 1]);
-
-    let num_iter = 50_000;
-    let alpha = 0.1;
-
-    println!("\nTraining...");
-    mlp.train(&inputs, &outputs, false, num_iter, alpha);
-
-    println!("\nResults:");
-    for (x, y) in inputs.iter().zip(outputs.iter()) {
-        let p = mlp.predict(x, false)[0];
-        println!("x={:?}, y={}, pred={:.2}", x, y[0], p);
+    for (generate, num_samples) in cases {
+        let Dataset { inputs, targets: outputs, metadata } = generate(time_seed(), num_samples, 0.0);
+        let test = generate(time_seed(), num_samples, 0.0);
+        let (test_inputs, test_outputs) = (test.inputs, test.targets);
+        println!("\n=== KNN Test: {} ===\n", metadata.name);
+
+        for k in [1, 5, 15] {
+            let mut knn = KNNClassifier::new(k, Distance::Euclidean, Weighting::Distance, Algorithm::KdTree { leaf_size: 16 });
+            knn.fit(&inputs, &outputs);
+
+            let mut correct = 0;
+            for (x, y) in test_inputs.iter().zip(test_outputs.iter()) {
+                if knn.predict(x) == *y {
+                    correct += 1;
+                }
+            }
+            println!("k={}: test accuracy={:.2}", k, correct as f64 / test_inputs.len() as f64);
+        }
     }
+}
 
-    // Test 2: Non-Linear Simple 2D
-    println!("\n=== Regression Test 2: Non-Linear Simple 2D ===\n");
-    let inputs = vec![
-        vec![1.0],
-        vec![2.0],
-        vec![3.0],
-    ];
-
-    let outputs = vec![
-        vec![2.0],
-        vec![3.0],
-        vec![2.5],
-    ];
-
-    let mut mlp = MyMLP::new(&[1, 3, 1]);
+fn run_knn_regression_tests() {
+    // #### KNN REGRESSION ####
 
-    let num_iter = 100_000;
-    let alpha = 0.05;
+    // Test 2: Non-Linear Simple 2D
+    println!("\n=== KNN Test 2: Non-Linear Simple 2D ===\n");
+    let Dataset { inputs, targets: outputs, .. } = datasets::non_linear_simple_2d(0, 3, 0.0);
 
-    println!("\nTraining...");
-    mlp.train(&inputs, &outputs, false, num_iter, alpha);
+    let mut knn = KNNRegressor::new(2, Distance::Euclidean, Weighting::Distance, Algorithm::BruteForce);
+    knn.fit(&inputs, &outputs);
 
     println!("\nResults:");
-    for (x, y) in inputs.iter().zip(outputs.iter()) {
-        let p = mlp.predict(x, false)[0];
-        println!("x={:?}, y={}, pred={:.2}", x, y[0], p);
+    for x in [vec![1.0], vec![1.5], vec![2.0], vec![2.5], vec![3.0]] {
+        println!("x={:?}, pred={:.2}", x, knn.predict(&x)[0]);
     }
 
-    // Test 3: Linear Simple 3D
-    println!("\n=== Regression Test 3: Linear Simple 3D ===\n");
-    let inputs = vec![
-        vec![1.0, 1.0],
-        vec![2.0, 2.0],
-        vec![3.0, 1.0],
-    ];
-
-    let outputs = vec![
-        vec![2.0],
-        vec![3.0],
-        vec![2.5],
-    ];
-
-    let mut mlp = MyMLP::new(&[2, 1]);
-
-    let num_iter = 50_000;
-    let alpha = 0.1;
+    // Test 5: Non-Linear Simple 3D
+    println!("\n=== KNN Test 5: Non-Linear Simple 3D ===\n");
+    let Dataset { inputs, targets: outputs, .. } = datasets::non_linear_simple_3d(0, 4, 0.0);
 
-    println!("\nTraining...");
-    mlp.train(&inputs, &outputs, false, num_iter, alpha);
+    let mut knn = KNNRegressor::new(1, Distance::Euclidean, Weighting::Uniform, Algorithm::BruteForce);
+    knn.fit(&inputs, &outputs);
 
     println!("\nResults:");
     for (x, y) in inputs.iter().zip(outputs.iter()) {
-        let p = mlp.predict(x, false)[0];
-        println!("x={:?}, y={}, pred={:.2}", x, y[0], p);
+        println!("x={:?}, y={}, pred={:.2}", x, y[0], knn.predict(x)[0]);
     }
+}
 
-    // Test 4: Linear Tricky 3D
-    println!("\n=== Regression Test 4: Linear Tricky 3D ===\n");
-    let inputs = vec![
-        vec![1.0, 1.0],
-        vec![2.0, 2.0],
-        vec![3.0, 3.0],
-    ];
+fn run_tree_classification_tests() {
+    // #### DECISION TREE / RANDOM FOREST CLASSIFICATION ####
+    // Each generated dataset is drawn twice: the second draw is used as a test set.
 
-    let outputs = vec![
-        vec![1.0],
-        vec![2.0],
-        vec![3.0],
+    let cases: Vec<(Generator, usize)> = vec![
+        (datasets::linear_multiple, 100),
+        (datasets::cross, 500),
+        (datasets::three_classes, 500),
+        (datasets::multi_cross, 1000),
     ];
 
-    let mut mlp = MyMLP::new(&[2, 1]);
-
-    let num_iter = 100_000;
-    let alpha = 0.1;
-
-    println!("\nTraining...");
-    mlp.train(&inputs, &outputs, false, num_iter, alpha);
-
-    println!("\nResults:");
-    for (x, y) in inputs.iter().zip(outputs.iter()) {
-        let p = mlp.predict(x, false)[0];
-        println!("x={:?}, y={}, pred={:.2}", x, y[0], p);
+    for (generate, num_samples) in cases {
+        let Dataset { inputs, targets: outputs, metadata } = generate(time_seed(), num_samples, 0.0);
+        let test = generate(time_seed(), num_samples, 0.0);
+        let (test_inputs, test_outputs) = (test.inputs, test.targets);
+        println!("\n=== Tree Test: {} ===\n", metadata.name);
+
+        let mut tree = DecisionTree::new(Criterion::Gini, TreeParams { max_depth: 8, ..TreeParams::default() });
+        tree.fit(&inputs, &outputs);
+
+        let mut forest = RandomForest::new(100, Criterion::Gini, TreeParams::default(), None, None);
+        forest.fit(&inputs, &outputs);
+
+        let mut tree_correct = 0;
+        let mut forest_correct = 0;
+        for (x, y) in test_inputs.iter().zip(test_outputs.iter()) {
+            if tree.predict(x) == *y {
+                tree_correct += 1;
+            }
+            if forest.predict(x) == *y {
+                forest_correct += 1;
+            }
+        }
+        println!(
+            "Tree: depth={}, leaves={}, test accuracy={:.2}",
+            tree.depth(),
+            tree.num_leaves(),
+            tree_correct as f64 / test_inputs.len() as f64
+        );
+        println!("Forest: test accuracy={:.2}", forest_correct as f64 / test_inputs.len() as f64);
+        println!("Forest feature importances: {:.2?}", forest.feature_importances());
     }
+}
 
-    // Test 5: Non-Linear Simple 3D
-    println!("\n=== Regression Test 5: Non-Linear Simple 3D ===\n");
-    let inputs = vec![
-        vec![1.0, 0.0],
-        vec![0.0, 1.0],
-        vec![1.0, 1.0],
-        vec![0.0, 0.0],
-    ];
-
-    let outputs = vec![
-        vec![2.0],
-        vec![1.0],
-        vec![-2.0],
-        vec![-1.0],
-    ];
+fn run_tree_regression_tests() {
+    // #### DECISION TREE / RANDOM FOREST REGRESSION ####
 
-    let mut mlp = MyMLP::new(&[2, 2, 1]);
+    // Test 5: Non-Linear Simple 3D
+    println!("\n=== Tree Test 5: Non-Linear Simple 3D ===\n");
+    let Dataset { inputs, targets: outputs, .. } = datasets::non_linear_simple_3d(0, 4, 0.0);
 
-    let num_iter = 200_000;
-    let alpha = 0.01;
+    let mut tree = DecisionTree::new(Criterion::Mse, TreeParams::default());
+    tree.fit(&inputs, &outputs);
 
-    println!("\nTraining...");
-    mlp.train(&inputs, &outputs, false, num_iter, alpha);
+    let mut forest = RandomForest::new(50, Criterion::Mse, TreeParams::default(), Some(2), None);
+    forest.fit(&inputs, &outputs);
 
     println!("\nResults:");
     for (x, y) in inputs.iter().zip(outputs.iter()) {
-        let p = mlp.predict(x, false)[0];
-        println!("x={:?}, y={}, pred={:.2}", x, y[0], p);
+        println!("x={:?}, y={}, tree={:.2}, forest={:.2}", x, y[0], tree.predict(x)[0], forest.predict(x)[0]);
     }
 }
@@ -0,0 +1,364 @@
+// Classification and regression metrics.
+//
+// Every metric takes `(predictions, targets)` as the paired `Vec<f64>` rows the models
+// produce and consume, so any of them can be used as a `cross_validation::Scorer`.
+// Classes are read from the rows with `split::class_labels`: the sign of a single
+// output (> 0 is the positive class, so 0 / 1 targets work too), the argmax of
+// several outputs (one-hot or ±1 per class).
+//
+// Predictions are read on the ±1 scale of tanh outputs: single outputs are cut at 0,
+// probabilities are (y + 1) / 2. Predictions on a 0 / 1 scale (models trained on
+// `Index` or `OneHot` targets, see `DatasetInfo::label_encoding`) go through
+// `to_bipolar` first. The scale is never guessed from the targets.
+
+use crate::cross_validation::Scorer;
+use crate::csv_loader::LabelEncoding;
+use crate::split::class_labels;
+
+/// One class per output, two classes for single outputs
+fn num_classes(targets: &[Vec<f64>]) -> usize {
+    targets.first().map_or(2, |t| t.len().max(2))
+}
+
+/// Predictions of a model trained on `num_classes` classes encoded with `encoding`, moved
+/// to the ±1 scale the classification metrics read: 2y - 1 for the 0 / 1 scale of `Index`
+/// and `OneHot`, unchanged for `Bipolar` and `Numeric`. A single class index has no ±1
+/// reading beyond two classes, so `Index` needs exactly two.
+pub fn to_bipolar(predictions: &[Vec<f64>], encoding: LabelEncoding, num_classes: usize) -> Vec<Vec<f64>> {
+    if encoding == LabelEncoding::Index {
+        assert_eq!(num_classes, 2, "Index predictions can only be scored for two classes");
+    }
+    match encoding {
+        LabelEncoding::Index | LabelEncoding::OneHot => predictions
+            .iter()
+            .map(|p| p.iter().map(|y| 2.0 * y - 1.0).collect())
+            .collect(),
+        LabelEncoding::Bipolar | LabelEncoding::Numeric => predictions.to_vec(),
+    }
+}
+
+fn check(predictions: &[Vec<f64>], targets: &[Vec<f64>]) {
+    assert_eq!(predictions.len(), targets.len(), "Need one prediction per target");
+    assert!(!targets.is_empty(), "Need at least one sample");
+}
+
+/// Fraction of samples predicted in the right class
+pub fn accuracy(predictions: &[Vec<f64>], targets: &[Vec<f64>]) -> f64 {
+    check(predictions, targets);
+    let predicted = class_labels(predictions);
+    let expected = class_labels(targets);
+    let correct = predicted.iter().zip(expected.iter()).filter(|(p, e)| p == e).count();
+    correct as f64 / targets.len() as f64
+}
+
+/// matrix[true class][predicted class] = number of samples
+pub fn confusion_matrix(predictions: &[Vec<f64>], targets: &[Vec<f64>]) -> Vec<Vec<usize>> {
+    check(predictions, targets);
+    assert!(
+        predictions.iter().zip(targets).all(|(p, t)| p.len() == t.len()),
+        "Predictions and targets must have the same width"
+    );
+    let k = num_classes(targets);
+    let mut matrix = vec![vec![0; k]; k];
+    for (p, e) in class_labels(predictions).into_iter().zip(class_labels(targets)) {
+        matrix[e][p] += 1;
+    }
+    matrix
+}
+
+/// Mean of the per-class recalls, not fooled by unbalanced classes
+pub fn balanced_accuracy(predictions: &[Vec<f64>], targets: &[Vec<f64>]) -> f64 {
+    let report = classification_report(predictions, targets);
+    let present: Vec<&ClassScores> = report.per_class.iter().filter(|c| c.support > 0).collect();
+    present.iter().map(|c| c.recall).sum::<f64>() / present.len() as f64
+}
+
+/// Precision, recall and F1 of one class
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ClassScores {
+    pub precision: f64,
+    pub recall: f64,
+    pub f1: f64,
+    /// Number of samples of the class
+    pub support: usize,
+}
+
+impl ClassScores {
+    fn new(true_positives: usize, false_positives: usize, false_negatives: usize) -> Self {
+        let ratio = |a: usize, b: usize| if a + b == 0 { 0.0 } else { a as f64 / (a + b) as f64 };
+        let precision = ratio(true_positives, false_positives);
+        let recall = ratio(true_positives, false_negatives);
+        let f1 = if precision + recall == 0.0 {
+            0.0
+        } else {
+            2.0 * precision * recall / (precision + recall)
+        };
+        ClassScores {
+            precision,
+            recall,
+            f1,
+            support: true_positives + false_negatives,
+        }
+    }
+}
+
+/// How per-class scores are combined
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Average {
+    /// Unweighted mean over the classes
+    Macro,
+    /// Computed from the counts pooled over the classes (equals the accuracy for single-label data)
+    Micro,
+    /// Mean over the classes weighted by their support
+    Weighted,
+}
+
+#[derive(Clone, Debug)]
+pub struct ClassificationReport {
+    pub per_class: Vec<ClassScores>,
+    pub macro_avg: ClassScores,
+    pub micro_avg: ClassScores,
+    pub weighted_avg: ClassScores,
+}
+
+impl ClassificationReport {
+    pub fn average(&self, average: Average) -> ClassScores {
+        match average {
+            Average::Macro => self.macro_avg,
+            Average::Micro => self.micro_avg,
+            Average::Weighted => self.weighted_avg,
+        }
+    }
+}
+
+/// Per-class and averaged precision / recall / F1
+pub fn classification_report(predictions: &[Vec<f64>], targets: &[Vec<f64>]) -> ClassificationReport {
+    let matrix = confusion_matrix(predictions, targets);
+    let k = matrix.len();
+    let mut per_class = Vec::with_capacity(k);
+    let (mut tp_sum, mut fp_sum, mut fn_sum) = (0, 0, 0);
+    for (c, row) in matrix.iter().enumerate() {
+        let tp = row[c];
+        let fp = matrix.iter().map(|r| r[c]).sum::<usize>() - tp;
+        let fn_ = row.iter().sum::<usize>() - tp;
+        tp_sum += tp;
+        fp_sum += fp;
+        fn_sum += fn_;
+        per_class.push(ClassScores::new(tp, fp, fn_));
+    }
+
+    let total: usize = per_class.iter().map(|c| c.support).sum();
+    let mean = |weight: &dyn Fn(&ClassScores) -> f64| {
+        let weights: f64 = per_class.iter().map(weight).sum();
+        let avg = |f: fn(&ClassScores) -> f64| per_class.iter().map(|c| weight(c) * f(c)).sum::<f64>() / weights;
+        ClassScores {
+            precision: avg(|c| c.precision),
+            recall: avg(|c| c.recall),
+            f1: avg(|c| c.f1),
+            support: total,
+        }
+    };
+    let macro_avg = mean(&|_| 1.0);
+    let weighted_avg = mean(&|c| c.support as f64);
+    ClassificationReport {
+        per_class,
+        macro_avg,
+        micro_avg: ClassScores::new(tp_sum, fp_sum, fn_sum),
+        weighted_avg,
+    }
+}
+
+pub fn precision(predictions: &[Vec<f64>], targets: &[Vec<f64>], average: Average) -> f64 {
+    classification_report(predictions, targets).average(average).precision
+}
+
+pub fn recall(predictions: &[Vec<f64>], targets: &[Vec<f64>], average: Average) -> f64 {
+    classification_report(predictions, targets).average(average).recall
+}
+
+pub fn f1_score(predictions: &[Vec<f64>], targets: &[Vec<f64>], average: Average) -> f64 {
+    classification_report(predictions, targets).average(average).f1
+}
+
+/// Macro-averaged F1, usable as a `Scorer`
+pub fn macro_f1(predictions: &[Vec<f64>], targets: &[Vec<f64>]) -> f64 {
+    f1_score(predictions, targets, Average::Macro)
+}
+
+/// Class probabilities of a row of model outputs.
+///
+/// Outputs are mapped from [-1, 1] to [0, 1] first. A single output is the
+/// probability of the positive class, several outputs are normalised.
+fn probabilities(output: &[f64]) -> Vec<f64> {
+    let unit: Vec<f64> = output.iter().map(|&v| ((v + 1.0) / 2.0).clamp(0.0, 1.0)).collect();
+    if unit.len() == 1 {
+        vec![1.0 - unit[0], unit[0]]
+    } else {
+        let sum: f64 = unit.iter().sum();
+        if sum > 0.0 {
+            unit.iter().map(|p| p / sum).collect()
+        } else {
+            vec![1.0 / unit.len() as f64; unit.len()]
+        }
+    }
+}
+
+/// Mean negative log-likelihood of the true classes (probabilities clipped to [1e-15, 1 - 1e-15])
+pub fn log_loss(predictions: &[Vec<f64>], targets: &[Vec<f64>]) -> f64 {
+    check(predictions, targets);
+    let expected = class_labels(targets);
+    let eps = 1e-15;
+    predictions
+        .iter()
+        .zip(expected.iter())
+        .map(|(p, &c)| -probabilities(p)[c].clamp(eps, 1.0 - eps).ln())
+        .sum::<f64>()
+        / targets.len() as f64
+}
+
+/// ROC curve of binary scores: (false positive rate, true positive rate) points
+/// from the highest threshold to the lowest, starting at (0, 0) and ending at (1, 1)
+pub fn roc_curve(scores: &[f64], positives: &[bool]) -> Vec<(f64, f64)> {
+    assert_eq!(scores.len(), positives.len());
+    let num_pos = positives.iter().filter(|&&p| p).count() as f64;
+    let num_neg = positives.len() as f64 - num_pos;
+    let mut order: Vec<usize> = (0..scores.len()).collect();
+    order.sort_by(|&a, &b| scores[b].total_cmp(&scores[a]));
+
+    let mut curve = vec![(0.0, 0.0)];
+    let (mut tp, mut fp) = (0.0, 0.0);
+    for (i, &s) in order.iter().enumerate() {
+        if positives[s] {
+            tp += 1.0;
+        } else {
+            fp += 1.0;
+        }
+        // One point per distinct threshold
+        if i + 1 == order.len() || scores[order[i + 1]] != scores[s] {
+            curve.push((
+                if num_neg > 0.0 { fp / num_neg } else { 0.0 },
+                if num_pos > 0.0 { tp / num_pos } else { 0.0 },
+            ));
+        }
+    }
+    curve
+}
+
+/// Area under a ROC curve (trapezoidal rule)
+pub fn auc(curve: &[(f64, f64)]) -> f64 {
+    curve
+        .windows(2)
+        .map(|w| (w[1].0 - w[0].0) * (w[1].1 + w[0].1) / 2.0)
+        .sum()
+}
+
+/// ROC-AUC: the first output is the score for single outputs,
+/// one-vs-rest AUC averaged over the classes (macro) for several outputs
+pub fn roc_auc(predictions: &[Vec<f64>], targets: &[Vec<f64>]) -> f64 {
+    check(predictions, targets);
+    let expected = class_labels(targets);
+    if targets[0].len() == 1 {
+        let scores: Vec<f64> = predictions.iter().map(|p| p[0]).collect();
+        let positives: Vec<bool> = expected.iter().map(|&c| c == 1).collect();
+        return auc(&roc_curve(&scores, &positives));
+    }
+    let k = targets[0].len();
+    let aucs: Vec<f64> = (0..k)
+        .filter(|&c| expected.contains(&c))
+        .map(|c| {
+            let scores: Vec<f64> = predictions.iter().map(|p| p[c]).collect();
+            let positives: Vec<bool> = expected.iter().map(|&e| e == c).collect();
+            auc(&roc_curve(&scores, &positives))
+        })
+        .collect();
+    aucs.iter().sum::<f64>() / aucs.len() as f64
+}
+
+/// (prediction, target) pairs of every output of every sample
+fn pairs<'a>(predictions: &'a [Vec<f64>], targets: &'a [Vec<f64>]) -> impl Iterator<Item = (f64, f64)> + 'a {
+    predictions
+        .iter()
+        .zip(targets.iter())
+        .flat_map(|(p, t)| p.iter().cloned().zip(t.iter().cloned()))
+}
+
+fn count(predictions: &[Vec<f64>], targets: &[Vec<f64>]) -> f64 {
+    check(predictions, targets);
+    pairs(predictions, targets).count() as f64
+}
+
+/// Squared error averaged over the samples and the outputs
+pub fn mean_squared_error(predictions: &[Vec<f64>], targets: &[Vec<f64>]) -> f64 {
+    pairs(predictions, targets).map(|(p, t)| (p - t).powi(2)).sum::<f64>() / count(predictions, targets)
+}
+
+pub fn root_mean_squared_error(predictions: &[Vec<f64>], targets: &[Vec<f64>]) -> f64 {
+    mean_squared_error(predictions, targets).sqrt()
+}
+
+pub fn mean_absolute_error(predictions: &[Vec<f64>], targets: &[Vec<f64>]) -> f64 {
+    pairs(predictions, targets).map(|(p, t)| (p - t).abs()).sum::<f64>() / count(predictions, targets)
+}
+
+/// Mean of |error / target| (targets equal to 0 are skipped), as a fraction (not in %)
+pub fn mean_absolute_percentage_error(predictions: &[Vec<f64>], targets: &[Vec<f64>]) -> f64 {
+    check(predictions, targets);
+    let errors: Vec<f64> = pairs(predictions, targets)
+        .filter(|&(_, t)| t != 0.0)
+        .map(|(p, t)| ((p - t) / t).abs())
+        .collect();
+    errors.iter().sum::<f64>() / errors.len().max(1) as f64
+}
+
+/// 1 - (residual variance or sum of squares) / (target variance), averaged over the outputs
+fn per_output_score(predictions: &[Vec<f64>], targets: &[Vec<f64>], centre_residuals: bool) -> f64 {
+    check(predictions, targets);
+    let n = targets.len() as f64;
+    let d = targets[0].len();
+    let mut total = 0.0;
+    for j in 0..d {
+        let mean_t = targets.iter().map(|t| t[j]).sum::<f64>() / n;
+        let residuals: Vec<f64> = predictions.iter().zip(targets.iter()).map(|(p, t)| t[j] - p[j]).collect();
+        let mean_r = if centre_residuals { residuals.iter().sum::<f64>() / n } else { 0.0 };
+        let ss_res: f64 = residuals.iter().map(|r| (r - mean_r).powi(2)).sum();
+        let ss_tot: f64 = targets.iter().map(|t| (t[j] - mean_t).powi(2)).sum();
+        total += if ss_tot > 0.0 {
+            1.0 - ss_res / ss_tot
+        } else if ss_res == 0.0 {
+            1.0
+        } else {
+            0.0
+        };
+    }
+    total / d as f64
+}
+
+/// Coefficient of determination (1 = perfect, 0 = as good as predicting the mean)
+pub fn r2_score(predictions: &[Vec<f64>], targets: &[Vec<f64>]) -> f64 {
+    per_output_score(predictions, targets, false)
+}
+
+/// Like R², but a constant bias of the predictions is not penalised
+pub fn explained_variance(predictions: &[Vec<f64>], targets: &[Vec<f64>]) -> f64 {
+    per_output_score(predictions, targets, true)
+}
+
+/// Metric from its name in config files and reports: accuracy, balanced_accuracy,
+/// macro_f1, log_loss, roc_auc, mse, rmse, mae, mape, r2, explained_variance
+pub fn by_name(name: &str) -> Option<Scorer> {
+    let metric: Scorer = match name {
+        "accuracy" => accuracy,
+        "balanced_accuracy" => balanced_accuracy,
+        "macro_f1" => macro_f1,
+        "log_loss" => log_loss,
+        "roc_auc" => roc_auc,
+        "mse" => mean_squared_error,
+        "rmse" => root_mean_squared_error,
+        "mae" => mean_absolute_error,
+        "mape" => mean_absolute_percentage_error,
+        "r2" => r2_score,
+        "explained_variance" => explained_variance,
+        _ => return None,
+    };
+    Some(metric)
+}
@@ -1,5 +1,12 @@
-use rand::Rng;
+#![allow(non_snake_case)]
 
+use crate::augmentation::AugmentationPipeline;
+use crate::history::{Monitor, TrainingHistory};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct MyMLP {
     /// neurons per layer (input included)
     d: Vec<usize>,
@@ -14,17 +21,67 @@ pub struct MyMLP {
     pub(crate) X: Vec<Vec<f64>>,
     /// deltas[l][j] = backpropagation error for neuron j in layer l
     pub(crate) deltas: Vec<Vec<f64>>,
+    /// Whether the last forward pass applied tanh to the outputs (classification)
+    #[serde(default)]
+    tanh_output: bool,
+    /// Buffer reused by `train_sample`
+    #[serde(skip)]
+    gradients: Option<Gradients>,
+}
+
+/// Gradients of a loss wrt the weights of a `MyMLP` and wrt its inputs
+#[derive(Clone, Debug, PartialEq)]
+pub struct Gradients {
+    /// weights[l][i][j] = gradient wrt W[l][i][j], same shape as the weights
+    /// (j = 0 is always 0)
+    pub weights: Vec<Vec<Vec<f64>>>,
+    /// Gradient wrt each input of the last forward pass
+    pub inputs: Vec<f64>,
+}
+
+impl Gradients {
+    /// Add the gradients of another loss or sample
+    pub fn add(&mut self, other: &Gradients) {
+        assert_eq!(self.inputs.len(), other.inputs.len(), "Gradients of different networks");
+        for (layer, other_layer) in self.weights.iter_mut().zip(other.weights.iter()) {
+            for (row, other_row) in layer.iter_mut().zip(other_layer.iter()) {
+                for (g, o) in row.iter_mut().zip(other_row.iter()) {
+                    *g += o;
+                }
+            }
+        }
+        for (g, o) in self.inputs.iter_mut().zip(other.inputs.iter()) {
+            *g += o;
+        }
+    }
+
+    /// Multiply every gradient, e.g. by 1 / n to average n samples
+    pub fn scale(&mut self, factor: f64) {
+        for g in self.weights.iter_mut().flatten().flatten() {
+            *g *= factor;
+        }
+        for g in self.inputs.iter_mut() {
+            *g *= factor;
+        }
+    }
 }
 
 impl MyMLP {
     /// npl: neurons per layer (input included)
     pub fn new(npl: &[usize]) -> Self {
+        Self::with_rng(npl, &mut rand::thread_rng())
+    }
+
+    /// Same as `new`, with reproducible initial weights
+    pub fn with_seed(npl: &[usize], seed: u64) -> Self {
+        Self::with_rng(npl, &mut StdRng::seed_from_u64(seed))
+    }
+
+    fn with_rng<R: Rng>(npl: &[usize], rng: &mut R) -> Self {
         assert!(npl.len() >= 2, "Need at least input and output layers");
         let d = npl.to_vec();
         let L = d.len() - 1;
 
-        let mut rng = rand::thread_rng();
-
         // Initialize weights
         let mut W: Vec<Vec<Vec<f64>>> = Vec::with_capacity(d.len());
         for l in 0..d.len() {
@@ -54,11 +111,11 @@ impl MyMLP {
         let mut X: Vec<Vec<f64>> = Vec::with_capacity(d.len());
         let mut deltas: Vec<Vec<f64>> = Vec::with_capacity(d.len());
 
-        for l in 0..d.len() {
-            let mut x_layer = Vec::with_capacity(d[l] + 1);
-            let mut delta_layer = Vec::with_capacity(d[l] + 1);
+        for &n in &d {
+            let mut x_layer = Vec::with_capacity(n + 1);
+            let mut delta_layer = Vec::with_capacity(n + 1);
 
-            for j in 0..=d[l] {
+            for j in 0..=n {
                 x_layer.push(if j == 0 { 1.0 } else { 0.0 });
                 delta_layer.push(0.0);
             }
@@ -67,7 +124,15 @@ impl MyMLP {
             deltas.push(delta_layer);
         }
 
-        MyMLP { d, L, W, X, deltas }
+        MyMLP {
+            d,
+            L,
+            W,
+            X,
+            deltas,
+            tanh_output: false,
+            gradients: None,
+        }
     }
 
     fn propagate(&mut self, inputs: &[f64], is_classification: bool) {
@@ -77,6 +142,8 @@ impl MyMLP {
             "Input size must match number of input neurons"
         );
 
+        self.tanh_output = is_classification;
+
         // Copy inputs into X[0][1..]
         for (j, &val) in inputs.iter().enumerate() {
             self.X[0][j + 1] = val;
@@ -100,10 +167,110 @@ impl MyMLP {
     }
 
     pub fn predict(&mut self, inputs: &[f64], is_classification: bool) -> Vec<f64> {
+        self.forward(inputs, is_classification)
+    }
+
+    /// Outputs for `inputs` (tanh for classification, linear for regression), the
+    /// activations are kept for `backward`
+    pub fn forward(&mut self, inputs: &[f64], is_classification: bool) -> Vec<f64> {
         self.propagate(inputs, is_classification);
         self.X[self.L][1..=self.d[self.L]].to_vec()
     }
 
+    /// Gradients filled with zeros, shaped for this network
+    pub fn zero_gradients(&self) -> Gradients {
+        Gradients {
+            weights: self.W.iter().map(|layer| layer.iter().map(|row| vec![0.0; row.len()]).collect()).collect(),
+            inputs: vec![0.0; self.d[0]],
+        }
+    }
+
+    /// Backpropagate `output_grad`, the gradient of a loss wrt the outputs of the last
+    /// `forward` (outputs - expected for 0.5 * ||outputs - expected||², the training
+    /// loss). The weights are left unchanged.
+    pub fn backward(&mut self, output_grad: &[f64]) -> Gradients {
+        let mut gradients = self.zero_gradients();
+        self.backward_into(output_grad, &mut gradients);
+        gradients
+    }
+
+    /// Same as `backward`, written over `gradients` (from `zero_gradients`) so that
+    /// training loops reuse it
+    pub fn backward_into(&mut self, output_grad: &[f64], gradients: &mut Gradients) {
+        assert_eq!(
+            output_grad.len(),
+            self.d[self.L],
+            "Output gradient size must match number of output neurons"
+        );
+
+        // Output layer deltas
+        for j in 1..=self.d[self.L] {
+            let mut delta = output_grad[j - 1];
+            if self.tanh_output {
+                delta *= 1.0 - self.X[self.L][j].powi(2);
+            }
+            self.deltas[self.L][j] = delta;
+        }
+
+        // Hidden layers
+        if self.L >= 2 {
+            for l in (2..=self.L).rev() {
+                for i in 1..=self.d[l - 1] {
+                    let mut total = 0.0;
+                    for j in 1..=self.d[l] {
+                        total += self.W[l][i][j] * self.deltas[l][j];
+                    }
+                    total *= 1.0 - self.X[l - 1][i].powi(2);
+                    self.deltas[l - 1][i] = total;
+                }
+            }
+        }
+
+        // Weights (deltas[l][0] = 0: no gradient into the bias neurons)
+        for l in 1..=self.L {
+            for (row, &x) in gradients.weights[l].iter_mut().zip(self.X[l - 1].iter()) {
+                for (g, &delta) in row.iter_mut().zip(self.deltas[l].iter()) {
+                    *g = x * delta;
+                }
+            }
+        }
+
+        // Inputs (layer 0 has no activation)
+        for i in 1..=self.d[0] {
+            let mut total = 0.0;
+            for j in 1..=self.d[1] {
+                total += self.W[1][i][j] * self.deltas[1][j];
+            }
+            gradients.inputs[i - 1] = total;
+        }
+    }
+
+    /// Gradient descent step: W -= alpha * gradients
+    pub fn apply_gradients(&mut self, gradients: &Gradients, alpha: f64) {
+        assert_eq!(gradients.weights.len(), self.W.len(), "Gradients of a different network");
+        for (w_layer, g_layer) in self.W.iter_mut().zip(gradients.weights.iter()) {
+            for (w_row, g_row) in w_layer.iter_mut().zip(g_layer.iter()) {
+                for (w, g) in w_row.iter_mut().zip(g_row.iter()) {
+                    *w -= alpha * g;
+                }
+            }
+        }
+    }
+
+    /// Neurons per layer (input included)
+    pub fn layers(&self) -> &[usize] {
+        &self.d
+    }
+
+    /// Number of trainable weights of each weight layer (biases included)
+    pub fn parameters_per_layer(&self) -> Vec<usize> {
+        (1..=self.L).map(|l| (self.d[l - 1] + 1) * self.d[l]).collect()
+    }
+
+    pub fn num_parameters(&self) -> usize {
+        self.parameters_per_layer().iter().sum()
+    }
+
     pub fn train(
         &mut self,
         all_samples_inputs: &[Vec<f64>],
@@ -112,55 +279,155 @@ impl MyMLP {
         num_iter: usize,
         alpha: f64,
     ) {
+        self.train_with_rng(
+            &mut rand::thread_rng(),
+            all_samples_inputs,
+            all_samples_expected_outputs,
+            is_classification,
+            num_iter,
+            alpha,
+        );
+    }
+
+    /// Same as `train`, with the samples drawn from `rng`: a seeded generator makes
+    /// the training reproducible
+    pub fn train_with_rng<R: Rng>(
+        &mut self,
+        rng: &mut R,
+        all_samples_inputs: &[Vec<f64>],
+        all_samples_expected_outputs: &[Vec<f64>],
+        is_classification: bool,
+        num_iter: usize,
+        alpha: f64,
+    ) {
+        self.train_loop(
+            rng,
+            all_samples_inputs,
+            all_samples_expected_outputs,
+            is_classification,
+            num_iter,
+            alpha,
+            None,
+            None,
+        );
+    }
+
+    /// Same as `train`, but the loss (mean squared error) and the metrics of `monitor`
+    /// are recorded on the training and validation samples every `monitor.every` iterations
+    pub fn train_monitored(
+        &mut self,
+        all_samples_inputs: &[Vec<f64>],
+        all_samples_expected_outputs: &[Vec<f64>],
+        is_classification: bool,
+        num_iter: usize,
+        alpha: f64,
+        monitor: &Monitor,
+    ) -> TrainingHistory {
+        self.train_loop(
+            &mut rand::thread_rng(),
+            all_samples_inputs,
+            all_samples_expected_outputs,
+            is_classification,
+            num_iter,
+            alpha,
+            Some(monitor),
+            None,
+        )
+    }
+
+    /// Same as `train`, but each drawn sample goes through `augmentation` first,
+    /// so the network never sees exactly the same input twice
+    pub fn train_augmented(
+        &mut self,
+        all_samples_inputs: &[Vec<f64>],
+        all_samples_expected_outputs: &[Vec<f64>],
+        is_classification: bool,
+        num_iter: usize,
+        alpha: f64,
+        augmentation: &mut AugmentationPipeline,
+    ) {
+        self.train_loop(
+            &mut rand::thread_rng(),
+            all_samples_inputs,
+            all_samples_expected_outputs,
+            is_classification,
+            num_iter,
+            alpha,
+            None,
+            Some(augmentation),
+        );
+    }
+
+    /// Training loop behind `train`, `train_monitored` and `train_augmented`: a sample
+    /// drawn from `rng` per iteration, augmented and monitored when asked
+    #[allow(clippy::too_many_arguments)]
+    fn train_loop<R: Rng>(
+        &mut self,
+        rng: &mut R,
+        all_samples_inputs: &[Vec<f64>],
+        all_samples_expected_outputs: &[Vec<f64>],
+        is_classification: bool,
+        num_iter: usize,
+        alpha: f64,
+        monitor: Option<&Monitor>,
+        mut augmentation: Option<&mut AugmentationPipeline>,
+    ) -> TrainingHistory {
         assert_eq!(
             all_samples_inputs.len(),
             all_samples_expected_outputs.len()
         );
 
-        let mut rng = rand::thread_rng();
+        let mut history = TrainingHistory::new();
 
         for it in 0..num_iter {
             let k = rng.gen_range(0..all_samples_inputs.len());
-            let inputs_k = &all_samples_inputs[k];
-            let expected_outputs_k = &all_samples_expected_outputs[k];
+            let augmented = augmentation.as_mut().map(|a| a.apply(&all_samples_inputs[k]));
+            self.train_sample(
+                augmented.as_deref().unwrap_or(&all_samples_inputs[k]),
+                &all_samples_expected_outputs[k],
+                is_classification,
+                alpha,
+            );
 
-            self.propagate(inputs_k, is_classification);
-
-            // Output layer deltas
-            for j in 1..=self.d[self.L] {
-                let mut delta = self.X[self.L][j] - expected_outputs_k[j - 1];
-                if is_classification {
-                    delta *= 1.0 - self.X[self.L][j].powi(2);
-                }
-                self.deltas[self.L][j] = delta;
+            if let Some(monitor) = monitor.filter(|m| m.is_due(it, num_iter)) {
+                monitor.record(
+                    &mut history,
+                    it + 1,
+                    all_samples_inputs,
+                    all_samples_expected_outputs,
+                    |x| self.predict(x, is_classification),
+                );
             }
-
-            // Hidden layers
-            if self.L >= 2 {
-                for l in (2..=self.L).rev() {
-                    for i in 1..=self.d[l - 1] {
-                        let mut total = 0.0;
-                        for j in 1..=self.d[l] {
-                            total += self.W[l][i][j] * self.deltas[l][j];
-                        }
-                        total *= 1.0 - self.X[l - 1][i].powi(2);
-                        self.deltas[l - 1][i] = total;
-                    }
+            if (it + 1) % (num_iter / 10).max(1) == 0 {
+                match history.last("train_loss") {
+                    Some(loss) => println!("Iteration {}/{} - loss {:.6}", it + 1, num_iter, loss),
+                    None => println!("Iteration {}/{}", it + 1, num_iter),
                 }
             }
+        }
+        history
+    }
 
-            // Update weights
-            for l in 1..=self.L {
-                for i in 0..=self.d[l - 1] {
-                    for j in 1..=self.d[l] {
-                        self.W[l][i][j] -= alpha * self.X[l - 1][i] * self.deltas[l][j];
-                    }
-                }
-            }
+    /// One stochastic gradient step on a single sample (what `train` does at each
+    /// iteration), for training loops driven from outside
+    pub fn train_sample(
+        &mut self,
+        inputs_k: &[f64],
+        expected_outputs_k: &[f64],
+        is_classification: bool,
+        alpha: f64,
+    ) {
+        let outputs = self.forward(inputs_k, is_classification);
+        // Gradient of 0.5 * ||outputs - expected||²
+        let output_grad: Vec<f64> = outputs
+            .iter()
+            .zip(expected_outputs_k.iter())
+            .map(|(o, e)| o - e)
+            .collect();
 
-            if (it + 1) % (num_iter / 10).max(1) == 0 {
-                println!("Iteration {}/{}", it + 1, num_iter);
-            }
-        }
+        let mut gradients = self.gradients.take().unwrap_or_else(|| self.zero_gradients());
+        self.backward_into(&output_grad, &mut gradients);
+        self.apply_gradients(&gradients, alpha);
+        self.gradients = Some(gradients);
     }
 }
@@ -0,0 +1,310 @@
+/// How the principal axes are computed
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PcaSolver {
+    /// Eigen-decomposition (Jacobi) of the d x d covariance matrix
+    Covariance,
+    /// SVD (one-sided Jacobi) of the centred n x d data matrix, more accurate
+    Svd,
+}
+
+/// How many components are kept
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum NumComponents {
+    /// Exactly this many components (at most the input dimension)
+    Count(usize),
+    /// The smallest number of components explaining at least this fraction of the variance (0..=1)
+    VarianceThreshold(f64),
+}
+
+/// Sweeps limit of the Jacobi iterations
+const MAX_SWEEPS: usize = 100;
+
+/// Principal Component Analysis.
+///
+/// `transform` projects a sample on the principal axes (optionally whitened to unit variance),
+/// `inverse_transform` maps a projection back to the input space.
+pub struct PCA {
+    solver: PcaSolver,
+    num_components: NumComponents,
+    whiten: bool,
+    mean: Vec<f64>,
+    /// components[k] = k-th principal axis (unit vector of size d)
+    components: Vec<Vec<f64>>,
+    /// Variance along each kept axis
+    explained_variance: Vec<f64>,
+    /// Explained variance divided by the total variance of the data
+    explained_variance_ratio: Vec<f64>,
+}
+
+impl PCA {
+    pub fn new(num_components: NumComponents, solver: PcaSolver, whiten: bool) -> Self {
+        if let NumComponents::VarianceThreshold(t) = num_components {
+            assert!((0.0..=1.0).contains(&t), "Variance threshold must be in [0, 1]");
+        }
+        PCA {
+            solver,
+            num_components,
+            whiten,
+            mean: Vec::new(),
+            components: Vec::new(),
+            explained_variance: Vec::new(),
+            explained_variance_ratio: Vec::new(),
+        }
+    }
+
+    /// Compute the mean and the principal axes of `inputs`
+    pub fn fit(&mut self, inputs: &[Vec<f64>]) {
+        assert!(inputs.len() >= 2, "Need at least two samples");
+        let n = inputs.len();
+        let d = inputs[0].len();
+
+        self.mean = vec![0.0; d];
+        for x in inputs {
+            assert_eq!(x.len(), d, "Samples must have the same size");
+            for (m, &v) in self.mean.iter_mut().zip(x.iter()) {
+                *m += v / n as f64;
+            }
+        }
+        let centred: Vec<Vec<f64>> = inputs
+            .iter()
+            .map(|x| x.iter().zip(self.mean.iter()).map(|(v, m)| v - m).collect())
+            .collect();
+
+        // (variance, axis) for every direction
+        let mut axes: Vec<(f64, Vec<f64>)> = match self.solver {
+            PcaSolver::Covariance => {
+                let mut cov = vec![vec![0.0; d]; d];
+                for x in &centred {
+                    for (row, &x_i) in cov.iter_mut().zip(x.iter()) {
+                        for (c, &x_j) in row.iter_mut().zip(x.iter()) {
+                            *c += x_i * x_j / (n - 1) as f64;
+                        }
+                    }
+                }
+                let (values, vectors) = symmetric_eigen(cov);
+                values.into_iter().map(|v| v.max(0.0)).zip(vectors).collect()
+            }
+            PcaSolver::Svd => {
+                let (singular, vectors) = right_singular_vectors(centred);
+                singular
+                    .into_iter()
+                    .map(|s| s * s / (n - 1) as f64)
+                    .zip(vectors)
+                    .collect()
+            }
+        };
+        axes.sort_by(|a, b| b.0.total_cmp(&a.0));
+
+        let total: f64 = axes.iter().map(|a| a.0).sum();
+        let keep = match self.num_components {
+            NumComponents::Count(k) => k.min(d),
+            NumComponents::VarianceThreshold(t) => {
+                let mut cumulated = 0.0;
+                let mut k = 0;
+                while k < d && (total == 0.0 || cumulated / total < t) {
+                    cumulated += axes[k].0;
+                    k += 1;
+                }
+                k.max(1)
+            }
+        };
+
+        self.components.clear();
+        self.explained_variance.clear();
+        self.explained_variance_ratio.clear();
+        for (variance, mut axis) in axes.into_iter().take(keep) {
+            // Deterministic sign: largest coordinate positive
+            let mut largest = 0;
+            for (i, v) in axis.iter().enumerate() {
+                if v.abs() > axis[largest].abs() {
+                    largest = i;
+                }
+            }
+            if axis[largest] < 0.0 {
+                for v in axis.iter_mut() {
+                    *v = -*v;
+                }
+            }
+            self.components.push(axis);
+            self.explained_variance.push(variance);
+            self.explained_variance_ratio
+                .push(if total > 0.0 { variance / total } else { 0.0 });
+        }
+    }
+
+    /// Coordinates of `input` on the principal axes
+    pub fn transform(&self, input: &[f64]) -> Vec<f64> {
+        assert!(!self.components.is_empty(), "PCA must be fitted first");
+        assert_eq!(input.len(), self.mean.len(), "Input size must match the fitted data");
+        self.components
+            .iter()
+            .zip(self.explained_variance.iter())
+            .map(|(axis, &variance)| {
+                let mut z = 0.0;
+                for ((&x, &m), &a) in input.iter().zip(self.mean.iter()).zip(axis.iter()) {
+                    z += (x - m) * a;
+                }
+                if self.whiten && variance > 0.0 {
+                    z / variance.sqrt()
+                } else {
+                    z
+                }
+            })
+            .collect()
+    }
+
+    /// Back to the input space (exact only if every component is kept)
+    pub fn inverse_transform(&self, projected: &[f64]) -> Vec<f64> {
+        assert_eq!(projected.len(), self.components.len(), "Size must match the number of components");
+        let mut x = self.mean.clone();
+        for ((&z, axis), &variance) in projected
+            .iter()
+            .zip(self.components.iter())
+            .zip(self.explained_variance.iter())
+        {
+            let z = if self.whiten { z * variance.sqrt() } else { z };
+            for (x_i, &a) in x.iter_mut().zip(axis.iter()) {
+                *x_i += z * a;
+            }
+        }
+        x
+    }
+
+    pub fn components(&self) -> &[Vec<f64>] {
+        &self.components
+    }
+
+    pub fn mean(&self) -> &[f64] {
+        &self.mean
+    }
+
+    pub fn explained_variance(&self) -> &[f64] {
+        &self.explained_variance
+    }
+
+    pub fn explained_variance_ratio(&self) -> &[f64] {
+        &self.explained_variance_ratio
+    }
+
+    pub fn num_components(&self) -> usize {
+        self.components.len()
+    }
+}
+
+/// Cyclic Jacobi eigenvalue algorithm for a symmetric matrix.
+/// Returns the eigenvalues and the matching unit eigenvectors (unsorted).
+#[allow(clippy::needless_range_loop)]
+fn symmetric_eigen(mut a: Vec<Vec<f64>>) -> (Vec<f64>, Vec<Vec<f64>>) {
+    let d = a.len();
+    // v[i][k] = i-th coordinate of the k-th eigenvector
+    let mut v = vec![vec![0.0; d]; d];
+    for (i, row) in v.iter_mut().enumerate() {
+        row[i] = 1.0;
+    }
+
+    for _ in 0..MAX_SWEEPS {
+        // Stop when the off-diagonal part is negligible relative to the whole matrix
+        let mut off = 0.0;
+        let mut norm = 0.0;
+        for i in 0..d {
+            for j in 0..d {
+                norm += a[i][j] * a[i][j];
+                if j > i {
+                    off += a[i][j] * a[i][j];
+                }
+            }
+        }
+        if off <= 1e-24 * norm {
+            break;
+        }
+
+        for p in 0..d {
+            for q in (p + 1)..d {
+                if a[p][q].abs() < 1e-300 {
+                    continue;
+                }
+                let theta = (a[q][q] - a[p][p]) / (2.0 * a[p][q]);
+                let t = theta.signum() / (theta.abs() + (theta * theta + 1.0).sqrt());
+                let t = if theta == 0.0 { 1.0 } else { t };
+                let c = 1.0 / (t * t + 1.0).sqrt();
+                let s = t * c;
+
+                for k in 0..d {
+                    let akp = a[k][p];
+                    let akq = a[k][q];
+                    a[k][p] = c * akp - s * akq;
+                    a[k][q] = s * akp + c * akq;
+                }
+                for k in 0..d {
+                    let apk = a[p][k];
+                    let aqk = a[q][k];
+                    a[p][k] = c * apk - s * aqk;
+                    a[q][k] = s * apk + c * aqk;
+                }
+                for row in v.iter_mut() {
+                    let vkp = row[p];
+                    let vkq = row[q];
+                    row[p] = c * vkp - s * vkq;
+                    row[q] = s * vkp + c * vkq;
+                }
+            }
+        }
+    }
+
+    let values = (0..d).map(|i| a[i][i]).collect();
+    let vectors = (0..d).map(|k| v.iter().map(|row| row[k]).collect()).collect();
+    (values, vectors)
+}
+
+/// One-sided Jacobi SVD of an n x d matrix (rows = samples).
+/// Returns the singular values and the matching right singular vectors (unsorted).
+fn right_singular_vectors(mut a: Vec<Vec<f64>>) -> (Vec<f64>, Vec<Vec<f64>>) {
+    let d = a[0].len();
+    let mut v = vec![vec![0.0; d]; d];
+    for (i, row) in v.iter_mut().enumerate() {
+        row[i] = 1.0;
+    }
+
+    for _ in 0..MAX_SWEEPS {
+        let mut rotated = false;
+        for p in 0..d {
+            for q in (p + 1)..d {
+                // Orthogonalise columns p and q
+                let mut alpha = 0.0;
+                let mut beta = 0.0;
+                let mut gamma = 0.0;
+                for row in a.iter() {
+                    alpha += row[p] * row[p];
+                    beta += row[q] * row[q];
+                    gamma += row[p] * row[q];
+                }
+                if gamma.abs() <= 1e-15 * (alpha * beta).sqrt() || gamma == 0.0 {
+                    continue;
+                }
+                rotated = true;
+
+                let zeta = (beta - alpha) / (2.0 * gamma);
+                let t = zeta.signum() / (zeta.abs() + (1.0 + zeta * zeta).sqrt());
+                let t = if zeta == 0.0 { 1.0 } else { t };
+                let c = 1.0 / (1.0 + t * t).sqrt();
+                let s = c * t;
+
+                for row in a.iter_mut().chain(v.iter_mut()) {
+                    let xp = row[p];
+                    let xq = row[q];
+                    row[p] = c * xp - s * xq;
+                    row[q] = s * xp + c * xq;
+                }
+            }
+        }
+        if !rotated {
+            break;
+        }
+    }
+
+    let singular = (0..d)
+        .map(|k| a.iter().map(|row| row[k] * row[k]).sum::<f64>().sqrt())
+        .collect();
+    let vectors = (0..d).map(|k| v.iter().map(|row| row[k]).collect()).collect();
+    (singular, vectors)
+}
@@ -0,0 +1,745 @@
+use crate::encoding::argmax;
+use crate::split::class_labels;
+use image::{Rgb, RgbImage};
+use std::fmt;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::Path;
+
+/// Colours of the classes (points), regions use a lighter shade
+const PALETTE: [(u8, u8, u8); 8] = [
+    (31, 119, 180),
+    (255, 127, 14),
+    (44, 160, 44),
+    (214, 39, 40),
+    (148, 103, 189),
+    (140, 86, 75),
+    (227, 119, 194),
+    (127, 127, 127),
+];
+const BOUNDARY: (u8, u8, u8) = (30, 30, 30);
+
+/// Line segment between two points in data coordinates
+type Segment = ((f64, f64), (f64, f64));
+
+#[derive(Clone, Copy, Debug)]
+pub struct PlotOptions {
+    /// Image size in pixels
+    pub width: u32,
+    pub height: u32,
+    /// Number of grid cells along each axis the model is evaluated on
+    pub resolution: usize,
+    /// Plotted area, None = range of the training points plus 10 %
+    pub x_range: Option<(f64, f64)>,
+    pub y_range: Option<(f64, f64)>,
+    /// Decision threshold of single-output models (0 for ±1 outputs, 0.5 for 0 / 1 outputs)
+    pub threshold: f64,
+    /// Radius of the training points in pixels
+    pub point_radius: f64,
+}
+
+impl Default for PlotOptions {
+    fn default() -> Self {
+        PlotOptions {
+            width: 600,
+            height: 600,
+            resolution: 200,
+            x_range: None,
+            y_range: None,
+            threshold: 0.0,
+            point_radius: 4.0,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum PlotError {
+    Io(std::io::Error),
+    Image(image::ImageError),
+    /// The extension of the output file is neither .png nor .svg
+    UnsupportedFormat(String),
+    /// A chart was asked for a series the history does not record
+    UnknownSeries(String),
+}
+
+impl fmt::Display for PlotError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PlotError::Io(e) => write!(f, "I/O error: {}", e),
+            PlotError::Image(e) => write!(f, "image error: {}", e),
+            PlotError::UnsupportedFormat(ext) => write!(f, "unsupported image format \"{}\" (use .png or .svg)", ext),
+            PlotError::UnknownSeries(name) => write!(f, "unknown series \"{}\"", name),
+        }
+    }
+}
+
+impl std::error::Error for PlotError {}
+
+impl From<std::io::Error> for PlotError {
+    fn from(e: std::io::Error) -> Self {
+        PlotError::Io(e)
+    }
+}
+
+impl From<image::ImageError> for PlotError {
+    fn from(e: image::ImageError) -> Self {
+        PlotError::Image(e)
+    }
+}
+
+/// Model evaluated on the nodes of a regular grid
+struct Grid {
+    x_range: (f64, f64),
+    y_range: (f64, f64),
+    /// Nodes per axis (resolution + 1)
+    size: usize,
+    /// Decision value of single-output models, minus the threshold
+    values: Vec<f64>,
+    classes: Vec<usize>,
+}
+
+impl Grid {
+    fn node(&self, i: usize, j: usize) -> (f64, f64) {
+        let step_x = (self.x_range.1 - self.x_range.0) / (self.size - 1) as f64;
+        let step_y = (self.y_range.1 - self.y_range.0) / (self.size - 1) as f64;
+        (self.x_range.0 + i as f64 * step_x, self.y_range.0 + j as f64 * step_y)
+    }
+
+    /// Class of the node nearest to (x, y)
+    fn class_at(&self, x: f64, y: f64) -> usize {
+        let fx = (x - self.x_range.0) / (self.x_range.1 - self.x_range.0);
+        let fy = (y - self.y_range.0) / (self.y_range.1 - self.y_range.0);
+        let i = (fx * (self.size - 1) as f64).round().clamp(0.0, (self.size - 1) as f64) as usize;
+        let j = (fy * (self.size - 1) as f64).round().clamp(0.0, (self.size - 1) as f64) as usize;
+        self.classes[j * self.size + i]
+    }
+
+    /// Decision boundary as segments in data coordinates: marching squares on the decision
+    /// value for single-output models, edges between cells of different classes otherwise
+    fn boundary(&self, binary: bool) -> Vec<Segment> {
+        let n = self.size;
+        let mut segments = Vec::new();
+        for j in 0..n - 1 {
+            for i in 0..n - 1 {
+                let corners = [(i, j), (i + 1, j), (i + 1, j + 1), (i, j + 1)];
+                if binary {
+                    let mut crossings = Vec::new();
+                    for e in 0..4 {
+                        let (a, b) = (corners[e], corners[(e + 1) % 4]);
+                        let va = self.values[a.1 * n + a.0];
+                        let vb = self.values[b.1 * n + b.0];
+                        if (va > 0.0) != (vb > 0.0) {
+                            let t = va / (va - vb);
+                            let (xa, ya) = self.node(a.0, a.1);
+                            let (xb, yb) = self.node(b.0, b.1);
+                            crossings.push((xa + t * (xb - xa), ya + t * (yb - ya)));
+                        }
+                    }
+                    for pair in crossings.chunks(2) {
+                        if pair.len() == 2 {
+                            segments.push((pair[0], pair[1]));
+                        }
+                    }
+                } else {
+                    // Half-way between nodes (i, j) -> (i + 1, j) and (i, j) -> (i, j + 1)
+                    let (x0, y0) = self.node(i, j);
+                    let (x1, y1) = self.node(i + 1, j + 1);
+                    let (xm, ym) = ((x0 + x1) / 2.0, (y0 + y1) / 2.0);
+                    let c = self.classes[j * n + i];
+                    if c != self.classes[j * n + i + 1] {
+                        segments.push(((xm, y0 - (y1 - y0) / 2.0), (xm, ym)));
+                    }
+                    if c != self.classes[(j + 1) * n + i] {
+                        segments.push(((x0 - (x1 - x0) / 2.0, ym), (xm, ym)));
+                    }
+                }
+            }
+        }
+        segments
+    }
+}
+
+/// Evaluate a trained 2-D classifier on a grid and write its decision regions, the decision
+/// boundary and the training points (coloured by true label) to a .png or .svg file.
+///
+/// `model` returns the raw output of the classifier: one value (class = value > threshold)
+/// or one value per class (class = argmax), e.g. `|x| mlp.predict(x, true)`.
+pub fn plot_decision_boundary<P, F>(
+    path: P,
+    inputs: &[Vec<f64>],
+    outputs: &[Vec<f64>],
+    options: &PlotOptions,
+    mut model: F,
+) -> Result<(), PlotError>
+where
+    P: AsRef<Path>,
+    F: FnMut(&[f64]) -> Vec<f64>,
+{
+    assert_eq!(inputs.len(), outputs.len());
+    assert!(inputs.iter().all(|x| x.len() == 2), "Only 2-D inputs can be plotted");
+    assert!(options.resolution >= 2, "Resolution must be at least 2");
+
+    let path = path.as_ref();
+    let extension = path
+        .extension()
+        .map_or(String::new(), |e| e.to_string_lossy().to_lowercase());
+    if extension != "png" && extension != "svg" {
+        return Err(PlotError::UnsupportedFormat(extension));
+    }
+
+    let padded = |values: Vec<f64>| {
+        let min = values.iter().cloned().fold(f64::INFINITY, f64::min);
+        let max = values.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+        let pad = if max > min { (max - min) * 0.1 } else { 1.0 };
+        (min - pad, max + pad)
+    };
+    let x_range = options
+        .x_range
+        .unwrap_or_else(|| padded(inputs.iter().map(|x| x[0]).collect()));
+    let y_range = options
+        .y_range
+        .unwrap_or_else(|| padded(inputs.iter().map(|x| x[1]).collect()));
+
+    let mut grid = Grid {
+        x_range,
+        y_range,
+        size: options.resolution + 1,
+        values: Vec::new(),
+        classes: Vec::new(),
+    };
+    let mut binary = true;
+    for j in 0..grid.size {
+        for i in 0..grid.size {
+            let (x, y) = grid.node(i, j);
+            let output = model(&[x, y]);
+            binary = output.len() == 1;
+            if binary {
+                let v = output[0] - options.threshold;
+                grid.values.push(v);
+                grid.classes.push((v > 0.0) as usize);
+            } else {
+                grid.classes.push(argmax(&output));
+            }
+        }
+    }
+
+    let labels = if binary {
+        outputs
+            .iter()
+            .map(|y| (y[0] > options.threshold) as usize)
+            .collect()
+    } else {
+        class_labels(outputs)
+    };
+    let segments = grid.boundary(binary);
+
+    if extension == "png" {
+        render_png(path, &grid, &segments, inputs, &labels, options)
+    } else {
+        render_svg(path, &grid, &segments, inputs, &labels, options)
+    }
+}
+
+fn colour(class: usize) -> (u8, u8, u8) {
+    PALETTE[class % PALETTE.len()]
+}
+
+/// Class colour mixed with white
+fn light(class: usize) -> (u8, u8, u8) {
+    let (r, g, b) = colour(class);
+    let mix = |c: u8| (c as f64 * 0.35 + 255.0 * 0.65) as u8;
+    (mix(r), mix(g), mix(b))
+}
+
+fn to_pixel(grid: &Grid, options: &PlotOptions, (x, y): (f64, f64)) -> (f64, f64) {
+    (
+        (x - grid.x_range.0) / (grid.x_range.1 - grid.x_range.0) * options.width as f64,
+        (1.0 - (y - grid.y_range.0) / (grid.y_range.1 - grid.y_range.0)) * options.height as f64,
+    )
+}
+
+/// Set a pixel, ignoring positions outside the image
+fn put_pixel(img: &mut RgbImage, x: f64, y: f64, (r, g, b): (u8, u8, u8)) {
+    if x >= 0.0 && y >= 0.0 && (x as u32) < img.width() && (y as u32) < img.height() {
+        img.put_pixel(x as u32, y as u32, Rgb([r, g, b]));
+    }
+}
+
+/// Line about 2 pixels thick between two pixel positions; `dash` = (length of the
+/// dashes and gaps, distance already drawn along the polyline) for dashed lines
+fn draw_line(img: &mut RgbImage, (ax, ay): (f64, f64), (bx, by): (f64, f64), colour: (u8, u8, u8), dash: Option<(f64, f64)>) {
+    let length = (bx - ax).hypot(by - ay);
+    let steps = ((bx - ax).abs().max((by - ay).abs()) * 2.0).ceil().max(1.0) as usize;
+    for s in 0..=steps {
+        let t = s as f64 / steps as f64;
+        if let Some((period, offset)) = dash {
+            if ((offset + t * length) / period) as usize % 2 == 1 {
+                continue;
+            }
+        }
+        let (x, y) = (ax + t * (bx - ax), ay + t * (by - ay));
+        for (dx, dy) in [(0.0, 0.0), (1.0, 0.0), (0.0, 1.0), (1.0, 1.0)] {
+            put_pixel(img, x + dx - 0.5, y + dy - 0.5, colour);
+        }
+    }
+}
+
+fn render_png(
+    path: &Path,
+    grid: &Grid,
+    segments: &[Segment],
+    inputs: &[Vec<f64>],
+    labels: &[usize],
+    options: &PlotOptions,
+) -> Result<(), PlotError> {
+    let (w, h) = (options.width, options.height);
+    let mut img = RgbImage::new(w, h);
+    for (px, py, pixel) in img.enumerate_pixels_mut() {
+        let x = grid.x_range.0 + (px as f64 + 0.5) / w as f64 * (grid.x_range.1 - grid.x_range.0);
+        let y = grid.y_range.1 - (py as f64 + 0.5) / h as f64 * (grid.y_range.1 - grid.y_range.0);
+        let (r, g, b) = light(grid.class_at(x, y));
+        *pixel = Rgb([r, g, b]);
+    }
+
+    // Boundary, about 2 pixels thick
+    for &(a, b) in segments {
+        draw_line(&mut img, to_pixel(grid, options, a), to_pixel(grid, options, b), BOUNDARY, None);
+    }
+
+    // Training points with a dark outline
+    let radius = options.point_radius;
+    for (x, &label) in inputs.iter().zip(labels.iter()) {
+        let (cx, cy) = to_pixel(grid, options, (x[0], x[1]));
+        let r = radius.ceil() as i64 + 1;
+        for dy in -r..=r {
+            for dx in -r..=r {
+                let d = ((dx * dx + dy * dy) as f64).sqrt();
+                if d <= radius {
+                    put_pixel(&mut img, cx + dx as f64, cy + dy as f64, colour(label));
+                } else if d <= radius + 1.0 {
+                    put_pixel(&mut img, cx + dx as f64, cy + dy as f64, BOUNDARY);
+                }
+            }
+        }
+    }
+
+    img.save(path)?;
+    Ok(())
+}
+
+fn render_svg(
+    path: &Path,
+    grid: &Grid,
+    segments: &[Segment],
+    inputs: &[Vec<f64>],
+    labels: &[usize],
+    options: &PlotOptions,
+) -> Result<(), PlotError> {
+    let (w, h) = (options.width as f64, options.height as f64);
+    let hex = |(r, g, b): (u8, u8, u8)| format!("#{:02x}{:02x}{:02x}", r, g, b);
+    let mut svg = String::new();
+    let _ = writeln!(
+        svg,
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" viewBox=\"0 0 {} {}\">",
+        w, h, w, h
+    );
+
+    // Regions: one rectangle per run of cells of the same class on a row
+    let n = grid.size;
+    let cell_w = w / (n - 1) as f64;
+    let cell_h = h / (n - 1) as f64;
+    let _ = writeln!(svg, "<g shape-rendering=\"crispEdges\">");
+    for j in 0..n {
+        let mut i = 0;
+        while i < n {
+            let class = grid.classes[j * n + i];
+            let start = i;
+            while i < n && grid.classes[j * n + i] == class {
+                i += 1;
+            }
+            let (x, y) = to_pixel(grid, options, grid.node(start, j));
+            let _ = writeln!(
+                svg,
+                "<rect x=\"{:.2}\" y=\"{:.2}\" width=\"{:.2}\" height=\"{:.2}\" fill=\"{}\"/>",
+                (x - cell_w / 2.0).max(0.0),
+                (y - cell_h / 2.0).max(0.0),
+                (i - start) as f64 * cell_w,
+                cell_h,
+                hex(light(class))
+            );
+        }
+    }
+    let _ = writeln!(svg, "</g>");
+
+    let _ = writeln!(svg, "<g stroke=\"{}\" stroke-width=\"2\" stroke-linecap=\"round\">", hex(BOUNDARY));
+    for &(a, b) in segments {
+        let (ax, ay) = to_pixel(grid, options, a);
+        let (bx, by) = to_pixel(grid, options, b);
+        let _ = writeln!(svg, "<line x1=\"{:.2}\" y1=\"{:.2}\" x2=\"{:.2}\" y2=\"{:.2}\"/>", ax, ay, bx, by);
+    }
+    let _ = writeln!(svg, "</g>");
+
+    let _ = writeln!(svg, "<g stroke=\"{}\" stroke-width=\"1\">", hex(BOUNDARY));
+    for (x, &label) in inputs.iter().zip(labels.iter()) {
+        let (cx, cy) = to_pixel(grid, options, (x[0], x[1]));
+        let _ = writeln!(
+            svg,
+            "<circle cx=\"{:.2}\" cy=\"{:.2}\" r=\"{}\" fill=\"{}\"/>",
+            cx,
+            cy,
+            options.point_radius,
+            hex(colour(label))
+        );
+    }
+    let _ = writeln!(svg, "</g>");
+    let _ = writeln!(svg, "</svg>");
+
+    fs::write(path, svg)?;
+    Ok(())
+}
+
+#[derive(Clone, Debug)]
+pub struct ChartOptions {
+    /// Image size in pixels
+    pub width: u32,
+    pub height: u32,
+    /// Logarithmic y axis (values <= 0 are left out)
+    pub log_scale: bool,
+    /// Title and axis label, drawn in SVG charts only (PNG charts carry no text)
+    pub title: String,
+    pub x_label: String,
+}
+
+impl Default for ChartOptions {
+    fn default() -> Self {
+        ChartOptions {
+            width: 800,
+            height: 500,
+            log_scale: false,
+            title: String::new(),
+            x_label: "iteration".to_string(),
+        }
+    }
+}
+
+const GRID_LINES: (u8, u8, u8) = (225, 225, 225);
+/// Margins of the plotting area: left, right, top, bottom
+const MARGINS: (f64, f64, f64, f64) = (70.0, 20.0, 40.0, 50.0);
+/// Length of the dashes and gaps of validation series
+const DASH: f64 = 6.0;
+
+/// Chart axes: data ranges and the pixel area they map to
+struct Axes {
+    x_range: (f64, f64),
+    /// In log10 units for log-scale charts
+    y_range: (f64, f64),
+    log_scale: bool,
+    left: f64,
+    right: f64,
+    top: f64,
+    bottom: f64,
+}
+
+impl Axes {
+    fn to_pixel(&self, x: f64, y: f64) -> (f64, f64) {
+        let y = if self.log_scale { y.log10() } else { y };
+        (
+            self.left + (x - self.x_range.0) / (self.x_range.1 - self.x_range.0) * (self.right - self.left),
+            self.bottom - (y - self.y_range.0) / (self.y_range.1 - self.y_range.0) * (self.bottom - self.top),
+        )
+    }
+
+    fn plottable(&self, y: f64) -> bool {
+        y.is_finite() && (!self.log_scale || y > 0.0)
+    }
+
+    /// Tick values of the y axis (data units)
+    fn y_ticks(&self) -> Vec<f64> {
+        if self.log_scale {
+            let (low, high) = (self.y_range.0.ceil() as i32, self.y_range.1.floor() as i32);
+            if high > low {
+                return (low..=high).map(|e| 10f64.powi(e)).collect();
+            }
+            // Less than two powers of 10 in range: round values in between
+            let range = (10f64.powf(self.y_range.0), 10f64.powf(self.y_range.1));
+            nice_ticks(range).into_iter().filter(|&t| t > 0.0).collect()
+        } else {
+            nice_ticks(self.y_range)
+        }
+    }
+}
+
+/// About 5 round values (1, 2 or 5 times a power of 10 apart) inside `range`
+fn nice_ticks((low, high): (f64, f64)) -> Vec<f64> {
+    let raw = (high - low) / 5.0;
+    let magnitude = 10f64.powf(raw.log10().floor());
+    let step = [1.0, 2.0, 5.0, 10.0]
+        .iter()
+        .map(|m| m * magnitude)
+        .find(|&s| s >= raw)
+        .unwrap_or(10.0 * magnitude);
+    let mut ticks = Vec::new();
+    let mut t = (low / step).ceil() * step;
+    while t <= high + step * 1e-9 {
+        ticks.push(if t.abs() < step * 1e-9 { 0.0 } else { t });
+        t += step;
+    }
+    ticks
+}
+
+/// Series name without its "train_" / "validation_" prefix, and whether it is a validation series
+fn split_series_name(name: &str) -> (&str, bool) {
+    if let Some(base) = name.strip_prefix("validation_") {
+        (base, true)
+    } else {
+        (name.strip_prefix("train_").unwrap_or(name), false)
+    }
+}
+
+/// Colour and dashing of each series: a colour per quantity, validation series dashed
+fn series_styles(series: &[(&str, &[f64])]) -> Vec<((u8, u8, u8), bool)> {
+    let mut bases: Vec<&str> = Vec::new();
+    series
+        .iter()
+        .map(|(name, _)| {
+            let (base, validation) = split_series_name(name);
+            let index = bases.iter().position(|&b| b == base).unwrap_or_else(|| {
+                bases.push(base);
+                bases.len() - 1
+            });
+            (colour(index), validation)
+        })
+        .collect()
+}
+
+/// Line chart of several series sharing the same x values to a .png or .svg file.
+///
+/// Series are coloured by quantity: "train_loss" and "validation_loss" share a colour,
+/// the validation one is dashed. Missing values (NaN) break the line.
+pub fn plot_lines<P: AsRef<Path>>(
+    path: P,
+    x: &[f64],
+    series: &[(&str, &[f64])],
+    options: &ChartOptions,
+) -> Result<(), PlotError> {
+    assert!(series.iter().all(|(_, y)| y.len() == x.len()), "Need one value per x in every series");
+
+    let path = path.as_ref();
+    let extension = path
+        .extension()
+        .map_or(String::new(), |e| e.to_string_lossy().to_lowercase());
+    if extension != "png" && extension != "svg" {
+        return Err(PlotError::UnsupportedFormat(extension));
+    }
+
+    let mut axes = Axes {
+        x_range: (0.0, 1.0),
+        y_range: (0.0, 1.0),
+        log_scale: options.log_scale,
+        left: MARGINS.0,
+        right: options.width as f64 - MARGINS.1,
+        top: MARGINS.2,
+        bottom: options.height as f64 - MARGINS.3,
+    };
+    let xs = x.iter().cloned().filter(|v| v.is_finite());
+    let (x_min, x_max) = xs.fold((f64::INFINITY, f64::NEG_INFINITY), |(a, b), v| (a.min(v), b.max(v)));
+    if x_min < x_max {
+        axes.x_range = (x_min, x_max);
+    } else if x_min.is_finite() {
+        axes.x_range = (x_min - 1.0, x_min + 1.0);
+    }
+    let ys = series
+        .iter()
+        .flat_map(|(_, y)| y.iter().cloned())
+        .filter(|&v| axes.plottable(v))
+        .map(|v| if options.log_scale { v.log10() } else { v });
+    let (y_min, y_max) = ys.fold((f64::INFINITY, f64::NEG_INFINITY), |(a, b), v| (a.min(v), b.max(v)));
+    if y_min.is_finite() {
+        let pad = if y_max > y_min { (y_max - y_min) * 0.05 } else { 0.5 };
+        axes.y_range = (y_min - pad, y_max + pad);
+    }
+
+    // Continuous runs of plottable points of each series, in pixels
+    let lines: Vec<Vec<Vec<(f64, f64)>>> = series
+        .iter()
+        .map(|(_, y)| {
+            let mut runs = vec![Vec::new()];
+            for (&xi, &yi) in x.iter().zip(y.iter()) {
+                if axes.plottable(yi) && xi.is_finite() {
+                    runs.last_mut().unwrap().push(axes.to_pixel(xi, yi));
+                } else if !runs.last().unwrap().is_empty() {
+                    runs.push(Vec::new());
+                }
+            }
+            runs.retain(|r| !r.is_empty());
+            runs
+        })
+        .collect();
+    let styles = series_styles(series);
+
+    if extension == "png" {
+        render_chart_png(path, &axes, &lines, &styles, options)
+    } else {
+        render_chart_svg(path, &axes, series, &lines, &styles, options)
+    }
+}
+
+fn render_chart_png(
+    path: &Path,
+    axes: &Axes,
+    lines: &[Vec<Vec<(f64, f64)>>],
+    styles: &[((u8, u8, u8), bool)],
+    options: &ChartOptions,
+) -> Result<(), PlotError> {
+    let mut img = RgbImage::from_pixel(options.width, options.height, Rgb([255, 255, 255]));
+    for y in axes.y_ticks() {
+        let (_, py) = axes.to_pixel(axes.x_range.0, y);
+        draw_line(&mut img, (axes.left, py), (axes.right, py), GRID_LINES, None);
+    }
+    let corners = [
+        (axes.left, axes.top),
+        (axes.right, axes.top),
+        (axes.right, axes.bottom),
+        (axes.left, axes.bottom),
+    ];
+    for k in 0..4 {
+        draw_line(&mut img, corners[k], corners[(k + 1) % 4], BOUNDARY, None);
+    }
+
+    for (runs, &(colour, dashed)) in lines.iter().zip(styles.iter()) {
+        for run in runs {
+            let mut drawn = 0.0;
+            for pair in run.windows(2) {
+                draw_line(&mut img, pair[0], pair[1], colour, dashed.then_some((DASH, drawn)));
+                drawn += (pair[1].0 - pair[0].0).hypot(pair[1].1 - pair[0].1);
+            }
+            if run.len() == 1 {
+                draw_line(&mut img, run[0], run[0], colour, None);
+            }
+        }
+    }
+
+    img.save(path)?;
+    Ok(())
+}
+
+fn render_chart_svg(
+    path: &Path,
+    axes: &Axes,
+    series: &[(&str, &[f64])],
+    lines: &[Vec<Vec<(f64, f64)>>],
+    styles: &[((u8, u8, u8), bool)],
+    options: &ChartOptions,
+) -> Result<(), PlotError> {
+    let (w, h) = (options.width as f64, options.height as f64);
+    let hex = |(r, g, b): (u8, u8, u8)| format!("#{:02x}{:02x}{:02x}", r, g, b);
+    let mut svg = String::new();
+    let _ = writeln!(
+        svg,
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" viewBox=\"0 0 {} {}\" font-family=\"sans-serif\" font-size=\"12\">",
+        w, h, w, h
+    );
+    let _ = writeln!(svg, "<rect width=\"{}\" height=\"{}\" fill=\"white\"/>", w, h);
+
+    // Grid lines and tick labels
+    let _ = writeln!(svg, "<g stroke=\"{}\">", hex(GRID_LINES));
+    let y_ticks = axes.y_ticks();
+    for &y in &y_ticks {
+        let (_, py) = axes.to_pixel(axes.x_range.0, y);
+        let _ = writeln!(svg, "<line x1=\"{:.2}\" y1=\"{:.2}\" x2=\"{:.2}\" y2=\"{:.2}\"/>", axes.left, py, axes.right, py);
+    }
+    let _ = writeln!(svg, "</g>");
+    let _ = writeln!(svg, "<g text-anchor=\"end\">");
+    for &y in &y_ticks {
+        let (_, py) = axes.to_pixel(axes.x_range.0, y);
+        let _ = writeln!(svg, "<text x=\"{:.2}\" y=\"{:.2}\">{}</text>", axes.left - 6.0, py + 4.0, format_tick(y));
+    }
+    let _ = writeln!(svg, "</g>");
+    let _ = writeln!(svg, "<g text-anchor=\"middle\">");
+    for x in nice_ticks(axes.x_range) {
+        let (px, _) = axes.to_pixel(x, 1.0);
+        let _ = writeln!(svg, "<text x=\"{:.2}\" y=\"{:.2}\">{}</text>", px, axes.bottom + 18.0, format_tick(x));
+    }
+    let _ = writeln!(
+        svg,
+        "<text x=\"{:.2}\" y=\"{:.2}\">{}</text>",
+        (axes.left + axes.right) / 2.0,
+        h - 10.0,
+        escape(&options.x_label)
+    );
+    if !options.title.is_empty() {
+        let _ = writeln!(
+            svg,
+            "<text x=\"{:.2}\" y=\"24\" font-size=\"16\">{}</text>",
+            (axes.left + axes.right) / 2.0,
+            escape(&options.title)
+        );
+    }
+    let _ = writeln!(svg, "</g>");
+    let _ = writeln!(
+        svg,
+        "<rect x=\"{:.2}\" y=\"{:.2}\" width=\"{:.2}\" height=\"{:.2}\" fill=\"none\" stroke=\"{}\"/>",
+        axes.left,
+        axes.top,
+        axes.right - axes.left,
+        axes.bottom - axes.top,
+        hex(BOUNDARY)
+    );
+
+    // Series
+    let dash = |dashed: bool| if dashed { format!(" stroke-dasharray=\"{} {}\"", DASH, DASH) } else { String::new() };
+    let _ = writeln!(svg, "<g fill=\"none\" stroke-width=\"2\" stroke-linejoin=\"round\">");
+    for (runs, &(colour, dashed)) in lines.iter().zip(styles.iter()) {
+        for run in runs {
+            let points: Vec<String> = run.iter().map(|(x, y)| format!("{:.2},{:.2}", x, y)).collect();
+            let _ = writeln!(
+                svg,
+                "<polyline points=\"{}\" stroke=\"{}\"{}/>",
+                points.join(" "),
+                hex(colour),
+                dash(dashed)
+            );
+        }
+    }
+    let _ = writeln!(svg, "</g>");
+
+    // Legend in the top right corner
+    let _ = writeln!(svg, "<g>");
+    for (k, ((name, _), &(colour, dashed))) in series.iter().zip(styles.iter()).enumerate() {
+        let y = axes.top + 16.0 + k as f64 * 18.0;
+        let x = axes.right - 170.0;
+        let _ = writeln!(
+            svg,
+            "<line x1=\"{:.2}\" y1=\"{:.2}\" x2=\"{:.2}\" y2=\"{:.2}\" stroke=\"{}\" stroke-width=\"2\"{}/>",
+            x,
+            y - 4.0,
+            x + 30.0,
+            y - 4.0,
+            hex(colour),
+            dash(dashed)
+        );
+        let _ = writeln!(svg, "<text x=\"{:.2}\" y=\"{:.2}\">{}</text>", x + 36.0, y, escape(name));
+    }
+    let _ = writeln!(svg, "</g>");
+    let _ = writeln!(svg, "</svg>");
+
+    fs::write(path, svg)?;
+    Ok(())
+}
+
+/// Short label of a tick value
+fn format_tick(v: f64) -> String {
+    if v.fract() == 0.0 && v.abs() < 1e9 {
+        format!("{}", v as i64)
+    } else if v.abs() < 1e-3 {
+        format!("{:e}", v)
+    } else {
+        // Up to 4 decimals, trailing zeros removed
+        let s = format!("{:.4}", v);
+        s.trim_end_matches('0').trim_end_matches('.').to_string()
+    }
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
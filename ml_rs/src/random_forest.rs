@@ -0,0 +1,152 @@
+use crate::decision_tree::{Criterion, DecisionTree, Targets, TreeParams};
+use crate::encoding::{argmax, encode_classes};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::thread;
+
+/// Random forest: `num_trees` CART trees, each grown on a bootstrap sample
+/// and looking at a random subset of the features at each split.
+///
+/// Same target layout as `DecisionTree`:
+/// - Classification (`Gini` / `Entropy`): probabilities averaged over the trees
+/// - Regression (`Mse`): predictions averaged over the trees
+pub struct RandomForest {
+    num_trees: usize,
+    criterion: Criterion,
+    params: TreeParams,
+    /// Features tried per split. None = sqrt(d) for classification, d / 3 for regression
+    max_features: Option<usize>,
+    seed: Option<u64>,
+    trees: Vec<DecisionTree>,
+    class_outputs: Vec<Vec<f64>>,
+}
+
+impl RandomForest {
+    pub fn new(
+        num_trees: usize,
+        criterion: Criterion,
+        params: TreeParams,
+        max_features: Option<usize>,
+        seed: Option<u64>,
+    ) -> Self {
+        assert!(num_trees >= 1, "Need at least one tree");
+        RandomForest {
+            num_trees,
+            criterion,
+            params,
+            max_features,
+            seed,
+            trees: Vec::new(),
+            class_outputs: Vec::new(),
+        }
+    }
+
+    /// Grow all the trees, in parallel over the available cores
+    pub fn fit(&mut self, inputs: &[Vec<f64>], outputs: &[Vec<f64>]) {
+        assert_eq!(inputs.len(), outputs.len());
+        assert!(!inputs.is_empty(), "Need at least one sample");
+
+        let n = inputs.len();
+        let num_features = inputs[0].len();
+        let max_features = self.max_features.unwrap_or(if self.criterion.is_classification() {
+            (num_features as f64).sqrt().round() as usize
+        } else {
+            num_features / 3
+        });
+
+        // Bootstrap samples and tree seeds are drawn up front,
+        // so the forest only depends on `seed` and not on the thread scheduling
+        let mut rng = match self.seed {
+            Some(s) => StdRng::seed_from_u64(s),
+            None => StdRng::from_entropy(),
+        };
+        let mut jobs = Vec::with_capacity(self.num_trees);
+        for _ in 0..self.num_trees {
+            let samples: Vec<usize> = (0..n).map(|_| rng.gen_range(0..n)).collect();
+            let tree = DecisionTree::with_feature_subsampling(
+                self.criterion,
+                self.params,
+                Some(max_features.max(1)),
+                Some(rng.gen()),
+            );
+            jobs.push((tree, samples));
+        }
+
+        let (labels, class_outputs) = if self.criterion.is_classification() {
+            encode_classes(outputs)
+        } else {
+            (Vec::new(), Vec::new())
+        };
+        let targets = if self.criterion.is_classification() {
+            Targets::Classes {
+                labels: &labels,
+                num_classes: class_outputs.len(),
+            }
+        } else {
+            Targets::Values(outputs)
+        };
+
+        let num_threads = thread::available_parallelism().map_or(1, |p| p.get());
+        let chunk_size = jobs.len().div_ceil(num_threads);
+        thread::scope(|scope| {
+            for chunk in jobs.chunks_mut(chunk_size) {
+                let targets = &targets;
+                scope.spawn(move || {
+                    for (tree, samples) in chunk.iter_mut() {
+                        tree.fit_samples(inputs, targets, samples);
+                    }
+                });
+            }
+        });
+
+        self.trees = jobs.into_iter().map(|(tree, _)| tree).collect();
+        for tree in self.trees.iter_mut() {
+            tree.class_outputs = class_outputs.clone();
+        }
+        self.class_outputs = class_outputs;
+    }
+
+    /// Class probabilities averaged over the trees (classification only)
+    pub fn predict_proba(&self, input: &[f64]) -> Vec<f64> {
+        assert!(self.criterion.is_classification(), "predict_proba needs a classification forest");
+        self.average(|tree| tree.predict_proba(input))
+    }
+
+    /// Predicted target vector: majority class output (classification) or mean prediction (regression)
+    pub fn predict(&self, input: &[f64]) -> Vec<f64> {
+        if self.criterion.is_classification() {
+            self.class_outputs[argmax(&self.predict_proba(input))].clone()
+        } else {
+            self.average(|tree| tree.predict(input))
+        }
+    }
+
+    /// Impurity-based importances averaged over the trees, renormalised to sum to 1
+    /// (trees that never split count as all 0; all 0 if no tree splits)
+    pub fn feature_importances(&self) -> Vec<f64> {
+        let mut importances = self.average(|tree| tree.feature_importances().to_vec());
+        let total: f64 = importances.iter().sum();
+        if total > 0.0 {
+            for v in importances.iter_mut() {
+                *v /= total;
+            }
+        }
+        importances
+    }
+
+    pub fn trees(&self) -> &[DecisionTree] {
+        &self.trees
+    }
+
+    fn average<F: Fn(&DecisionTree) -> Vec<f64>>(&self, f: F) -> Vec<f64> {
+        assert!(!self.trees.is_empty(), "RandomForest must be fitted first");
+        let mut sum = f(&self.trees[0]);
+        for tree in &self.trees[1..] {
+            for (s, v) in sum.iter_mut().zip(f(tree)) {
+                *s += v;
+            }
+        }
+        let count = self.trees.len() as f64;
+        sum.iter().map(|s| s / count).collect()
+    }
+}
@@ -0,0 +1,159 @@
+use crate::csv_loader::{encode_label, load_csv, Column, CsvError, CsvOptions, LabelEncoding};
+use crate::datasets::Dataset;
+use crate::encoding::argmax;
+use crate::linear_perceptron::LinearPerceptron;
+use crate::naive_multi_layer_perceptron::MyMLP;
+use crate::scaling::Scaler;
+use crate::split::class_labels;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// Trained model of any serialisable kind
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub enum Model {
+    /// One perceptron per output (one-vs-rest for several classes)
+    Linear(Vec<LinearPerceptron>),
+    Mlp(MyMLP),
+}
+
+impl Model {
+    /// Raw outputs: tanh for MLP classification, sign for a single linear classifier
+    /// (w · x + b for one-vs-rest, compared by argmax), linear for regression
+    pub fn predict(&mut self, input: &[f64], is_classification: bool) -> Vec<f64> {
+        match self {
+            Model::Linear(perceptrons) if is_classification && perceptrons.len() == 1 => {
+                vec![perceptrons[0].predict_class(input)]
+            }
+            Model::Linear(perceptrons) => perceptrons.iter().map(|p| p.predict_raw(input)).collect(),
+            Model::Mlp(mlp) => mlp.predict(input, is_classification),
+        }
+    }
+
+    /// One stochastic gradient step on a single sample
+    pub fn train_step(&mut self, input: &[f64], target: &[f64], is_classification: bool, alpha: f64) {
+        match self {
+            Model::Linear(perceptrons) => {
+                for (p, &t) in perceptrons.iter_mut().zip(target.iter()) {
+                    p.train_step(input, t, alpha);
+                }
+            }
+            Model::Mlp(mlp) => mlp.train_sample(input, target, is_classification, alpha),
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Model::Linear(_) => "linear",
+            Model::Mlp(_) => "mlp",
+        }
+    }
+
+    /// Neurons per layer, input included
+    pub fn layers(&self) -> Vec<usize> {
+        match self {
+            Model::Linear(perceptrons) => vec![perceptrons.first().map_or(0, |p| p.weights().len()), perceptrons.len()],
+            Model::Mlp(mlp) => mlp.layers().to_vec(),
+        }
+    }
+
+    /// Trainable parameters of each weight layer (biases included)
+    pub fn parameters_per_layer(&self) -> Vec<usize> {
+        match self {
+            Model::Linear(perceptrons) => vec![perceptrons.iter().map(|p| p.weights().len() + 1).sum()],
+            Model::Mlp(mlp) => mlp.parameters_per_layer(),
+        }
+    }
+
+    pub fn num_parameters(&self) -> usize {
+        self.parameters_per_layer().iter().sum()
+    }
+}
+
+/// A model with everything needed to use it on new files: input scaler, column names
+/// and class labels. Saved as JSON.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SavedModel {
+    pub model: Model,
+    pub is_classification: bool,
+    /// Applied to the inputs before the model
+    pub scaler: Option<Scaler>,
+    pub feature_names: Vec<String>,
+    pub target_names: Vec<String>,
+    /// Class labels in encoding order (classification only, ±1 targets as
+    /// `LabelEncoding::Bipolar`)
+    pub classes: Vec<String>,
+}
+
+impl SavedModel {
+    /// Raw outputs of the model for an unscaled input
+    pub fn predict(&mut self, input: &[f64]) -> Vec<f64> {
+        match &self.scaler {
+            Some(scaler) => {
+                let scaled = scaler.transform(input);
+                self.model.predict(&scaled, self.is_classification)
+            }
+            None => self.model.predict(input, self.is_classification),
+        }
+    }
+
+    /// Class label of a raw output (classification only)
+    pub fn class_name(&self, output: &[f64]) -> &str {
+        let index = if output.len() == 1 { (output[0] > 0.0) as usize } else { argmax(output) };
+        &self.classes[index]
+    }
+
+    /// Columns as the training file named them (`column_<i>` = position i of a file
+    /// without header)
+    fn column(name: &str) -> Column {
+        match name.strip_prefix("column_").and_then(|i| i.parse().ok()) {
+            Some(i) => Column::Index(i),
+            None => Column::Name(name.to_string()),
+        }
+    }
+
+    /// Load a labelled file with the columns of the training file, targets encoded as
+    /// during training (classes follow the model's order, unknown classes are an error)
+    pub fn load_csv<P: AsRef<Path>>(&self, path: P) -> Result<Dataset, CsvError> {
+        let encoding = if self.is_classification { LabelEncoding::Bipolar } else { LabelEncoding::Numeric };
+        let mut options = CsvOptions::new(encoding);
+        options.features = Some(self.feature_names.iter().map(|n| Self::column(n)).collect());
+        options.targets = self.target_names.iter().map(|n| Self::column(n)).collect();
+        let mut data = load_csv(path, &options)?;
+        if !self.is_classification {
+            return Ok(data.dataset);
+        }
+
+        // Class index in the file -> class index in the model
+        let mut mapping = Vec::with_capacity(data.classes.len());
+        for class in &data.classes {
+            match self.classes.iter().position(|c| c == class) {
+                Some(i) => mapping.push(i),
+                None => return Err(CsvError::Invalid(format!("class \"{}\" unknown to the model", class))),
+            }
+        }
+        let file_labels = if data.classes.len() > 1 {
+            class_labels(&data.dataset.targets)
+        } else {
+            vec![0; data.dataset.len()]
+        };
+        let n = self.classes.len();
+        data.dataset.targets = file_labels
+            .iter()
+            .map(|&l| encode_label(mapping[l], n, LabelEncoding::Bipolar))
+            .collect();
+        data.dataset.metadata.num_classes = Some(n);
+        Ok(data.dataset)
+    }
+
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        let json = serde_json::to_string(self).map_err(io::Error::other)?;
+        fs::write(path, json)
+    }
+
+    pub fn load<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let json = fs::read_to_string(path)?;
+        serde_json::from_str(&json).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+}
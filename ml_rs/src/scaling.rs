@@ -0,0 +1,156 @@
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// How each feature is rescaled
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum ScalerKind {
+    /// (x - mean) / standard deviation
+    Standard,
+    /// Linear map of [min, max] of the training data onto [low, high]
+    MinMax { low: f64, high: f64 },
+    /// (x - median) / interquartile range, insensitive to outliers
+    Robust,
+    /// x / max |x|, keeps zeros (and signs) in place
+    MaxAbs,
+}
+
+/// Per-feature affine scaler: transform(x) = (x - center) / scale + offset.
+///
+/// Features with a zero spread (constant columns) keep a scale of 1.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Scaler {
+    kind: ScalerKind,
+    center: Vec<f64>,
+    scale: Vec<f64>,
+    offset: f64,
+}
+
+impl Scaler {
+    pub fn new(kind: ScalerKind) -> Self {
+        if let ScalerKind::MinMax { low, high } = kind {
+            assert!(low < high, "MinMax range must satisfy low < high");
+        }
+        Scaler {
+            kind,
+            center: Vec::new(),
+            scale: Vec::new(),
+            offset: 0.0,
+        }
+    }
+
+    /// Learn the statistics of each feature of `inputs`
+    pub fn fit(&mut self, inputs: &[Vec<f64>]) {
+        assert!(!inputs.is_empty(), "Need at least one sample");
+        let d = inputs[0].len();
+        assert!(inputs.iter().all(|x| x.len() == d), "Samples must have the same size");
+        let n = inputs.len() as f64;
+
+        self.center.clear();
+        self.scale.clear();
+        self.offset = 0.0;
+        for j in 0..d {
+            let mut column: Vec<f64> = inputs.iter().map(|x| x[j]).collect();
+            let (center, spread) = match self.kind {
+                ScalerKind::Standard => {
+                    let mean = column.iter().sum::<f64>() / n;
+                    let variance = column.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / n;
+                    (mean, variance.sqrt())
+                }
+                ScalerKind::MinMax { low, high } => {
+                    let min = column.iter().cloned().fold(f64::INFINITY, f64::min);
+                    let max = column.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+                    self.offset = low;
+                    (min, (max - min) / (high - low))
+                }
+                ScalerKind::Robust => {
+                    column.sort_by(|a, b| a.total_cmp(b));
+                    let q1 = quantile(&column, 0.25);
+                    let q3 = quantile(&column, 0.75);
+                    (quantile(&column, 0.5), q3 - q1)
+                }
+                ScalerKind::MaxAbs => (0.0, column.iter().fold(0.0f64, |m, v| m.max(v.abs()))),
+            };
+            self.center.push(center);
+            self.scale.push(if spread > 0.0 { spread } else { 1.0 });
+        }
+    }
+
+    pub fn transform(&self, input: &[f64]) -> Vec<f64> {
+        assert!(!self.scale.is_empty(), "Scaler must be fitted first");
+        assert_eq!(input.len(), self.scale.len(), "Input size must match the fitted data");
+        input
+            .iter()
+            .zip(self.center.iter().zip(self.scale.iter()))
+            .map(|(&x, (&c, &s))| (x - c) / s + self.offset)
+            .collect()
+    }
+
+    pub fn inverse_transform(&self, scaled: &[f64]) -> Vec<f64> {
+        assert_eq!(scaled.len(), self.scale.len(), "Input size must match the fitted data");
+        scaled
+            .iter()
+            .zip(self.center.iter().zip(self.scale.iter()))
+            .map(|(&y, (&c, &s))| (y - self.offset) * s + c)
+            .collect()
+    }
+
+    pub fn transform_all(&self, inputs: &[Vec<f64>]) -> Vec<Vec<f64>> {
+        inputs.iter().map(|x| self.transform(x)).collect()
+    }
+
+    pub fn inverse_transform_all(&self, scaled: &[Vec<f64>]) -> Vec<Vec<f64>> {
+        scaled.iter().map(|y| self.inverse_transform(y)).collect()
+    }
+
+    /// `fit` then `transform_all`
+    pub fn fit_transform(&mut self, inputs: &[Vec<f64>]) -> Vec<Vec<f64>> {
+        self.fit(inputs);
+        self.transform_all(inputs)
+    }
+
+    pub fn kind(&self) -> ScalerKind {
+        self.kind
+    }
+}
+
+/// A model together with the scaler of its inputs, saved and loaded as one JSON file
+/// so inference applies exactly the scaling seen during training
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ScaledModel<M> {
+    pub scaler: Scaler,
+    pub model: M,
+}
+
+impl<M> ScaledModel<M> {
+    pub fn new(scaler: Scaler, model: M) -> Self {
+        ScaledModel { scaler, model }
+    }
+
+    /// Input as the model expects it
+    pub fn scale(&self, input: &[f64]) -> Vec<f64> {
+        self.scaler.transform(input)
+    }
+}
+
+impl<M: Serialize + DeserializeOwned> ScaledModel<M> {
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        let json = serde_json::to_string(self).map_err(io::Error::other)?;
+        fs::write(path, json)
+    }
+
+    pub fn load<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let json = fs::read_to_string(path)?;
+        serde_json::from_str(&json).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+}
+
+/// Linear interpolation between the order statistics of sorted `values`
+fn quantile(sorted: &[f64], q: f64) -> f64 {
+    let position = q * (sorted.len() - 1) as f64;
+    let below = position.floor() as usize;
+    let above = position.ceil() as usize;
+    sorted[below] + (sorted[above] - sorted[below]) * (position - below as f64)
+}
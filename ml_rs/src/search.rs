@@ -0,0 +1,523 @@
+use crate::cross_validation::{folds, CvStrategy, Scorer};
+use crate::split::{select, train_test_indices, SplitStrategy};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::thread;
+use std::time::Instant;
+
+/// Value of one hyperparameter
+#[derive(Clone, Debug, PartialEq)]
+pub enum ParamValue {
+    Int(i64),
+    Float(f64),
+    /// Neurons per layer, as given to `MyMLP::new`
+    Layers(Vec<usize>),
+    /// Named choice (activation, kernel, criterion, ...)
+    Text(String),
+}
+
+impl fmt::Display for ParamValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParamValue::Int(v) => write!(f, "{}", v),
+            ParamValue::Float(v) => write!(f, "{}", v),
+            ParamValue::Layers(v) => write!(f, "{:?}", v),
+            ParamValue::Text(v) => write!(f, "{}", v),
+        }
+    }
+}
+
+/// Values a hyperparameter can take
+#[derive(Clone, Debug, PartialEq)]
+pub enum Domain {
+    /// Explicit list (the only kind grid search accepts)
+    Values(Vec<ParamValue>),
+    /// Integers in [low, high]
+    IntRange { low: i64, high: i64 },
+    /// Reals in [low, high], drawn uniformly in log scale if `log` (learning rates, penalties)
+    FloatRange { low: f64, high: f64, log: bool },
+}
+
+/// Declared hyperparameters, in order
+#[derive(Clone, Debug, Default)]
+pub struct SearchSpace {
+    params: Vec<(String, Domain)>,
+}
+
+impl SearchSpace {
+    pub fn new() -> Self {
+        SearchSpace { params: Vec::new() }
+    }
+
+    /// Add a hyperparameter
+    pub fn with(mut self, name: &str, domain: Domain) -> Self {
+        if let Domain::Values(values) = &domain {
+            assert!(!values.is_empty(), "Domain of {} is empty", name);
+        }
+        self.params.push((name.to_string(), domain));
+        self
+    }
+
+    /// Every combination of the `Values` domains
+    pub fn grid(&self) -> Vec<Params> {
+        let mut grid = vec![Params::default()];
+        for (name, domain) in &self.params {
+            let values = match domain {
+                Domain::Values(values) => values,
+                _ => panic!("Grid search needs a list of values for {}", name),
+            };
+            grid = grid
+                .into_iter()
+                .flat_map(|p| values.iter().map(move |v| p.clone().set(name, v.clone())))
+                .collect();
+        }
+        grid
+    }
+
+    /// One random point of the space
+    pub fn sample(&self, rng: &mut StdRng) -> Params {
+        let mut params = Params::default();
+        for (name, domain) in &self.params {
+            let value = match domain {
+                Domain::Values(values) => values[rng.gen_range(0..values.len())].clone(),
+                Domain::IntRange { low, high } => ParamValue::Int(rng.gen_range(*low..=*high)),
+                Domain::FloatRange { low, high, log: false } => ParamValue::Float(rng.gen_range(*low..=*high)),
+                Domain::FloatRange { low, high, log: true } => {
+                    ParamValue::Float(rng.gen_range(low.ln()..=high.ln()).exp())
+                }
+            };
+            params = params.set(name, value);
+        }
+        params
+    }
+}
+
+/// One point of the search space
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Params {
+    values: Vec<(String, ParamValue)>,
+}
+
+impl Params {
+    /// Same parameters with `name` set to `value` (added or replaced)
+    pub fn set(mut self, name: &str, value: ParamValue) -> Self {
+        match self.values.iter_mut().find(|(n, _)| n == name) {
+            Some(slot) => slot.1 = value,
+            None => self.values.push((name.to_string(), value)),
+        }
+        self
+    }
+
+    pub fn get(&self, name: &str) -> Option<&ParamValue> {
+        self.values.iter().find(|(n, _)| n == name).map(|(_, v)| v)
+    }
+
+    /// Integer parameter (panics if missing or of another kind)
+    pub fn int(&self, name: &str) -> i64 {
+        match self.get(name) {
+            Some(ParamValue::Int(v)) => *v,
+            other => panic!("Parameter {} is not an integer: {:?}", name, other),
+        }
+    }
+
+    /// Real parameter (integers are converted)
+    pub fn float(&self, name: &str) -> f64 {
+        match self.get(name) {
+            Some(ParamValue::Float(v)) => *v,
+            Some(ParamValue::Int(v)) => *v as f64,
+            other => panic!("Parameter {} is not a number: {:?}", name, other),
+        }
+    }
+
+    pub fn layers(&self, name: &str) -> &[usize] {
+        match self.get(name) {
+            Some(ParamValue::Layers(v)) => v,
+            other => panic!("Parameter {} is not a layer list: {:?}", name, other),
+        }
+    }
+
+    pub fn text(&self, name: &str) -> &str {
+        match self.get(name) {
+            Some(ParamValue::Text(v)) => v,
+            other => panic!("Parameter {} is not a text: {:?}", name, other),
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &ParamValue)> {
+        self.values.iter().map(|(n, v)| (n.as_str(), v))
+    }
+}
+
+impl fmt::Display for Params {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let parts: Vec<String> = self.values.iter().map(|(n, v)| format!("{}={}", n, v)).collect();
+        write!(f, "{}", parts.join(", "))
+    }
+}
+
+/// How the candidates are chosen
+#[derive(Clone, Debug, PartialEq)]
+pub enum SearchStrategy {
+    /// Every combination of the space (`Values` domains only)
+    Grid,
+    /// `num_candidates` random points
+    Random { num_candidates: usize, seed: u64 },
+    /// `num_candidates` random points trained with `min_resource` of the parameter `resource`
+    /// (e.g. "num_iter"), the best 1 / eta kept and trained with eta times more, up to `max_resource`
+    SuccessiveHalving {
+        num_candidates: usize,
+        resource: String,
+        min_resource: usize,
+        max_resource: usize,
+        eta: usize,
+        seed: u64,
+    },
+    /// Successive halving brackets from many cheap candidates to a few fully trained ones
+    Hyperband {
+        resource: String,
+        min_resource: usize,
+        max_resource: usize,
+        eta: usize,
+        seed: u64,
+    },
+}
+
+/// How a candidate is scored
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Evaluation<'a> {
+    /// Trained on the rest, scored on `validation_ratio` of the samples
+    Holdout { validation_ratio: f64, strategy: SplitStrategy<'a>, seed: u64 },
+    /// Mean score over cross-validation folds
+    CrossValidation(CvStrategy<'a>),
+}
+
+/// Scoring of the candidates
+#[derive(Clone, Copy, Debug)]
+pub struct Objective<'a> {
+    pub evaluation: Evaluation<'a>,
+    pub scorer: Scorer,
+    /// true for accuracy-like scores, false for errors
+    pub maximize: bool,
+}
+
+/// One trained and scored candidate
+#[derive(Clone, Debug)]
+pub struct Trial {
+    pub id: usize,
+    pub params: Params,
+    /// Successive halving round (0 for grid / random search)
+    pub rung: usize,
+    /// Value of the resource parameter (None for grid / random search)
+    pub budget: Option<usize>,
+    pub score: f64,
+    /// Standard deviation over the folds (0 for a hold-out)
+    pub score_std: f64,
+    pub seconds: f64,
+}
+
+/// Every trial in the order they were run
+#[derive(Clone, Debug)]
+pub struct SearchResults {
+    pub trials: Vec<Trial>,
+    pub maximize: bool,
+}
+
+impl SearchResults {
+    /// Best trial (None if no trial was run); with successive halving / Hyperband only
+    /// the trials trained with the largest budget compete
+    pub fn best(&self) -> Option<&Trial> {
+        let better = |a: &Trial, b: &Trial| {
+            if a.budget != b.budget {
+                a.budget > b.budget
+            } else if self.maximize {
+                a.score > b.score
+            } else {
+                a.score < b.score
+            }
+        };
+        let mut best = self.trials.first()?;
+        for t in &self.trials {
+            if better(t, best) {
+                best = t;
+            }
+        }
+        Some(best)
+    }
+
+    /// Trials best first (largest budget first, then by score)
+    pub fn ranked(&self) -> Vec<&Trial> {
+        let mut trials: Vec<&Trial> = self.trials.iter().collect();
+        trials.sort_by(|a, b| {
+            b.budget.cmp(&a.budget).then_with(|| {
+                if self.maximize {
+                    b.score.total_cmp(&a.score)
+                } else {
+                    a.score.total_cmp(&b.score)
+                }
+            })
+        });
+        trials
+    }
+
+    /// One line per trial: id, rung, score, std, seconds, then one column per parameter
+    /// (quoted, embedded quotes doubled)
+    pub fn to_csv(&self) -> String {
+        let mut names: Vec<&str> = Vec::new();
+        for t in &self.trials {
+            for (n, _) in t.params.iter() {
+                if !names.contains(&n) {
+                    names.push(n);
+                }
+            }
+        }
+        let mut csv = format!("id,rung,score,score_std,seconds,{}\n", names.join(","));
+        for t in &self.trials {
+            let values: Vec<String> = names
+                .iter()
+                .map(|n| {
+                    t.params
+                        .get(n)
+                        .map_or(String::new(), |v| format!("\"{}\"", v.to_string().replace('"', "\"\"")))
+                })
+                .collect();
+            csv.push_str(&format!(
+                "{},{},{},{},{:.3},{}\n",
+                t.id,
+                t.rung,
+                t.score,
+                t.score_std,
+                t.seconds,
+                values.join(",")
+            ));
+        }
+        csv
+    }
+
+    pub fn write_csv<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        fs::write(path, self.to_csv())
+    }
+}
+
+impl fmt::Display for SearchResults {
+    /// Ranked table
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{:>4} {:>4} {:>10} {:>8} {:>8}  params", "id", "rung", "score", "std", "seconds")?;
+        for t in self.ranked() {
+            writeln!(
+                f,
+                "{:>4} {:>4} {:>10.4} {:>8.4} {:>8.2}  {}",
+                t.id, t.rung, t.score, t.score_std, t.seconds, t.params
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Search the hyperparameters of a model.
+///
+/// `fit` builds and trains a model from a candidate's parameters (and the training samples),
+/// `predict` gives its output; candidates of a round are evaluated in parallel.
+///
+/// ```ignore
+/// let space = SearchSpace::new()
+///     .with("npl", Domain::Values(vec![ParamValue::Layers(vec![2, 4, 1]), ParamValue::Layers(vec![2, 8, 1])]))
+///     .with("alpha", Domain::FloatRange { low: 1e-3, high: 1e-1, log: true });
+/// let results = search(
+///     &space,
+///     SearchStrategy::Random { num_candidates: 20, seed: 0 },
+///     &inputs,
+///     &outputs,
+///     &Objective { evaluation: Evaluation::CrossValidation(CvStrategy::KFold { k: 5, seed: Some(0) }), scorer: metrics::accuracy, maximize: true },
+///     |p, x, y| {
+///         let mut mlp = MyMLP::new(p.layers("npl"));
+///         mlp.train(x, y, true, 100_000, p.float("alpha"));
+///         mlp
+///     },
+///     |mlp, x| mlp.predict(x, true),
+/// );
+/// println!("{}", results);
+/// ```
+pub fn search<M, F, P>(
+    space: &SearchSpace,
+    strategy: SearchStrategy,
+    inputs: &[Vec<f64>],
+    outputs: &[Vec<f64>],
+    objective: &Objective,
+    fit: F,
+    predict: P,
+) -> SearchResults
+where
+    F: Fn(&Params, &[Vec<f64>], &[Vec<f64>]) -> M + Sync,
+    P: Fn(&mut M, &[f64]) -> Vec<f64> + Sync,
+{
+    assert_eq!(inputs.len(), outputs.len());
+    let evaluate = |params: &Params| evaluate(params, inputs, outputs, objective, &fit, &predict);
+    let mut trials: Vec<Trial> = Vec::new();
+
+    match strategy {
+        SearchStrategy::Grid => run_round(&mut trials, space.grid(), 0, None, &evaluate),
+        SearchStrategy::Random { num_candidates, seed } => {
+            let mut rng = StdRng::seed_from_u64(seed);
+            let candidates = (0..num_candidates).map(|_| space.sample(&mut rng)).collect();
+            run_round(&mut trials, candidates, 0, None, &evaluate);
+        }
+        SearchStrategy::SuccessiveHalving {
+            num_candidates,
+            resource,
+            min_resource,
+            max_resource,
+            eta,
+            seed,
+        } => {
+            let mut rng = StdRng::seed_from_u64(seed);
+            let candidates = (0..num_candidates).map(|_| space.sample(&mut rng)).collect();
+            successive_halving(&mut trials, candidates, &resource, min_resource, max_resource, eta, objective.maximize, &evaluate);
+        }
+        SearchStrategy::Hyperband {
+            resource,
+            min_resource,
+            max_resource,
+            eta,
+            seed,
+        } => {
+            assert!(eta >= 2 && min_resource >= 1 && min_resource <= max_resource);
+            let mut rng = StdRng::seed_from_u64(seed);
+            // s_max = floor(log_eta(max / min))
+            let mut s_max = 0;
+            while min_resource * eta.pow(s_max + 1) <= max_resource {
+                s_max += 1;
+            }
+            for s in (0..=s_max).rev() {
+                let n = ((s_max + 1) as f64 / (s + 1) as f64 * eta.pow(s) as f64).ceil() as usize;
+                let start = max_resource / eta.pow(s);
+                let candidates = (0..n).map(|_| space.sample(&mut rng)).collect();
+                successive_halving(&mut trials, candidates, &resource, start, max_resource, eta, objective.maximize, &evaluate);
+            }
+        }
+    }
+
+    SearchResults {
+        trials,
+        maximize: objective.maximize,
+    }
+}
+
+/// Evaluate `candidates` in parallel and append the trials (in candidate order)
+fn run_round<E>(trials: &mut Vec<Trial>, candidates: Vec<Params>, rung: usize, budget: Option<usize>, evaluate: &E)
+where
+    E: Fn(&Params) -> (f64, f64, f64) + Sync,
+{
+    let num_threads = thread::available_parallelism().map_or(1, |p| p.get());
+    let chunk_size = candidates.len().div_ceil(num_threads).max(1);
+    let mut scores = Vec::with_capacity(candidates.len());
+    thread::scope(|scope| {
+        let handles: Vec<_> = candidates
+            .chunks(chunk_size)
+            .map(|chunk| scope.spawn(move || chunk.iter().map(evaluate).collect::<Vec<_>>()))
+            .collect();
+        for handle in handles {
+            scores.extend(handle.join().unwrap());
+        }
+    });
+
+    for (params, (score, score_std, seconds)) in candidates.into_iter().zip(scores) {
+        trials.push(Trial {
+            id: trials.len(),
+            params,
+            rung,
+            budget,
+            score,
+            score_std,
+            seconds,
+        });
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn successive_halving<E>(
+    trials: &mut Vec<Trial>,
+    mut candidates: Vec<Params>,
+    resource: &str,
+    min_resource: usize,
+    max_resource: usize,
+    eta: usize,
+    maximize: bool,
+    evaluate: &E,
+) where
+    E: Fn(&Params) -> (f64, f64, f64) + Sync,
+{
+    assert!(eta >= 2, "eta must be at least 2");
+    let mut budget = min_resource.max(1);
+    let mut rung = 0;
+    loop {
+        let round: Vec<Params> = candidates
+            .iter()
+            .map(|p| p.clone().set(resource, ParamValue::Int(budget as i64)))
+            .collect();
+        let first = trials.len();
+        run_round(trials, round, rung, Some(budget), evaluate);
+        if candidates.len() <= 1 || budget >= max_resource {
+            break;
+        }
+
+        // Keep the best 1 / eta
+        let mut order: Vec<usize> = (0..candidates.len()).collect();
+        let score = |k: usize| trials[first + k].score;
+        order.sort_by(|&a, &b| {
+            if maximize {
+                score(b).total_cmp(&score(a))
+            } else {
+                score(a).total_cmp(&score(b))
+            }
+        });
+        let keep = (candidates.len() / eta).max(1);
+        candidates = order[..keep].iter().map(|&k| candidates[k].clone()).collect();
+        budget = (budget * eta).min(max_resource);
+        rung += 1;
+    }
+}
+
+/// (mean score, standard deviation, seconds) of one candidate
+fn evaluate<M, F, P>(
+    params: &Params,
+    inputs: &[Vec<f64>],
+    outputs: &[Vec<f64>],
+    objective: &Objective,
+    fit: &F,
+    predict: &P,
+) -> (f64, f64, f64)
+where
+    F: Fn(&Params, &[Vec<f64>], &[Vec<f64>]) -> M,
+    P: Fn(&mut M, &[f64]) -> Vec<f64>,
+{
+    let start = Instant::now();
+    let parts: Vec<(Vec<usize>, Vec<usize>)> = match objective.evaluation {
+        Evaluation::Holdout { validation_ratio, strategy, seed } => {
+            let split = train_test_indices(inputs.len(), validation_ratio, strategy, seed);
+            vec![(split.train, split.test)]
+        }
+        Evaluation::CrossValidation(strategy) => folds(inputs.len(), strategy)
+            .into_iter()
+            .map(|f| (f.train, f.validation))
+            .collect(),
+    };
+
+    let scores: Vec<f64> = parts
+        .iter()
+        .map(|(train, validation)| {
+            let (train_inputs, train_outputs) = select(inputs, outputs, train);
+            let (validation_inputs, validation_outputs) = select(inputs, outputs, validation);
+            let mut model = fit(params, &train_inputs, &train_outputs);
+            let predictions: Vec<Vec<f64>> = validation_inputs.iter().map(|x| predict(&mut model, x)).collect();
+            (objective.scorer)(&predictions, &validation_outputs)
+        })
+        .collect();
+
+    let mean = scores.iter().sum::<f64>() / scores.len() as f64;
+    let std = (scores.iter().map(|s| (s - mean).powi(2)).sum::<f64>() / scores.len() as f64).sqrt();
+    (mean, std, start.elapsed().as_secs_f64())
+}
@@ -0,0 +1,161 @@
+use crate::encoding::{argmax, is_positive_class};
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+
+/// How samples are assigned to the held-out part
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SplitStrategy<'a> {
+    /// Uniformly at random
+    Random,
+    /// Random within each class, so every part keeps the class proportions
+    /// (`labels[k]` = class of sample k, see `class_labels`)
+    Stratified(&'a [usize]),
+    /// Whole groups are held out, a group never appears on both sides
+    /// (`groups[k]` = group of sample k, e.g. the image of an augmented copy)
+    Grouped(&'a [usize]),
+    /// The last samples are held out, in the order of the data (no shuffling)
+    TimeOrdered,
+}
+
+/// Sample indices of each part, in increasing order
+#[derive(Clone, Debug, PartialEq)]
+pub struct SplitIndices {
+    pub train: Vec<usize>,
+    pub validation: Vec<usize>,
+    pub test: Vec<usize>,
+}
+
+/// Inputs and outputs of each part, ready for the `train` / `predict` methods
+#[derive(Clone, Debug)]
+pub struct Split {
+    pub train_inputs: Vec<Vec<f64>>,
+    pub train_outputs: Vec<Vec<f64>>,
+    pub validation_inputs: Vec<Vec<f64>>,
+    pub validation_outputs: Vec<Vec<f64>>,
+    pub test_inputs: Vec<Vec<f64>>,
+    pub test_outputs: Vec<Vec<f64>>,
+}
+
+impl SplitIndices {
+    /// Copy the samples of each part
+    pub fn apply(&self, inputs: &[Vec<f64>], outputs: &[Vec<f64>]) -> Split {
+        assert_eq!(inputs.len(), outputs.len());
+        let (train_inputs, train_outputs) = select(inputs, outputs, &self.train);
+        let (validation_inputs, validation_outputs) = select(inputs, outputs, &self.validation);
+        let (test_inputs, test_outputs) = select(inputs, outputs, &self.test);
+        Split {
+            train_inputs,
+            train_outputs,
+            validation_inputs,
+            validation_outputs,
+            test_inputs,
+            test_outputs,
+        }
+    }
+}
+
+/// Samples `indices` of the paired vectors
+pub fn select(inputs: &[Vec<f64>], outputs: &[Vec<f64>], indices: &[usize]) -> (Vec<Vec<f64>>, Vec<Vec<f64>>) {
+    (
+        indices.iter().map(|&k| inputs[k].clone()).collect(),
+        indices.iter().map(|&k| outputs[k].clone()).collect(),
+    )
+}
+
+/// Class index of each target: sign for single-value targets (-1 -> 0, +1 -> 1,
+/// 0 / 1 targets work too), argmax for one-hot / bipolar vectors
+pub fn class_labels(outputs: &[Vec<f64>]) -> Vec<usize> {
+    outputs
+        .iter()
+        .map(|y| if y.len() == 1 { is_positive_class(y[0]) as usize } else { argmax(y) })
+        .collect()
+}
+
+/// Train / test split of `num_samples` samples, `test_ratio` of them (rounded) held out
+pub fn train_test_indices(num_samples: usize, test_ratio: f64, strategy: SplitStrategy, seed: u64) -> SplitIndices {
+    train_validation_test_indices(num_samples, 0.0, test_ratio, strategy, seed)
+}
+
+/// Train / validation / test split, the ratios are fractions of all samples
+pub fn train_validation_test_indices(
+    num_samples: usize,
+    validation_ratio: f64,
+    test_ratio: f64,
+    strategy: SplitStrategy,
+    seed: u64,
+) -> SplitIndices {
+    assert!(validation_ratio >= 0.0 && test_ratio >= 0.0, "Ratios must be non-negative");
+    assert!(validation_ratio + test_ratio <= 1.0, "Ratios must sum to at most 1");
+    match strategy {
+        SplitStrategy::Stratified(labels) | SplitStrategy::Grouped(labels) => {
+            assert_eq!(labels.len(), num_samples, "One label / group per sample is needed")
+        }
+        _ => {}
+    }
+    let mut rng = StdRng::seed_from_u64(seed);
+
+    let all: Vec<usize> = (0..num_samples).collect();
+    let (rest, test) = hold_out(&all, test_ratio, strategy, &mut rng);
+    // Validation ratio relative to what is left after the test part
+    let remaining = 1.0 - test_ratio;
+    let ratio = if remaining > 0.0 { validation_ratio / remaining } else { 0.0 };
+    let (train, validation) = hold_out(&rest, ratio.min(1.0), strategy, &mut rng);
+
+    SplitIndices { train, validation, test }
+}
+
+/// Train / test split of paired inputs and outputs (the validation part is empty)
+pub fn train_test_split(
+    inputs: &[Vec<f64>],
+    outputs: &[Vec<f64>],
+    test_ratio: f64,
+    strategy: SplitStrategy,
+    seed: u64,
+) -> Split {
+    train_test_indices(inputs.len(), test_ratio, strategy, seed).apply(inputs, outputs)
+}
+
+/// Split `indices` (sorted) into (kept, held out) with about `ratio` of them held out
+fn hold_out(indices: &[usize], ratio: f64, strategy: SplitStrategy, rng: &mut StdRng) -> (Vec<usize>, Vec<usize>) {
+    let target = (indices.len() as f64 * ratio).round() as usize;
+    let mut held: Vec<usize> = match strategy {
+        SplitStrategy::Random => {
+            let mut shuffled = indices.to_vec();
+            shuffled.shuffle(rng);
+            shuffled.truncate(target);
+            shuffled
+        }
+        SplitStrategy::Stratified(labels) => {
+            let mut classes: Vec<usize> = indices.iter().map(|&k| labels[k]).collect();
+            classes.sort();
+            classes.dedup();
+            let mut held = Vec::new();
+            for class in classes {
+                let mut members: Vec<usize> = indices.iter().cloned().filter(|&k| labels[k] == class).collect();
+                members.shuffle(rng);
+                let count = (members.len() as f64 * ratio).round() as usize;
+                held.extend_from_slice(&members[..count]);
+            }
+            held
+        }
+        SplitStrategy::Grouped(groups) => {
+            let mut ids: Vec<usize> = indices.iter().map(|&k| groups[k]).collect();
+            ids.sort();
+            ids.dedup();
+            ids.shuffle(rng);
+            let mut held = Vec::new();
+            for id in ids {
+                if held.len() >= target {
+                    break;
+                }
+                held.extend(indices.iter().cloned().filter(|&k| groups[k] == id));
+            }
+            held
+        }
+        SplitStrategy::TimeOrdered => indices[indices.len() - target..].to_vec(),
+    };
+    held.sort();
+    let kept = indices.iter().cloned().filter(|k| held.binary_search(k).is_err()).collect();
+    (kept, held)
+}
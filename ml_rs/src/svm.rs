@@ -0,0 +1,438 @@
+use crate::encoding::{argmax, bipolar_class};
+
+/// Kernel function used by the SVM:
+/// - Linear: k(a, b) = a · b
+/// - Polynomial: k(a, b) = (gamma * a · b + coef0)^degree
+/// - Rbf: k(a, b) = exp(-gamma * ||a - b||^2)
+#[derive(Clone, Copy, Debug)]
+pub enum Kernel {
+    Linear,
+    Polynomial { degree: i32, gamma: f64, coef0: f64 },
+    Rbf { gamma: f64 },
+}
+
+impl Kernel {
+    pub fn compute(&self, a: &[f64], b: &[f64]) -> f64 {
+        match *self {
+            Kernel::Linear => dot(a, b),
+            Kernel::Polynomial { degree, gamma, coef0 } => (gamma * dot(a, b) + coef0).powi(degree),
+            Kernel::Rbf { gamma } => {
+                let mut dist2 = 0.0;
+                for (&x, &y) in a.iter().zip(b.iter()) {
+                    dist2 += (x - y) * (x - y);
+                }
+                (-gamma * dist2).exp()
+            }
+        }
+    }
+}
+
+fn dot(a: &[f64], b: &[f64]) -> f64 {
+    let mut sum = 0.0;
+    for (&x, &y) in a.iter().zip(b.iter()) {
+        sum += x * y;
+    }
+    sum
+}
+
+/// Used in place of a non-positive curvature in the SMO step (same value as LIBSVM)
+const TAU: f64 = 1e-12;
+
+/// Binary SVM classifier trained with SMO:
+/// f(x) = sum_i coef_i * k(sv_i, x) + b, with coef_i = alpha_i * y_i
+///
+/// - Soft margin: 0 <= alpha_i <= C
+/// - Hard margin: C = +inf (see `BinarySVM::hard_margin`)
+///
+/// Targets follow the `LinearPerceptron` convention: only `outputs[k][0]` is used
+/// and its sign gives the class (+1 / -1)
+pub struct BinarySVM {
+    kernel: Kernel,
+    c: f64,
+    /// support vectors (samples with alpha_i > 0)
+    support_vectors: Vec<Vec<f64>>,
+    /// dual coefficients alpha_i * y_i, one per support vector
+    dual_coefs: Vec<f64>,
+    bias: f64,
+}
+
+impl BinarySVM {
+    /// Soft-margin SVM with penalty `c` (must be > 0)
+    pub fn new(kernel: Kernel, c: f64) -> Self {
+        assert!(c > 0.0, "C must be strictly positive");
+        BinarySVM {
+            kernel,
+            c,
+            support_vectors: Vec::new(),
+            dual_coefs: Vec::new(),
+            bias: 0.0,
+        }
+    }
+
+    /// Hard-margin SVM (no slack allowed, only for separable data)
+    pub fn hard_margin(kernel: Kernel) -> Self {
+        Self::new(kernel, f64::INFINITY)
+    }
+
+    /// Train with SMO (maximal violating pair, second order working set selection)
+    ///
+    /// - `inputs`: Vec of samples
+    /// - `outputs`: Vec of targets, `outputs[k][0] > 0` is the +1 class (±1 or 0 / 1 targets)
+    /// - `max_iter`: maximum number of SMO steps
+    /// - `tol`: stopping tolerance on the KKT violation
+    pub fn train(&mut self, inputs: &[Vec<f64>], outputs: &[Vec<f64>], max_iter: usize, tol: f64) {
+        assert_eq!(inputs.len(), outputs.len());
+        assert!(!inputs.is_empty(), "Need at least one sample");
+
+        let y: Vec<f64> = outputs
+            .iter()
+            .map(|o| bipolar_class(o[0]))
+            .collect();
+        self.train_labels(inputs, &y, max_iter, tol);
+    }
+
+    pub(crate) fn train_labels(&mut self, inputs: &[Vec<f64>], y: &[f64], max_iter: usize, tol: f64) {
+        let n = inputs.len();
+        let c = self.c;
+
+        // Precomputed kernel matrix K[i][j]
+        let mut k = vec![vec![0.0; n]; n];
+        for i in 0..n {
+            for j in i..n {
+                let v = self.kernel.compute(&inputs[i], &inputs[j]);
+                k[i][j] = v;
+                k[j][i] = v;
+            }
+        }
+
+        // Dual problem: min 0.5 * a^T Q a - e^T a, Q[i][j] = y_i y_j K[i][j]
+        // grad[i] = (Q a)_i - 1
+        let mut alpha = vec![0.0; n];
+        let mut grad = vec![-1.0; n];
+
+        let is_upper = |a: f64| a >= c;
+        let is_lower = |a: f64| a <= 0.0;
+
+        for _ in 0..max_iter {
+            // i: maximal violation in I_up
+            let mut g_max = f64::NEG_INFINITY;
+            let mut i_sel = None;
+            for t in 0..n {
+                let in_up = if y[t] > 0.0 { !is_upper(alpha[t]) } else { !is_lower(alpha[t]) };
+                if in_up && -y[t] * grad[t] >= g_max {
+                    g_max = -y[t] * grad[t];
+                    i_sel = Some(t);
+                }
+            }
+            let i = match i_sel {
+                Some(i) => i,
+                None => break,
+            };
+
+            // j: best second order decrease in I_low
+            let mut g_max2 = f64::NEG_INFINITY;
+            let mut obj_min = f64::INFINITY;
+            let mut j_sel = None;
+            for t in 0..n {
+                let in_low = if y[t] > 0.0 { !is_lower(alpha[t]) } else { !is_upper(alpha[t]) };
+                if !in_low {
+                    continue;
+                }
+                let yg = y[t] * grad[t];
+                if yg >= g_max2 {
+                    g_max2 = yg;
+                }
+                let grad_diff = g_max + yg;
+                if grad_diff > 0.0 {
+                    let mut quad = k[i][i] + k[t][t] - 2.0 * k[i][t];
+                    if quad <= 0.0 {
+                        quad = TAU;
+                    }
+                    let obj = -(grad_diff * grad_diff) / quad;
+                    if obj <= obj_min {
+                        obj_min = obj;
+                        j_sel = Some(t);
+                    }
+                }
+            }
+
+            let j = match j_sel {
+                Some(j) if g_max + g_max2 >= tol => j,
+                _ => break,
+            };
+
+            let old_ai = alpha[i];
+            let old_aj = alpha[j];
+            let mut quad = k[i][i] + k[j][j] - 2.0 * k[i][j];
+            if quad <= 0.0 {
+                quad = TAU;
+            }
+
+            if y[i] != y[j] {
+                let delta = (-grad[i] - grad[j]) / quad;
+                let diff = alpha[i] - alpha[j];
+                alpha[i] += delta;
+                alpha[j] += delta;
+                if diff > 0.0 {
+                    if alpha[j] < 0.0 {
+                        alpha[j] = 0.0;
+                        alpha[i] = diff;
+                    }
+                } else if alpha[i] < 0.0 {
+                    alpha[i] = 0.0;
+                    alpha[j] = -diff;
+                }
+                if diff > 0.0 {
+                    if alpha[i] > c {
+                        alpha[i] = c;
+                        alpha[j] = c - diff;
+                    }
+                } else if alpha[j] > c {
+                    alpha[j] = c;
+                    alpha[i] = c + diff;
+                }
+            } else {
+                let delta = (grad[i] - grad[j]) / quad;
+                let sum = alpha[i] + alpha[j];
+                alpha[i] -= delta;
+                alpha[j] += delta;
+                if sum > c {
+                    if alpha[i] > c {
+                        alpha[i] = c;
+                        alpha[j] = sum - c;
+                    }
+                } else if alpha[j] < 0.0 {
+                    alpha[j] = 0.0;
+                    alpha[i] = sum;
+                }
+                if sum > c {
+                    if alpha[j] > c {
+                        alpha[j] = c;
+                        alpha[i] = sum - c;
+                    }
+                } else if alpha[i] < 0.0 {
+                    alpha[i] = 0.0;
+                    alpha[j] = sum;
+                }
+            }
+
+            // Update gradient
+            let d_ai = alpha[i] - old_ai;
+            let d_aj = alpha[j] - old_aj;
+            for t in 0..n {
+                grad[t] += y[t] * (y[i] * k[i][t] * d_ai + y[j] * k[j][t] * d_aj);
+            }
+        }
+
+        // Bias: average over free support vectors, midpoint of the bounds otherwise
+        let mut ub = f64::INFINITY;
+        let mut lb = f64::NEG_INFINITY;
+        let mut sum_free = 0.0;
+        let mut nr_free = 0;
+        for t in 0..n {
+            let yg = y[t] * grad[t];
+            if is_upper(alpha[t]) {
+                if y[t] < 0.0 { ub = ub.min(yg) } else { lb = lb.max(yg) }
+            } else if is_lower(alpha[t]) {
+                if y[t] > 0.0 { ub = ub.min(yg) } else { lb = lb.max(yg) }
+            } else {
+                nr_free += 1;
+                sum_free += yg;
+            }
+        }
+        let rho = if nr_free > 0 {
+            sum_free / nr_free as f64
+        } else {
+            (ub + lb) / 2.0
+        };
+        self.bias = -rho;
+
+        self.support_vectors.clear();
+        self.dual_coefs.clear();
+        for t in 0..n {
+            if alpha[t] > 0.0 {
+                self.support_vectors.push(inputs[t].clone());
+                self.dual_coefs.push(alpha[t] * y[t]);
+            }
+        }
+    }
+
+    /// Signed distance-like score: sum_i coef_i * k(sv_i, x) + b
+    pub fn decision_function(&self, input: &[f64]) -> f64 {
+        let mut sum = self.bias;
+        for (sv, &coef) in self.support_vectors.iter().zip(self.dual_coefs.iter()) {
+            sum += coef * self.kernel.compute(sv, input);
+        }
+        sum
+    }
+
+    /// Predicted class in {-1.0, 1.0}
+    pub fn predict_class(&self, input: &[f64]) -> f64 {
+        if self.decision_function(input) >= 0.0 { 1.0 } else { -1.0 }
+    }
+
+    pub fn support_vectors(&self) -> &[Vec<f64>] {
+        &self.support_vectors
+    }
+
+    /// alpha_i * y_i for each support vector
+    pub fn dual_coefficients(&self) -> &[f64] {
+        &self.dual_coefs
+    }
+
+    pub fn bias(&self) -> f64 {
+        self.bias
+    }
+
+    /// Primal weights w = sum_i coef_i * sv_i (linear kernel only)
+    pub fn weights(&self) -> Option<Vec<f64>> {
+        if !matches!(self.kernel, Kernel::Linear) || self.support_vectors.is_empty() {
+            return None;
+        }
+        let mut w = vec![0.0; self.support_vectors[0].len()];
+        for (sv, &coef) in self.support_vectors.iter().zip(self.dual_coefs.iter()) {
+            for (w_i, &x_i) in w.iter_mut().zip(sv.iter()) {
+                *w_i += coef * x_i;
+            }
+        }
+        Some(w)
+    }
+
+    /// Geometric margin width 2 / ||w||, computed in feature space for any kernel
+    pub fn margin(&self) -> f64 {
+        let mut norm2 = 0.0;
+        for (a, &ca) in self.support_vectors.iter().zip(self.dual_coefs.iter()) {
+            for (b, &cb) in self.support_vectors.iter().zip(self.dual_coefs.iter()) {
+                norm2 += ca * cb * self.kernel.compute(a, b);
+            }
+        }
+        2.0 / norm2.sqrt()
+    }
+}
+
+/// How `MultiClassSVM` combines binary SVMs
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum MultiClassStrategy {
+    /// One SVM per class (class vs all the others), highest score wins
+    OneVsRest,
+    /// One SVM per pair of classes, majority vote
+    OneVsOne,
+}
+
+/// Multi-class SVM built from `BinarySVM`s.
+///
+/// Targets use the same layout as the 3-class tests: one value per class,
+/// +1 for the true class and -1 elsewhere (the argmax is taken as the class).
+pub struct MultiClassSVM {
+    strategy: MultiClassStrategy,
+    num_classes: usize,
+    /// (positive class, negative class, model); negative class is unused for one-vs-rest
+    models: Vec<(usize, usize, BinarySVM)>,
+    /// Whether each model saw samples in the last `train` (one-vs-one pairs without any
+    /// sample of their two classes stay untrained and are left out of the vote)
+    trained: Vec<bool>,
+}
+
+impl MultiClassSVM {
+    pub fn new(num_classes: usize, kernel: Kernel, c: f64, strategy: MultiClassStrategy) -> Self {
+        assert!(num_classes >= 2, "Need at least two classes");
+        let mut models = Vec::new();
+        match strategy {
+            MultiClassStrategy::OneVsRest => {
+                for a in 0..num_classes {
+                    models.push((a, a, BinarySVM::new(kernel, c)));
+                }
+            }
+            MultiClassStrategy::OneVsOne => {
+                for a in 0..num_classes {
+                    for b in (a + 1)..num_classes {
+                        models.push((a, b, BinarySVM::new(kernel, c)));
+                    }
+                }
+            }
+        }
+
+        MultiClassSVM {
+            strategy,
+            num_classes,
+            trained: vec![false; models.len()],
+            models,
+        }
+    }
+
+    pub fn train(&mut self, inputs: &[Vec<f64>], outputs: &[Vec<f64>], max_iter: usize, tol: f64) {
+        assert_eq!(inputs.len(), outputs.len());
+        let classes: Vec<usize> = outputs
+            .iter()
+            .map(|o| {
+                assert_eq!(o.len(), self.num_classes, "Target size must match number of classes");
+                argmax(o)
+            })
+            .collect();
+
+        for ((a, b, model), trained) in self.models.iter_mut().zip(self.trained.iter_mut()) {
+            *trained = false;
+            match self.strategy {
+                MultiClassStrategy::OneVsRest => {
+                    let y: Vec<f64> = classes
+                        .iter()
+                        .map(|&cl| if cl == *a { 1.0 } else { -1.0 })
+                        .collect();
+                    model.train_labels(inputs, &y, max_iter, tol);
+                    *trained = true;
+                }
+                MultiClassStrategy::OneVsOne => {
+                    let mut sub_inputs = Vec::new();
+                    let mut y = Vec::new();
+                    for (x, &cl) in inputs.iter().zip(classes.iter()) {
+                        if cl == *a || cl == *b {
+                            sub_inputs.push(x.clone());
+                            y.push(if cl == *a { 1.0 } else { -1.0 });
+                        }
+                    }
+                    if !sub_inputs.is_empty() {
+                        model.train_labels(&sub_inputs, &y, max_iter, tol);
+                        *trained = true;
+                    }
+                }
+            }
+        }
+    }
+
+    /// One score per class: decision values (one-vs-rest) or vote counts (one-vs-one)
+    pub fn decision_function(&self, input: &[f64]) -> Vec<f64> {
+        let mut scores = vec![0.0; self.num_classes];
+        for ((a, b, model), _) in self.models.iter().zip(&self.trained).filter(|(_, &t)| t) {
+            let f = model.decision_function(input);
+            match self.strategy {
+                MultiClassStrategy::OneVsRest => scores[*a] = f,
+                MultiClassStrategy::OneVsOne => {
+                    if f >= 0.0 {
+                        scores[*a] += 1.0;
+                    } else {
+                        scores[*b] += 1.0;
+                    }
+                }
+            }
+        }
+        scores
+    }
+
+    /// Index of the predicted class
+    pub fn predict_class_index(&self, input: &[f64]) -> usize {
+        argmax(&self.decision_function(input))
+    }
+
+    /// Predicted class as a +1 / -1 vector (same layout as the targets)
+    pub fn predict(&self, input: &[f64]) -> Vec<f64> {
+        let cl = self.predict_class_index(input);
+        (0..self.num_classes)
+            .map(|j| if j == cl { 1.0 } else { -1.0 })
+            .collect()
+    }
+
+    /// The underlying binary SVMs as (positive class, negative class, model)
+    pub fn models(&self) -> &[(usize, usize, BinarySVM)] {
+        &self.models
+    }
+}
@@ -0,0 +1,194 @@
+// Course cases of "Rapport résultats observés.md": each model is trained with fixed
+// seeds and checked against the outcome recorded in the report (OK = learns the case,
+// KO = cannot, e.g. a linear model on XOR).
+//
+// Training goes through `MyMLP::train_with_rng` / `LinearPerceptron::train_with_rng`
+// (what `train` / `train_*` run) with a seeded generator, so the results do not change
+// between runs.
+
+use ml_lib::datasets::{self, Dataset};
+use ml_lib::linear_perceptron::LinearPerceptron;
+use ml_lib::metrics::{accuracy, r2_score, to_bipolar};
+use ml_lib::naive_multi_layer_perceptron::MyMLP;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+const SEED: u64 = 1;
+
+fn train_mlp(npl: &[usize], data: &Dataset, is_classification: bool, num_iter: usize, alpha: f64) -> Vec<Vec<f64>> {
+    let mut mlp = MyMLP::with_seed(npl, SEED);
+    let mut rng = StdRng::seed_from_u64(SEED);
+    mlp.train_with_rng(&mut rng, &data.inputs, &data.targets, is_classification, num_iter, alpha);
+    let predictions: Vec<Vec<f64>> = data.inputs.iter().map(|x| mlp.predict(x, is_classification)).collect();
+    on_target_scale(predictions, data)
+}
+
+/// Predictions read on the ±1 scale of the metrics (XOR has 0 / 1 targets)
+fn on_target_scale(predictions: Vec<Vec<f64>>, data: &Dataset) -> Vec<Vec<f64>> {
+    match (data.metadata.label_encoding, data.metadata.num_classes) {
+        (Some(encoding), Some(num_classes)) => to_bipolar(&predictions, encoding, num_classes),
+        _ => predictions,
+    }
+}
+
+/// One perceptron per output (one-vs-rest for several classes), raw outputs
+fn train_linear(data: &Dataset, num_iter: usize, alpha: f64) -> Vec<Vec<f64>> {
+    let perceptrons: Vec<LinearPerceptron> = (0..data.output_dim())
+        .map(|o| {
+            let outputs: Vec<Vec<f64>> = data.targets.iter().map(|y| vec![y[o]]).collect();
+            let mut p = LinearPerceptron::with_seed(data.input_dim(), SEED + o as u64);
+            p.train_with_rng(&mut StdRng::seed_from_u64(SEED), &data.inputs, &outputs, num_iter, alpha);
+            p
+        })
+        .collect();
+    let predictions = data
+        .inputs
+        .iter()
+        .map(|x| perceptrons.iter().map(|p| p.predict_raw(x)).collect())
+        .collect();
+    on_target_scale(predictions, data)
+}
+
+// #### LINEAR CLASSIFICATION ####
+
+#[test]
+fn linear_learns_linear_simple() {
+    let data = datasets::linear_simple(SEED, 3, 0.0);
+    assert_eq!(accuracy(&train_linear(&data, 50_000, 0.1), &data.targets), 1.0);
+}
+
+#[test]
+fn linear_learns_linear_multiple() {
+    let data = datasets::linear_multiple(SEED, 100, 0.0);
+    assert_eq!(accuracy(&train_linear(&data, 100_000, 0.01), &data.targets), 1.0);
+}
+
+#[test]
+fn linear_fails_on_xor() {
+    let data = datasets::xor(SEED, 4, 0.0);
+    assert!(accuracy(&train_linear(&data, 100_000, 0.1), &data.targets) <= 0.75);
+}
+
+#[test]
+fn linear_fails_on_cross() {
+    let data = datasets::cross(SEED, 500, 0.0);
+    assert!(accuracy(&train_linear(&data, 100_000, 0.05), &data.targets) < 0.7);
+}
+
+#[test]
+fn linear_mostly_learns_three_classes() {
+    // The report: high accuracy, but not every point
+    let data = datasets::three_classes(SEED, 500, 0.0);
+    assert!(accuracy(&train_linear(&data, 100_000, 0.05), &data.targets) > 0.9);
+}
+
+#[test]
+fn linear_fails_on_multi_cross() {
+    let data = datasets::multi_cross(SEED, 1000, 0.0);
+    assert!(accuracy(&train_linear(&data, 100_000, 0.01), &data.targets) < 0.6);
+}
+
+// #### LINEAR REGRESSION ####
+
+#[test]
+fn linear_fits_linear_simple_2d() {
+    let data = datasets::linear_simple_2d(SEED, 2, 0.0);
+    assert!(r2_score(&train_linear(&data, 50_000, 0.1), &data.targets) > 0.999);
+}
+
+#[test]
+fn linear_fits_linear_simple_3d() {
+    let data = datasets::linear_simple_3d(SEED, 3, 0.0);
+    assert!(r2_score(&train_linear(&data, 50_000, 0.1), &data.targets) > 0.999);
+}
+
+#[test]
+fn linear_fits_linear_tricky_3d() {
+    let data = datasets::linear_tricky_3d(SEED, 3, 0.0);
+    assert!(r2_score(&train_linear(&data, 100_000, 0.1), &data.targets) > 0.999);
+}
+
+#[test]
+fn linear_fails_on_non_linear_simple_2d() {
+    // The best line only explains a quarter of the variance
+    let data = datasets::non_linear_simple_2d(SEED, 3, 0.0);
+    assert!(r2_score(&train_linear(&data, 50_000, 0.05), &data.targets) < 0.5);
+}
+
+#[test]
+fn linear_fails_on_non_linear_simple_3d() {
+    let data = datasets::non_linear_simple_3d(SEED, 4, 0.0);
+    assert!(r2_score(&train_linear(&data, 50_000, 0.05), &data.targets) < 0.5);
+}
+
+// #### MLP CLASSIFICATION ####
+
+#[test]
+fn mlp_learns_linear_simple() {
+    let data = datasets::linear_simple(SEED, 3, 0.0);
+    assert_eq!(accuracy(&train_mlp(&[2, 1], &data, true, 50_000, 0.1), &data.targets), 1.0);
+}
+
+#[test]
+fn mlp_learns_linear_multiple() {
+    let data = datasets::linear_multiple(SEED, 100, 0.0);
+    assert_eq!(accuracy(&train_mlp(&[2, 1], &data, true, 50_000, 0.1), &data.targets), 1.0);
+}
+
+#[test]
+fn mlp_learns_xor() {
+    let data = datasets::xor(SEED, 4, 0.0);
+    assert_eq!(accuracy(&train_mlp(&[2, 2, 1], &data, true, 500_000, 0.1), &data.targets), 1.0);
+}
+
+#[test]
+fn mlp_learns_cross() {
+    let data = datasets::cross(SEED, 500, 0.0);
+    assert!(accuracy(&train_mlp(&[2, 4, 1], &data, true, 500_000, 0.05), &data.targets) > 0.95);
+}
+
+#[test]
+fn mlp_learns_three_classes() {
+    let data = datasets::three_classes(SEED, 500, 0.0);
+    assert!(accuracy(&train_mlp(&[2, 3], &data, true, 200_000, 0.05), &data.targets) > 0.97);
+}
+
+#[test]
+#[ignore = "slow: millions of iterations, run with --ignored in release"]
+fn mlp_mostly_learns_multi_cross() {
+    // The report: about 80-85 %
+    let data = datasets::multi_cross(SEED, 1000, 0.0);
+    assert!(accuracy(&train_mlp(&[2, 16, 16, 3], &data, true, 5_000_000, 0.01), &data.targets) > 0.8);
+}
+
+// #### MLP REGRESSION ####
+
+#[test]
+fn mlp_fits_linear_simple_2d() {
+    let data = datasets::linear_simple_2d(SEED, 2, 0.0);
+    assert!(r2_score(&train_mlp(&[1, 1], &data, false, 50_000, 0.1), &data.targets) > 0.999);
+}
+
+#[test]
+fn mlp_fits_non_linear_simple_2d() {
+    let data = datasets::non_linear_simple_2d(SEED, 3, 0.0);
+    assert!(r2_score(&train_mlp(&[1, 3, 1], &data, false, 100_000, 0.05), &data.targets) > 0.99);
+}
+
+#[test]
+fn mlp_fits_linear_simple_3d() {
+    let data = datasets::linear_simple_3d(SEED, 3, 0.0);
+    assert!(r2_score(&train_mlp(&[2, 1], &data, false, 50_000, 0.1), &data.targets) > 0.999);
+}
+
+#[test]
+fn mlp_fits_linear_tricky_3d() {
+    let data = datasets::linear_tricky_3d(SEED, 3, 0.0);
+    assert!(r2_score(&train_mlp(&[2, 1], &data, false, 50_000, 0.1), &data.targets) > 0.999);
+}
+
+#[test]
+fn mlp_fits_non_linear_simple_3d() {
+    let data = datasets::non_linear_simple_3d(SEED, 4, 0.0);
+    assert!(r2_score(&train_mlp(&[2, 2, 1], &data, false, 200_000, 0.01), &data.targets) > 0.99);
+}
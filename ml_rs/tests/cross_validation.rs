@@ -0,0 +1,36 @@
+use ml_lib::cross_validation::{folds, CvStrategy};
+
+#[test]
+fn stratified_folds_keep_every_sample_once() {
+    let labels = [0, 0, 0, 1, 1, 1];
+    let folds = folds(6, CvStrategy::StratifiedKFold { k: 3, labels: &labels, seed: 0 });
+    assert_eq!(folds.len(), 3);
+    let mut validation: Vec<usize> = folds.iter().flat_map(|f| f.validation.clone()).collect();
+    validation.sort();
+    assert_eq!(validation, (0..6).collect::<Vec<_>>());
+}
+
+#[test]
+#[should_panic(expected = "2 <= k")]
+fn stratified_rejects_zero_folds() {
+    folds(4, CvStrategy::StratifiedKFold { k: 0, labels: &[0, 0, 1, 1], seed: 0 });
+}
+
+#[test]
+#[should_panic(expected = "2 <= k")]
+fn stratified_rejects_more_folds_than_samples() {
+    folds(4, CvStrategy::StratifiedKFold { k: 5, labels: &[0, 0, 1, 1], seed: 0 });
+}
+
+#[test]
+fn leave_one_out_holds_out_each_sample() {
+    let folds = folds(3, CvStrategy::LeaveOneOut);
+    let validation: Vec<Vec<usize>> = folds.iter().map(|f| f.validation.clone()).collect();
+    assert_eq!(validation, [[0], [1], [2]]);
+}
+
+#[test]
+#[should_panic(expected = "2 <= k")]
+fn leave_one_out_rejects_a_single_sample() {
+    folds(1, CvStrategy::LeaveOneOut);
+}
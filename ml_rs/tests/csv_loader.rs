@@ -0,0 +1,100 @@
+use ml_lib::csv_loader::{parse_csv, parse_inputs, Column, CsvOptions, LabelEncoding, MissingValues};
+
+#[test]
+fn sorts_numeric_labels_numerically() {
+    let text = "x,label\n0.1,10\n0.2,2\n0.3,1\n0.4,2\n";
+    let data = parse_csv(text, "numbers", &CsvOptions::new(LabelEncoding::Index)).unwrap();
+    assert_eq!(data.classes, ["1", "2", "10"]);
+    assert_eq!(data.dataset.targets, [[2.0], [1.0], [0.0], [1.0]]);
+}
+
+#[test]
+fn sorts_text_labels_alphabetically() {
+    let text = "x,label\n0.1,dog\n0.2,cat\n0.3,dog\n";
+    let data = parse_csv(text, "animals", &CsvOptions::new(LabelEncoding::Bipolar)).unwrap();
+    assert_eq!(data.classes, ["cat", "dog"]);
+    // Second class = +1
+    assert_eq!(data.dataset.targets, [[1.0], [-1.0], [1.0]]);
+}
+
+#[test]
+fn picks_columns_by_name_and_position() {
+    let text = "a,b,c,y\n1,2,3,4\n5,6,7,8\n";
+    let mut options = CsvOptions::new(LabelEncoding::Numeric);
+    options.features = Some(vec![Column::Name("c".to_string()), Column::Index(0)]);
+    options.targets = vec![Column::Name("y".to_string()), Column::Index(1)];
+    let data = parse_csv(text, "table", &options).unwrap();
+    assert_eq!(data.feature_names, ["c", "a"]);
+    assert_eq!(data.target_names, ["y", "b"]);
+    assert_eq!(data.dataset.inputs, [[3.0, 1.0], [7.0, 5.0]]);
+    assert_eq!(data.dataset.targets, [[4.0, 2.0], [8.0, 6.0]]);
+}
+
+#[test]
+fn encodes_labels_one_hot() {
+    let text = "x,label\n0.1,b\n0.2,a\n0.3,c\n";
+    let data = parse_csv(text, "letters", &CsvOptions::new(LabelEncoding::OneHot)).unwrap();
+    assert_eq!(data.classes, ["a", "b", "c"]);
+    assert_eq!(data.dataset.targets, [[0.0, 1.0, 0.0], [1.0, 0.0, 0.0], [0.0, 0.0, 1.0]]);
+}
+
+#[test]
+fn handles_missing_values() {
+    let text = "x1,x2,y\n1,NA,1\n3,4,2\n5,8,?\n";
+    let mut options = CsvOptions::new(LabelEncoding::Numeric);
+    let err = parse_csv(text, "gaps", &options).unwrap_err();
+    assert_eq!(err.to_string(), "line 2: missing value in column \"x2\"");
+
+    // The row with a missing target is always dropped, so the mean is 4
+    options.missing = MissingValues::Mean;
+    let data = parse_csv(text, "gaps", &options).unwrap();
+    assert_eq!(data.dataset.inputs, [[1.0, 4.0], [3.0, 4.0]]);
+
+    options.missing = MissingValues::Drop;
+    let data = parse_csv(text, "gaps", &options).unwrap();
+    assert_eq!(data.dataset.inputs, [[3.0, 4.0]]);
+}
+
+#[test]
+fn reads_files_without_header() {
+    let text = "1;2;0\n3;4;1\n";
+    let data = parse_csv(text, "plain", &CsvOptions::new(LabelEncoding::Index)).unwrap();
+    assert_eq!(data.feature_names, ["column_0", "column_1"]);
+    assert_eq!(data.dataset.inputs, [[1.0, 2.0], [3.0, 4.0]]);
+    assert_eq!(data.dataset.targets, [[0.0], [1.0]]);
+}
+
+#[test]
+fn rejects_rows_of_the_wrong_width() {
+    let text = "x,y,label\n1,2,a\n3,b\n";
+    let err = parse_csv(text, "short", &CsvOptions::new(LabelEncoding::Index)).unwrap_err();
+    assert_eq!(err.to_string(), "line 3: expected 3 fields, found 2");
+}
+
+#[test]
+fn inputs_pick_feature_columns_by_header_name() {
+    let text = "b,label,a\n2,x,1\n4,y,3\n";
+    let mut options = CsvOptions::new(LabelEncoding::Numeric);
+    options.features = Some(vec![Column::Name("a".to_string()), Column::Name("b".to_string())]);
+    let (names, inputs) = parse_inputs(text, &options).unwrap();
+    assert_eq!(names.unwrap(), ["a", "b"]);
+    assert_eq!(inputs, [[1.0, 2.0], [3.0, 4.0]]);
+}
+
+#[test]
+fn inputs_follow_the_header_option() {
+    // Column names that look like numbers: only an explicit header takes them out
+    let text = "1,2\n3,4\n";
+    let mut options = CsvOptions::new(LabelEncoding::Numeric);
+    assert_eq!(parse_inputs(text, &options).unwrap(), (None, vec![vec![1.0, 2.0], vec![3.0, 4.0]]));
+    options.has_header = Some(true);
+    let (names, inputs) = parse_inputs(text, &options).unwrap();
+    assert_eq!(names.unwrap(), ["1", "2"]);
+    assert_eq!(inputs, [[3.0, 4.0]]);
+}
+
+#[test]
+fn inputs_of_the_wrong_width_are_rejected_with_their_line() {
+    let err = parse_inputs("1,2\n3\n", &CsvOptions::new(LabelEncoding::Numeric)).unwrap_err();
+    assert_eq!(err.to_string(), "line 2: expected 2 fields, found 1");
+}
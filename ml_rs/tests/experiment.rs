@@ -0,0 +1,68 @@
+// Experiments that cannot run fail on their own instead of taking the batch down.
+
+use ml_lib::experiment::{parse_configs, run_experiment, run_experiments, ExperimentError};
+
+#[test]
+fn empty_training_split_is_invalid() {
+    let configs = parse_configs(
+        r#"
+        name = "All held out"
+        dataset = { generator = "linear_simple", num_samples = 3 }
+        model = { type = "linear" }
+        training = { iterations = 10, alpha = 0.1, validation = 0.9 }
+        "#,
+        "held_out.toml",
+    )
+    .unwrap();
+    match run_experiment(&configs[0]) {
+        Err(ExperimentError::Invalid(message)) => assert!(message.contains("no training samples"), "{}", message),
+        other => panic!("expected an error, got {:?}", other.map(|r| r.name)),
+    }
+}
+
+#[test]
+fn invalid_dataset_param_fails_alone() {
+    let configs = parse_configs(
+        r#"
+        [[experiment]]
+        name = "One arm"
+        dataset = { generator = "spirals", num_samples = 10, param = 1 }
+        model = { type = "linear" }
+        training = { iterations = 10, alpha = 0.1 }
+
+        [[experiment]]
+        name = "Linear Simple"
+        dataset = { generator = "linear_simple", num_samples = 3 }
+        model = { type = "linear" }
+        training = { iterations = 10, alpha = 0.1 }
+        "#,
+        "mixed.toml",
+    )
+    .unwrap();
+    let results = run_experiments(&configs);
+    match &results[0] {
+        Err(ExperimentError::Invalid(message)) => assert!(message.contains("at least 2"), "{}", message),
+        other => panic!("expected an error, got {:?}", other.as_ref().map(|r| &r.name)),
+    }
+    assert!(results[1].is_ok());
+}
+
+#[test]
+fn dataset_params_are_checked() {
+    for dataset in [
+        r#"{ generator = "blobs", num_samples = 10, param = 2.5 }"#,
+        r#"{ generator = "checkerboard", num_samples = 10, param = -1 }"#,
+        r#"{ generator = "checkerboard", num_samples = 10, param = 0 }"#,
+        r#"{ generator = "concentric_circles", num_samples = 10, param = 1 }"#,
+    ] {
+        let text = format!(
+            "name = \"Bad param\"\ndataset = {}\nmodel = {{ type = \"linear\" }}\ntraining = {{ iterations = 10, alpha = 0.1 }}\n",
+            dataset
+        );
+        let configs = parse_configs(&text, "bad_param.toml").unwrap();
+        match run_experiment(&configs[0]) {
+            Err(ExperimentError::Invalid(message)) => assert!(message.contains("dataset.param"), "{}", message),
+            other => panic!("expected an error for {}, got {:?}", dataset, other.map(|r| r.name)),
+        }
+    }
+}
@@ -0,0 +1,62 @@
+use ml_lib::gradient_check::{check_gradients, sample_loss};
+use ml_lib::naive_multi_layer_perceptron::MyMLP;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+const EPSILON: f64 = 1e-5;
+const TOLERANCE: f64 = 1e-5;
+
+/// 1 to 4 weight layers of 1 to 6 neurons
+fn random_architecture(rng: &mut StdRng) -> Vec<usize> {
+    let num_layers = rng.gen_range(2..=5);
+    (0..num_layers).map(|_| rng.gen_range(1..=6)).collect()
+}
+
+fn random_vec(rng: &mut StdRng, len: usize) -> Vec<f64> {
+    (0..len).map(|_| rng.gen_range(-1.0..1.0)).collect()
+}
+
+#[test]
+fn backpropagation_matches_finite_differences() {
+    let mut rng = StdRng::seed_from_u64(0);
+    for case in 0..40 {
+        let npl = random_architecture(&mut rng);
+        let mlp = MyMLP::with_seed(&npl, case);
+        let inputs = random_vec(&mut rng, npl[0]);
+        let expected = random_vec(&mut rng, npl[npl.len() - 1]);
+
+        for is_classification in [true, false] {
+            let check = check_gradients(&mlp, &inputs, &expected, is_classification, EPSILON);
+            assert_eq!(check.weights.len(), mlp.num_parameters());
+            assert_eq!(check.max_relative_error_per_layer().len(), npl.len() - 1);
+            assert!(
+                check.passes(TOLERANCE),
+                "{:?} (classification: {}): {:?}",
+                npl,
+                is_classification,
+                check.worst()
+            );
+        }
+    }
+}
+
+#[test]
+fn leaves_the_network_unchanged() {
+    let mut mlp = MyMLP::with_seed(&[3, 4, 2], 7);
+    let inputs = [0.5, -0.2, 0.9];
+    let expected = [1.0, -1.0];
+    let before = sample_loss(&mut mlp, &inputs, &expected, true);
+    check_gradients(&mlp, &inputs, &expected, true, EPSILON);
+    assert_eq!(sample_loss(&mut mlp, &inputs, &expected, true), before);
+}
+
+#[test]
+fn gradient_step_lowers_the_loss() {
+    // Sanity check of the sign: a small training step goes down the checked gradient
+    let mut mlp = MyMLP::with_seed(&[2, 3, 1], 3);
+    let inputs = [0.3, -0.7];
+    let expected = [0.8];
+    let before = sample_loss(&mut mlp, &inputs, &expected, false);
+    mlp.train_sample(&inputs, &expected, false, 1e-3);
+    assert!(sample_loss(&mut mlp, &inputs, &expected, false) < before);
+}
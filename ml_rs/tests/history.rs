@@ -0,0 +1,33 @@
+use ml_lib::history::TrainingHistory;
+use ml_lib::plot::{ChartOptions, PlotError};
+
+fn history() -> TrainingHistory {
+    let mut history = TrainingHistory::new();
+    for it in 0..5 {
+        let loss = 1.0 / (it + 1) as f64;
+        history.push(it * 10, &[("train_loss".to_string(), loss), ("validation_loss".to_string(), 1.2 * loss)]);
+    }
+    history
+}
+
+#[test]
+fn charts_are_written_as_png_and_svg() {
+    let dir = std::env::temp_dir();
+    let options = ChartOptions::default();
+    for file in ["ml_rs_history_chart.png", "ml_rs_history_chart.svg"] {
+        let path = dir.join(file);
+        history().plot(&path, &[], &options).unwrap();
+        assert!(path.exists());
+    }
+    let png = image::open(dir.join("ml_rs_history_chart.png")).unwrap();
+    assert_eq!((png.width(), png.height()), (options.width, options.height));
+}
+
+#[test]
+fn unknown_series_is_an_error() {
+    let path = std::env::temp_dir().join("ml_rs_history_unknown.svg");
+    match history().plot(&path, &["train_loss", "accuracy"], &ChartOptions::default()) {
+        Err(PlotError::UnknownSeries(name)) => assert_eq!(name, "accuracy"),
+        other => panic!("expected an unknown series error, got {:?}", other),
+    }
+}
@@ -0,0 +1,86 @@
+use ml_lib::csv_loader::LabelEncoding;
+use ml_lib::idx::{load_mnist, parse_idx, read_idx, IdxError, Normalization};
+
+const IMAGES: &str = "tests/fixtures/tiny-images-idx3-ubyte";
+const LABELS: &str = "tests/fixtures/tiny-labels-idx1-ubyte";
+
+#[test]
+fn reads_raw_images() {
+    let images = read_idx(IMAGES).unwrap();
+    assert_eq!(images.dims, vec![3, 2, 3]);
+    assert_eq!(images.item_size(), 6);
+    assert_eq!(images.item(0), &[0.0, 128.0, 255.0, 64.0, 32.0, 16.0]);
+    assert_eq!(images.item(2), &[10.0, 20.0, 30.0, 40.0, 50.0, 60.0]);
+}
+
+#[test]
+fn gzip_matches_raw() {
+    let raw = read_idx(IMAGES).unwrap();
+    let gz = read_idx(format!("{}.gz", IMAGES)).unwrap();
+    assert_eq!(raw.dims, gz.dims);
+    assert_eq!(raw.data, gz.data);
+
+    let labels = read_idx(format!("{}.gz", LABELS)).unwrap();
+    assert_eq!(labels.dims, vec![3]);
+    assert_eq!(labels.data, vec![7.0, 0.0, 9.0]);
+}
+
+#[test]
+fn normalises_and_flattens() {
+    let zero_one = load_mnist(IMAGES, LABELS, Normalization::ZeroOne, LabelEncoding::Index).unwrap();
+    assert_eq!(zero_one.len(), 3);
+    assert_eq!(zero_one.input_dim(), 6);
+    assert_eq!(zero_one.inputs[1], vec![1.0, 1.0, 1.0, 0.0, 0.0, 0.0]);
+    assert_eq!(zero_one.targets, vec![vec![7.0], vec![0.0], vec![9.0]]);
+
+    let signed = load_mnist(IMAGES, LABELS, Normalization::MinusOneOne, LabelEncoding::Index).unwrap();
+    assert_eq!(signed.inputs[0][0], -1.0);
+    assert_eq!(signed.inputs[0][2], 1.0);
+    assert!(signed.inputs.iter().flatten().all(|v| (-1.0..=1.0).contains(v)));
+}
+
+#[test]
+fn encodes_labels() {
+    let one_hot = load_mnist(IMAGES, LABELS, Normalization::None, LabelEncoding::OneHot).unwrap();
+    assert_eq!(one_hot.metadata.num_classes, Some(10));
+    assert_eq!(one_hot.output_dim(), 10);
+    assert_eq!(one_hot.targets[0][7], 1.0);
+    assert_eq!(one_hot.targets[0].iter().sum::<f64>(), 1.0);
+
+    let bipolar = load_mnist(IMAGES, LABELS, Normalization::None, LabelEncoding::Bipolar).unwrap();
+    assert_eq!(bipolar.targets[1][0], 1.0);
+    assert_eq!(bipolar.targets[1][1..], [-1.0; 9]);
+}
+
+#[test]
+fn rejects_truncated_file() {
+    match read_idx("tests/fixtures/truncated-images-idx3-ubyte") {
+        Err(IdxError::Format(_)) => {}
+        other => panic!("expected a format error, got {:?}", other.map(|a| a.dims)),
+    }
+}
+
+#[test]
+fn rejects_one_dimensional_images() {
+    // The labels file is read as images: 1-D, so it is refused
+    assert!(load_mnist(LABELS, LABELS, Normalization::None, LabelEncoding::Index).is_err());
+}
+
+#[test]
+fn rejects_mismatched_counts() {
+    // 3 images, 2 labels
+    match load_mnist(IMAGES, "tests/fixtures/short-labels-idx1-ubyte", Normalization::None, LabelEncoding::Index) {
+        Err(IdxError::Format(_)) => {}
+        other => panic!("expected a format error, got {:?}", other.map(|d| d.len())),
+    }
+}
+
+#[test]
+fn rejects_overflowing_dimensions() {
+    // u8 values, 3 dimensions of 2^32 - 1
+    let mut bytes = vec![0, 0, 0x08, 3];
+    for _ in 0..3 {
+        bytes.extend_from_slice(&u32::MAX.to_be_bytes());
+    }
+    assert!(matches!(parse_idx(&bytes), Err(IdxError::Format(_))));
+}
@@ -0,0 +1,80 @@
+use ml_lib::csv_loader::LabelEncoding;
+use ml_lib::metrics::{
+    accuracy, classification_report, confusion_matrix, f1_score, r2_score, roc_auc, to_bipolar, Average,
+};
+
+fn close(a: f64, b: f64) -> bool {
+    (a - b).abs() < 1e-12
+}
+
+/// ±1 row with the `class` output high
+fn one_hot(class: usize) -> Vec<f64> {
+    (0..3).map(|c| if c == class { 1.0 } else { -1.0 }).collect()
+}
+
+/// True classes 0 0 1 1 2 2, predicted 0 1 1 1 2 0
+fn three_classes() -> (Vec<Vec<f64>>, Vec<Vec<f64>>) {
+    let predictions = [0, 1, 1, 1, 2, 0].into_iter().map(one_hot).collect();
+    let targets = [0, 0, 1, 1, 2, 2].into_iter().map(one_hot).collect();
+    (predictions, targets)
+}
+
+#[test]
+fn accuracy_cuts_single_outputs_at_zero() {
+    let predictions = [vec![0.8], vec![-0.3], vec![0.2], vec![-0.9]];
+    let targets = [vec![1.0], vec![1.0], vec![-1.0], vec![-1.0]];
+    assert_eq!(accuracy(&predictions, &targets), 0.5);
+}
+
+#[test]
+fn confusion_matrix_counts_true_against_predicted() {
+    let (predictions, targets) = three_classes();
+    assert_eq!(confusion_matrix(&predictions, &targets), [[1, 1, 0], [0, 2, 0], [1, 0, 1]]);
+}
+
+#[test]
+#[should_panic(expected = "same width")]
+fn confusion_matrix_rejects_mismatched_widths() {
+    confusion_matrix(&[vec![0.5], vec![-0.5]], &[one_hot(0), one_hot(1)]);
+}
+
+#[test]
+fn macro_and_micro_f1() {
+    let (predictions, targets) = three_classes();
+    // Per class F1: 1/2, 4/5 (precision 2/3, recall 1), 2/3 (precision 1, recall 1/2)
+    let report = classification_report(&predictions, &targets);
+    let f1: Vec<f64> = report.per_class.iter().map(|c| c.f1).collect();
+    assert!(close(f1[0], 0.5) && close(f1[1], 0.8) && close(f1[2], 2.0 / 3.0));
+    assert!(close(f1_score(&predictions, &targets, Average::Macro), (0.5 + 0.8 + 2.0 / 3.0) / 3.0));
+    // Micro F1 is the accuracy for single-label data
+    assert!(close(f1_score(&predictions, &targets, Average::Micro), 4.0 / 6.0));
+}
+
+#[test]
+fn roc_auc_is_the_fraction_of_ordered_pairs() {
+    // Positives scored 0.9 and 0.7, negatives 0.8 and 0.6: 3 of the 4 pairs are ordered
+    let predictions = [vec![0.9], vec![0.8], vec![0.7], vec![0.6]];
+    let targets = [vec![1.0], vec![-1.0], vec![1.0], vec![-1.0]];
+    assert!(close(roc_auc(&predictions, &targets), 0.75));
+}
+
+#[test]
+fn r2_compares_residuals_with_the_target_variance() {
+    // Sum of squares: residuals 0.5, targets around their mean 2.5: 5
+    let predictions = [vec![1.5], vec![2.0], vec![2.5], vec![4.0]];
+    let targets = [vec![1.0], vec![2.0], vec![3.0], vec![4.0]];
+    assert!(close(r2_score(&predictions, &targets), 0.9));
+}
+
+#[test]
+fn to_bipolar_maps_the_unit_scale() {
+    let predictions = [vec![0.0], vec![0.75]];
+    assert_eq!(to_bipolar(&predictions, LabelEncoding::Index, 2), [[-1.0], [0.5]]);
+    assert_eq!(to_bipolar(&predictions, LabelEncoding::Bipolar, 2), predictions);
+}
+
+#[test]
+#[should_panic(expected = "two classes")]
+fn to_bipolar_rejects_indices_of_more_classes() {
+    to_bipolar(&[vec![2.0]], LabelEncoding::Index, 3);
+}
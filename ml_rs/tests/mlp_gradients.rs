@@ -0,0 +1,72 @@
+use ml_lib::gradient_check::sample_loss;
+use ml_lib::naive_multi_layer_perceptron::MyMLP;
+
+const INPUTS: [f64; 3] = [0.4, -0.8, 0.1];
+const EXPECTED: [f64; 2] = [1.0, -1.0];
+
+fn output_grad(outputs: &[f64], expected: &[f64]) -> Vec<f64> {
+    outputs.iter().zip(expected.iter()).map(|(o, e)| o - e).collect()
+}
+
+#[test]
+fn backward_leaves_the_weights_unchanged() {
+    let mut mlp = MyMLP::with_seed(&[3, 4, 2], 0);
+    let outputs = mlp.forward(&INPUTS, true);
+    let gradients = mlp.backward(&output_grad(&outputs, &EXPECTED));
+    assert!(gradients.weights.iter().flatten().flatten().any(|&g| g != 0.0));
+    assert_eq!(mlp.forward(&INPUTS, true), outputs);
+}
+
+#[test]
+fn input_gradients_match_finite_differences() {
+    let epsilon = 1e-5;
+    for is_classification in [true, false] {
+        let mut mlp = MyMLP::with_seed(&[3, 5, 4, 2], 1);
+        let outputs = mlp.forward(&INPUTS, is_classification);
+        let gradients = mlp.backward(&output_grad(&outputs, &EXPECTED));
+
+        for i in 0..INPUTS.len() {
+            let (mut plus, mut minus) = (INPUTS, INPUTS);
+            plus[i] += epsilon;
+            minus[i] -= epsilon;
+            let numerical = (sample_loss(&mut mlp, &plus, &EXPECTED, is_classification)
+                - sample_loss(&mut mlp, &minus, &EXPECTED, is_classification))
+                / (2.0 * epsilon);
+            assert!((gradients.inputs[i] - numerical).abs() < 1e-8, "input {}", i);
+        }
+    }
+}
+
+#[test]
+fn train_sample_is_forward_backward_apply() {
+    let mut trained = MyMLP::with_seed(&[3, 4, 2], 2);
+    let mut manual = trained.clone();
+
+    trained.train_sample(&INPUTS, &EXPECTED, true, 0.1);
+    let outputs = manual.forward(&INPUTS, true);
+    let gradients = manual.backward(&output_grad(&outputs, &EXPECTED));
+    manual.apply_gradients(&gradients, 0.1);
+
+    assert_eq!(trained.forward(&INPUTS, true), manual.forward(&INPUTS, true));
+}
+
+#[test]
+fn combined_gradients_follow_the_summed_loss() {
+    // Two samples in one step: the mini-batch loss goes down
+    let mut mlp = MyMLP::with_seed(&[3, 4, 2], 3);
+    let other_inputs = [-0.5, 0.2, 0.9];
+    let other_expected = [-1.0, 1.0];
+    let batch_loss = |mlp: &mut MyMLP| {
+        sample_loss(mlp, &INPUTS, &EXPECTED, true) + sample_loss(mlp, &other_inputs, &other_expected, true)
+    };
+    let before = batch_loss(&mut mlp);
+
+    let outputs = mlp.forward(&INPUTS, true);
+    let mut gradients = mlp.backward(&output_grad(&outputs, &EXPECTED));
+    let outputs = mlp.forward(&other_inputs, true);
+    gradients.add(&mlp.backward(&output_grad(&outputs, &other_expected)));
+    gradients.scale(0.5);
+    mlp.apply_gradients(&gradients, 0.01);
+
+    assert!(batch_loss(&mut mlp) < before);
+}
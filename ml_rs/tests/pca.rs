@@ -0,0 +1,66 @@
+use ml_lib::pca::{NumComponents, PcaSolver, PCA};
+
+/// Correlated 3-D cloud with a different spread along each axis
+fn cloud() -> Vec<Vec<f64>> {
+    (0..60)
+        .map(|i| {
+            let t = i as f64 / 10.0 - 3.0;
+            vec![t, 0.5 * t + 0.4 * (3.0 * i as f64).sin(), 0.2 * (7.0 * i as f64).cos() - 0.1 * t]
+        })
+        .collect()
+}
+
+fn fitted(solver: PcaSolver, whiten: bool) -> PCA {
+    let mut pca = PCA::new(NumComponents::Count(3), solver, whiten);
+    pca.fit(&cloud());
+    pca
+}
+
+fn assert_close(a: &[f64], b: &[f64], tolerance: f64) {
+    assert_eq!(a.len(), b.len());
+    for (x, y) in a.iter().zip(b) {
+        assert!((x - y).abs() < tolerance, "{a:?} != {b:?}");
+    }
+}
+
+#[test]
+fn covariance_and_svd_solvers_agree() {
+    let covariance = fitted(PcaSolver::Covariance, false);
+    let svd = fitted(PcaSolver::Svd, false);
+    assert_close(covariance.explained_variance(), svd.explained_variance(), 1e-9);
+    assert_close(covariance.explained_variance_ratio(), svd.explained_variance_ratio(), 1e-9);
+    for (a, b) in covariance.components().iter().zip(svd.components()) {
+        assert_close(a, b, 1e-6);
+    }
+    for x in cloud() {
+        assert_close(&covariance.transform(&x), &svd.transform(&x), 1e-6);
+    }
+}
+
+#[test]
+fn full_rank_inverse_transform_gives_the_input_back() {
+    for solver in [PcaSolver::Covariance, PcaSolver::Svd] {
+        for whiten in [false, true] {
+            let pca = fitted(solver, whiten);
+            for x in cloud() {
+                assert_close(&pca.inverse_transform(&pca.transform(&x)), &x, 1e-9);
+            }
+        }
+    }
+}
+
+#[test]
+fn whitened_projections_have_unit_variance() {
+    let data = cloud();
+    let n = data.len() as f64;
+    for solver in [PcaSolver::Covariance, PcaSolver::Svd] {
+        let pca = fitted(solver, true);
+        let projected: Vec<Vec<f64>> = data.iter().map(|x| pca.transform(x)).collect();
+        for k in 0..3 {
+            let mean = projected.iter().map(|z| z[k]).sum::<f64>() / n;
+            let variance = projected.iter().map(|z| (z[k] - mean).powi(2)).sum::<f64>() / (n - 1.0);
+            assert!(mean.abs() < 1e-9);
+            assert!((variance - 1.0).abs() < 1e-9, "component {k}: variance {variance}");
+        }
+    }
+}
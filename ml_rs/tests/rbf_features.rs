@@ -0,0 +1,34 @@
+use ml_lib::datasets;
+use ml_lib::features::{ExpandedPerceptron, FeatureMap, RbfFeatures};
+use ml_lib::kmeans::KMeans;
+use ml_lib::metrics::accuracy;
+
+#[test]
+fn features_peak_at_the_centres() {
+    let rbf = RbfFeatures::new(vec![vec![0.0, 0.0], vec![1.0, 1.0]], 2.0);
+    let z = rbf.transform(&[0.0, 0.0]);
+    assert_eq!(z[0], 1.0);
+    assert!((z[1] - (-4.0f64).exp()).abs() < 1e-12);
+}
+
+#[test]
+fn kmeans_centres_make_cross_linearly_separable() {
+    // A linear model alone stays near 50 % on Cross
+    let data = datasets::cross(0, 500, 0.0);
+    let mut kmeans = KMeans::new(16, Some(0));
+    kmeans.fit(&data.inputs, 100, 1e-6, 3);
+
+    let rbf = RbfFeatures::from_kmeans(&kmeans, 8.0);
+    assert_eq!(rbf.centres(), kmeans.centroids());
+    let mut model = ExpandedPerceptron::new(rbf, 2);
+    model.train_classification(&data.inputs, &data.targets, 200_000, 0.05);
+
+    let predictions: Vec<Vec<f64>> = data.inputs.iter().map(|x| vec![model.predict_raw(x)]).collect();
+    assert!(accuracy(&predictions, &data.targets) > 0.85);
+}
+
+#[test]
+#[should_panic(expected = "fitted")]
+fn transform_needs_a_fitted_model() {
+    KMeans::new(2, Some(0)).transform(&[0.0, 0.0]);
+}
@@ -0,0 +1,35 @@
+use ml_lib::search::{ParamValue, Params, SearchResults, Trial};
+
+fn trial(id: usize, kernel: &str, score: f64) -> Trial {
+    Trial {
+        id,
+        params: Params::default().set("kernel", ParamValue::Text(kernel.to_string())),
+        rung: 0,
+        budget: None,
+        score,
+        score_std: 0.0,
+        seconds: 0.0,
+    }
+}
+
+#[test]
+fn best_is_none_without_trials() {
+    let results = SearchResults { trials: Vec::new(), maximize: true };
+    assert!(results.best().is_none());
+}
+
+#[test]
+fn best_follows_the_direction_of_the_score() {
+    let trials = vec![trial(0, "rbf", 0.8), trial(1, "linear", 0.6)];
+    let maximized = SearchResults { trials: trials.clone(), maximize: true };
+    assert_eq!(maximized.best().map(|t| t.id), Some(0));
+    let minimized = SearchResults { trials, maximize: false };
+    assert_eq!(minimized.best().map(|t| t.id), Some(1));
+}
+
+#[test]
+fn csv_doubles_embedded_quotes() {
+    let results = SearchResults { trials: vec![trial(0, "say \"hi\"", 0.5)], maximize: true };
+    let csv = results.to_csv();
+    assert_eq!(csv.lines().nth(1), Some("0,0,0.5,0,0.000,\"say \"\"hi\"\"\""));
+}
@@ -0,0 +1,78 @@
+// SVM behaviour on the course cases: kernels, hard-margin support vectors and the two
+// multi-class strategies.
+
+use ml_lib::datasets;
+use ml_lib::split::class_labels;
+use ml_lib::svm::{BinarySVM, Kernel, MultiClassSVM, MultiClassStrategy};
+
+#[test]
+fn kernels_separate_xor() {
+    let data = datasets::xor(0, 4, 0.0);
+    let expected: Vec<f64> = data.targets.iter().map(|t| if t[0] > 0.0 { 1.0 } else { -1.0 }).collect();
+    let kernels = [
+        Kernel::Rbf { gamma: 1.0 },
+        Kernel::Polynomial { degree: 2, gamma: 1.0, coef0: 1.0 },
+    ];
+    for kernel in kernels {
+        let mut svm = BinarySVM::new(kernel, 10.0);
+        svm.train(&data.inputs, &data.targets, 10_000, 1e-6);
+        let predicted: Vec<f64> = data.inputs.iter().map(|x| svm.predict_class(x)).collect();
+        assert_eq!(predicted, expected, "{:?}", kernel);
+    }
+}
+
+#[test]
+fn hard_margin_support_vectors_on_linear_simple() {
+    // (1, 1) against the segment (2, 3) - (3, 3): the closest pair is (1, 1) and (2, 3),
+    // so w = 2 (-1, -2) / 5, b = 2.2 and the margin is sqrt(5)
+    let data = datasets::linear_simple(0, 3, 0.0);
+    let mut svm = BinarySVM::hard_margin(Kernel::Linear);
+    svm.train(&data.inputs, &data.targets, 10_000, 1e-9);
+
+    let mut support = svm.support_vectors().to_vec();
+    support.sort_by(|a, b| a[0].total_cmp(&b[0]));
+    assert_eq!(support, [[1.0, 1.0], [2.0, 3.0]]);
+    let w = svm.weights().unwrap();
+    assert!((w[0] + 0.4).abs() < 1e-6 && (w[1] + 0.8).abs() < 1e-6, "{:?}", w);
+    assert!((svm.bias() - 2.2).abs() < 1e-6, "{}", svm.bias());
+    assert!((svm.margin() - 5f64.sqrt()).abs() < 1e-6);
+}
+
+#[test]
+fn both_strategies_learn_three_classes() {
+    let data = datasets::three_classes(0, 300, 0.0);
+    let labels = class_labels(&data.targets);
+    for strategy in [MultiClassStrategy::OneVsRest, MultiClassStrategy::OneVsOne] {
+        let mut svm = MultiClassSVM::new(3, Kernel::Linear, 10.0, strategy);
+        svm.train(&data.inputs, &data.targets, 100_000, 1e-4);
+        let correct = data
+            .inputs
+            .iter()
+            .zip(&labels)
+            .filter(|(x, &l)| svm.predict_class_index(x) == l)
+            .count();
+        let accuracy = correct as f64 / data.len() as f64;
+        assert!(accuracy > 0.97, "{:?}: {}", strategy, accuracy);
+    }
+}
+
+#[test]
+fn one_vs_one_skips_pairs_without_samples() {
+    // Classes 2 and 3 never occur: their pair has nothing to learn and must not vote
+    let inputs = vec![vec![0.0], vec![1.0], vec![4.0], vec![5.0]];
+    let targets = vec![
+        vec![1.0, -1.0, -1.0, -1.0],
+        vec![1.0, -1.0, -1.0, -1.0],
+        vec![-1.0, 1.0, -1.0, -1.0],
+        vec![-1.0, 1.0, -1.0, -1.0],
+    ];
+    let mut svm = MultiClassSVM::new(4, Kernel::Linear, 1.0, MultiClassStrategy::OneVsOne);
+    svm.train(&inputs, &targets, 10_000, 1e-6);
+    for x in &inputs {
+        let votes = svm.decision_function(x);
+        assert_eq!(votes.iter().sum::<f64>(), 5.0, "{:?}", votes);
+        assert_eq!(votes[3], 0.0);
+    }
+    assert_eq!(svm.predict_class_index(&[0.5]), 0);
+    assert_eq!(svm.predict_class_index(&[4.5]), 1);
+}
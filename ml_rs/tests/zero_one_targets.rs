@@ -0,0 +1,87 @@
+// Single-value targets, 0 / 1 (as `datasets::xor` gives) or class indices, keep one
+// class per value for every classifier, and predictions come back as those values.
+
+use ml_lib::datasets;
+use ml_lib::decision_tree::{Criterion, DecisionTree, TreeParams};
+use ml_lib::knn::{Algorithm, Distance, KNNClassifier, Weighting};
+use ml_lib::metrics::{accuracy, log_loss, to_bipolar};
+use ml_lib::random_forest::RandomForest;
+
+/// Three classes as indices, one per band of x
+fn index_targets() -> (Vec<Vec<f64>>, Vec<Vec<f64>>) {
+    let inputs = (0..9).map(|i| vec![i as f64]).collect();
+    let targets = (0..9).map(|i| vec![(i / 3) as f64]).collect();
+    (inputs, targets)
+}
+
+#[test]
+fn knn_keeps_both_classes() {
+    let data = datasets::xor(0, 4, 0.0);
+    let mut knn = KNNClassifier::new(1, Distance::Euclidean, Weighting::Uniform, Algorithm::BruteForce);
+    knn.fit(&data.inputs, &data.targets);
+    let predicted: Vec<Vec<f64>> = data.inputs.iter().map(|x| knn.predict(x)).collect();
+    assert_eq!(predicted, data.targets);
+}
+
+#[test]
+fn tree_keeps_both_classes() {
+    // Not XOR: its first split lowers no impurity, so a greedy tree stays a leaf
+    let inputs = vec![vec![0.0], vec![1.0], vec![2.0], vec![3.0]];
+    let targets = vec![vec![0.0], vec![0.0], vec![1.0], vec![1.0]];
+    let mut tree = DecisionTree::new(Criterion::Gini, TreeParams::default());
+    tree.fit(&inputs, &targets);
+    let predicted: Vec<Vec<f64>> = inputs.iter().map(|x| tree.predict(x)).collect();
+    assert_eq!(predicted, targets);
+}
+
+#[test]
+fn knn_keeps_index_classes_apart() {
+    let (inputs, targets) = index_targets();
+    let mut knn = KNNClassifier::new(1, Distance::Euclidean, Weighting::Uniform, Algorithm::KdTree { leaf_size: 2 });
+    knn.fit(&inputs, &targets);
+    assert_eq!(knn.predict_scores(&[4.0]).len(), 3);
+    let predicted: Vec<Vec<f64>> = inputs.iter().map(|x| knn.predict(x)).collect();
+    assert_eq!(predicted, targets);
+}
+
+#[test]
+fn tree_and_forest_keep_index_classes_apart() {
+    let (inputs, targets) = index_targets();
+    let mut tree = DecisionTree::new(Criterion::Gini, TreeParams::default());
+    tree.fit(&inputs, &targets);
+    let predicted: Vec<Vec<f64>> = inputs.iter().map(|x| tree.predict(x)).collect();
+    assert_eq!(predicted, targets);
+
+    let mut forest = RandomForest::new(20, Criterion::Entropy, TreeParams::default(), None, Some(0));
+    forest.fit(&inputs, &targets);
+    assert_eq!(forest.predict_proba(&[4.0]).len(), 3);
+    assert_eq!(forest.predict(&[4.0]), [1.0]);
+    assert_eq!(forest.predict(&[8.0]), [2.0]);
+}
+
+#[test]
+fn forest_importances_sum_to_one_with_unsplit_trees() {
+    // 3 samples: many bootstrap draws hold a single class, those trees never split
+    let data = datasets::linear_simple(0, 3, 0.0);
+    let mut forest = RandomForest::new(20, Criterion::Gini, TreeParams::default(), None, Some(0));
+    forest.fit(&data.inputs, &data.targets);
+    assert!(forest.trees().iter().any(|t| t.feature_importances().iter().sum::<f64>() == 0.0));
+    assert!((forest.feature_importances().iter().sum::<f64>() - 1.0).abs() < 1e-12);
+}
+
+#[test]
+fn single_outputs_are_read_on_the_bipolar_scale() {
+    // Every target is +1: a prediction of 0.3 is the positive class, whatever the batch holds
+    let targets = vec![vec![1.0], vec![1.0]];
+    assert_eq!(accuracy(&[vec![0.3], vec![0.9]], &targets), 1.0);
+}
+
+#[test]
+fn zero_one_predictions_go_through_the_dataset_encoding() {
+    let data = datasets::xor(0, 4, 0.0);
+    let predictions = vec![vec![0.1], vec![0.9], vec![0.8], vec![0.2]];
+    let encoding = data.metadata.label_encoding.expect("XOR is a classification dataset");
+    let bipolar = to_bipolar(&predictions, encoding, 2);
+    assert_eq!(accuracy(&bipolar, &data.targets), 1.0);
+    assert!(log_loss(&bipolar, &data.targets) < 0.25);
+}